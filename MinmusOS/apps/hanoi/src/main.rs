@@ -9,7 +9,7 @@
 
 use core::panic::PanicInfo;
 use lib;
-use lib::{print, println};
+use lib::{print, println, process};
 
 const STACK_SIZE: usize = 10;
 
@@ -112,7 +112,7 @@ fn print_stacks(towers: &HanoiTowers) {
 #[link_section = ".start"]
 pub extern "C" fn _start() {
     main();
-    loop {}
+    process::exit();
 }
 
 #[panic_handler]
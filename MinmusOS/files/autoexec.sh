@@ -0,0 +1,12 @@
+# MinmusOS shell startup script, run by the shell of every console
+
+HOSTNAME=MinmusOS
+
+# Prints the numbers from 1 to N, e.g. "count 5"
+count() {
+    i=1
+    while [ $i -le $1 ]; do
+        echo $i
+        i=$((i + 1))
+    done
+}
\ No newline at end of file
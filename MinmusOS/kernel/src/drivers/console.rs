@@ -0,0 +1,140 @@
+// Project Name:  MinmusOS
+// File Name:     console.rs
+// File Function: Virtual consoles
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::event::{EventQueue, MouseEvent, NULL_KEY_EVENT, NULL_MOUSE_EVENT};
+use crate::drivers::input::InputQueue;
+use crate::drivers::tty::{Tty, ICANON, ISIG};
+use crate::ipc::signal::{SIGINT, SIGNALS, SIGTSTP};
+use crate::multitasking::task::TASK_MANAGER;
+use crate::syscalls::print::Printer;
+use lib::keyboard::KeyEvent;
+
+pub const MAX_CONSOLES: usize = 6;
+
+pub static mut CONSOLES: ConsoleManager = ConsoleManager {
+    consoles: [NULL_CONSOLE; MAX_CONSOLES],
+    active: 0,
+};
+
+#[derive(Copy, Clone)]
+pub struct Console {
+    pub printer: Printer,
+    pub tty: Tty,
+    pub input: InputQueue,
+    pub events: EventQueue<KeyEvent>,
+    pub mouse: EventQueue<MouseEvent>,
+    pub foreground: i8,
+    pub interrupts: u32,
+}
+
+static NULL_CONSOLE: Console = Console {
+    printer: Printer::new(),
+    tty: Tty::new(),
+    input: InputQueue::new(),
+    events: EventQueue::new(NULL_KEY_EVENT),
+    mouse: EventQueue::new(NULL_MOUSE_EVENT),
+    foreground: -1,
+    interrupts: 0,
+};
+
+impl Console {
+    pub fn write(&mut self, buffer: &[u8]) -> i32 {
+        self.tty.write(&mut self.printer, buffer)
+    }
+
+    pub fn read(&mut self, buffer: &mut [u8]) -> i32 {
+        self.input.read(buffer, &self.tty)
+    }
+
+    pub fn key(&mut self, c: u8) {
+        self.printer.reset_view();
+        self.input.key(c, &mut self.tty, &mut self.printer);
+    }
+
+    pub fn keys(&mut self, sequence: &[u8]) {
+        self.printer.reset_view();
+        if !self.tty.is_set(ICANON) {
+            for &c in sequence.iter() {
+                self.input.key(c, &mut self.tty, &mut self.printer);
+            }
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: u32) -> i32 {
+        self.input.set_mode(mode, &mut self.tty)
+    }
+
+    pub fn interrupt(&mut self) {
+        if !self.tty.is_set(ISIG) {
+            self.key(0x03);
+            return;
+        }
+        self.printer.reset_view();
+        self.input.cancel(&mut self.tty, &mut self.printer);
+        self.interrupts = self.interrupts.wrapping_add(1);
+        if self.foreground >= 0 {
+            unsafe {
+                SIGNALS.send_group(self.foreground, SIGINT);
+            }
+        }
+    }
+
+    pub fn suspend(&mut self) {
+        if !self.tty.is_set(ISIG) {
+            self.key(0x1A);
+            return;
+        }
+        if self.foreground >= 0 {
+            self.printer.reset_view();
+            unsafe {
+                SIGNALS.send_group(self.foreground, SIGTSTP);
+            }
+        }
+    }
+}
+
+pub struct ConsoleManager {
+    consoles: [Console; MAX_CONSOLES],
+    active: usize,
+}
+
+impl ConsoleManager {
+    pub fn init(&mut self) {
+        self.consoles[self.active].printer.capture();
+    }
+
+    pub fn switch(&mut self, id: usize) {
+        if id >= MAX_CONSOLES || id == self.active {
+            return;
+        }
+        self.consoles[self.active].printer.hide();
+        self.active = id;
+        self.consoles[id].printer.show();
+    }
+
+    pub fn get_current_id(&self) -> usize {
+        let console: i8 = unsafe { TASK_MANAGER.get_current_console() };
+        if console < 0 { self.active } else { console as usize }
+    }
+
+    pub fn current(&mut self) -> &mut Console {
+        &mut self.consoles[self.get_current_id()]
+    }
+
+    pub fn active(&mut self) -> &mut Console {
+        &mut self.consoles[self.active]
+    }
+
+    pub fn printer(&mut self) -> &mut Printer {
+        &mut self.current().printer
+    }
+
+    pub fn set_default_colors(&mut self, fg_color: u8, bg_color: u8) {
+        for console in self.consoles.iter_mut() {
+            console.printer.set_default_colors(fg_color, bg_color);
+        }
+    }
+}
\ No newline at end of file
@@ -14,8 +14,11 @@ const LBA_HIGH_REGISTER: u16 = 0x1F5;
 const DRIVE_REGISTER: u16 = 0x1F6;
 const STATUS_COMMAND_REGISTER: u16 = 0x1F7;
 const READ_COMMAND: u8 = 0x20;
+const WRITE_COMMAND: u8 = 0x30;
+const FLUSH_COMMAND: u8 = 0xE7;
 const STATUS_BSY: u8 = 0b10000000;
 const STATUS_RDY: u8 = 0b01000000;
+const STATUS_DRQ: u8 = 0b00001000;
 
 pub static mut DISK: Disk = Disk {
     enabled: false
@@ -64,6 +67,49 @@ impl Disk {
         self.reset();
     }
 
+    pub fn write<T>(&self, source: *const T, lba: u64, sectors: u16) {
+        if !self.enabled {
+            lib::println!("[ERROR] Failed to Write Disk!");
+            return;
+        }
+
+        while self.is_busy() {}
+
+        unsafe {
+            asm!("out dx, al", in("dx") 0x3F6, in("al") 0b00000010u8);
+            asm!("out dx, al", in("dx") SECTOR_COUNT_REGISTER, in("al") sectors as u8);
+            asm!("out dx, al", in("dx") LBA_LOW_REGISTER, in("al") lba as u8);
+            asm!("out dx, al", in("dx") LBA_MID_REGISTER, in("al") (lba >> 8) as u8);
+            asm!("out dx, al", in("dx") LBA_HIGH_REGISTER, in("al") (lba >> 16) as u8);
+            asm!("out dx, al", in("dx") DRIVE_REGISTER, in("al") (0xE0 | ((lba >> 24) & 0xF)) as u8);
+            asm!("out dx, al", in("dx") STATUS_COMMAND_REGISTER, in("al") WRITE_COMMAND);
+        }
+
+        let mut sectors_left = sectors;
+        let mut source_pointer = source;
+
+        while sectors_left > 0 {
+            while self.is_busy() {}
+            while !self.is_requesting() {}
+            for _i in 0..128 {
+                unsafe {
+                    let buffer: u32 = core::ptr::read_unaligned(source_pointer as *const u32);
+                    asm!("out dx, eax", in("dx") DATA_REGISTER, in("eax") buffer);
+                    source_pointer = source_pointer.byte_add(4);
+                }
+            }
+            sectors_left -= 1;
+        }
+
+        while self.is_busy() {}
+        unsafe {
+            asm!("out dx, al", in("dx") STATUS_COMMAND_REGISTER, in("al") FLUSH_COMMAND);
+        }
+        while self.is_busy() {}
+
+        self.reset();
+    }
+
     pub fn is_busy(&self) -> bool {
         let status: u8;
         unsafe {
@@ -80,6 +126,14 @@ impl Disk {
         (status & STATUS_RDY) != 0
     }
 
+    pub fn is_requesting(&self) -> bool {
+        let status: u8;
+        unsafe {
+            asm!("in al, dx", out("al") status, in("dx") STATUS_COMMAND_REGISTER);
+        }
+        (status & STATUS_DRQ) != 0
+    }
+
     pub fn check(&mut self) {
         let status: u8;
         unsafe {
@@ -0,0 +1,98 @@
+// Project Name:  MinmusOS
+// File Name:     event.rs
+// File Function: Input event queues
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::multitasking::task::TASK_MANAGER;
+use core::ptr;
+use lib::errno::{EAGAIN, EINTR};
+use lib::keyboard::KeyEvent;
+
+const QUEUE_SIZE: usize = 64;
+
+pub static NULL_KEY_EVENT: KeyEvent = KeyEvent {
+    key: 0,
+    character: 0,
+    modifiers: 0,
+    flags: 0,
+};
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct MouseEvent {
+    pub x: u16,
+    pub y: u16,
+    pub dx: i16,
+    pub dy: i16,
+    pub wheel: i8,
+    pub buttons: u8,
+}
+
+pub static NULL_MOUSE_EVENT: MouseEvent = MouseEvent {
+    x: 0,
+    y: 0,
+    dx: 0,
+    dy: 0,
+    wheel: 0,
+    buttons: 0,
+};
+
+#[derive(Copy, Clone)]
+pub struct EventQueue<T: Copy> {
+    events: [T; QUEUE_SIZE],
+    head: usize,
+    count: usize,
+    reader: i8,
+}
+
+impl<T: Copy> EventQueue<T> {
+    pub const fn new(empty: T) -> Self {
+        EventQueue {
+            events: [empty; QUEUE_SIZE],
+            head: 0,
+            count: 0,
+            reader: -1,
+        }
+    }
+
+    pub fn push(&mut self, event: T) {
+        if self.reader < 0 || !unsafe { TASK_MANAGER.is_process_running(self.reader) } {
+            self.reader = -1;
+            return;
+        }
+        if self.count == QUEUE_SIZE {
+            self.head = (self.head + 1) % QUEUE_SIZE;
+            self.count -= 1;
+        }
+        self.events[(self.head + self.count) % QUEUE_SIZE] = event;
+        self.count += 1;
+    }
+
+    pub fn read(&mut self, event: &mut T, wait: bool) -> i32 {
+        let process: i8 = unsafe { TASK_MANAGER.get_current_process() };
+        if self.reader != process {
+            self.reader = process;
+            self.head = 0;
+            self.count = 0;
+        }
+        loop {
+            unsafe {
+                if TASK_MANAGER.is_interrupted() {
+                    return -EINTR;
+                }
+                if TASK_MANAGER.is_foreground() && ptr::read_volatile(&self.count) > 0 {
+                    break;
+                }
+                if !wait {
+                    return -EAGAIN;
+                }
+                TASK_MANAGER.yield_now();
+            }
+        }
+        *event = self.events[self.head];
+        self.head = (self.head + 1) % QUEUE_SIZE;
+        self.count -= 1;
+        0
+    }
+}
\ No newline at end of file
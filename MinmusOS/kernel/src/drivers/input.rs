@@ -0,0 +1,172 @@
+// Project Name:  MinmusOS
+// File Name:     input.rs
+// File Function: Console input queue
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::tty::{Tty, ECHO, ICANON, ISIG};
+use crate::multitasking::task::TASK_MANAGER;
+use crate::syscalls::print::Printer;
+use core::ptr;
+use lib::errno::{EINTR, EINVAL};
+
+pub const MODE_COOKED: u32 = 0;
+pub const MODE_RAW: u32 = 1;
+const QUEUE_SIZE: usize = 256;
+const LINE_SIZE: usize = 256;
+const KEY_BACKSPACE: u8 = 0x08;
+const KEY_EOF: u8 = 0x04;
+
+#[derive(Copy, Clone)]
+pub struct InputQueue {
+    queue: [u8; QUEUE_SIZE],
+    head: usize,
+    count: usize,
+    line: [u8; LINE_SIZE],
+    length: usize,
+    eof: bool,
+    cancelled: bool,
+    waiting: bool,
+}
+
+impl InputQueue {
+    pub const fn new() -> Self {
+        InputQueue {
+            queue: [0; QUEUE_SIZE],
+            head: 0,
+            count: 0,
+            line: [0; LINE_SIZE],
+            length: 0,
+            eof: false,
+            cancelled: false,
+            waiting: false,
+        }
+    }
+
+    pub fn key(&mut self, c: u8, tty: &mut Tty, printer: &mut Printer) {
+        if !tty.is_set(ICANON) {
+            self.push(c);
+            Self::echo(tty, printer, &[c]);
+            return;
+        }
+        match c {
+            b'\n' => {
+                if self.count + self.length < QUEUE_SIZE {
+                    self.line[self.length] = b'\n';
+                    self.length += 1;
+                    self.commit();
+                }
+                Self::echo(tty, printer, b"\n");
+            }
+            KEY_BACKSPACE => {
+                if self.length > 0 {
+                    self.length -= 1;
+                    Self::echo(tty, printer, b"\x08 \x08");
+                }
+            }
+            KEY_EOF => {
+                if self.length == 0 {
+                    self.eof = true;
+                } else {
+                    self.commit();
+                }
+            }
+            _ => {
+                if c >= b' ' && self.length < LINE_SIZE - 1 {
+                    self.line[self.length] = c;
+                    self.length += 1;
+                    Self::echo(tty, printer, &[c]);
+                }
+            }
+        }
+    }
+
+    pub fn cancel(&mut self, tty: &mut Tty, printer: &mut Printer) {
+        self.length = 0;
+        self.cancelled = self.waiting;
+        Self::echo(tty, printer, b"^C\n");
+    }
+
+    pub fn set_mode(&mut self, mode: u32, tty: &mut Tty) -> i32 {
+        if mode != MODE_COOKED && mode != MODE_RAW {
+            return -EINVAL;
+        }
+        let previous: u32 = if tty.is_set(ICANON) { MODE_COOKED } else { MODE_RAW };
+        let flags: u32 = tty.get_attributes().flags;
+        if mode == MODE_RAW {
+            tty.set_flags(flags & !(ICANON | ECHO));
+        } else {
+            tty.set_flags(flags | ICANON | ECHO | ISIG);
+        }
+        self.length = 0;
+        previous as i32
+    }
+
+    fn state(&self) -> (usize, bool, bool) {
+        unsafe { (ptr::read_volatile(&self.count), ptr::read_volatile(&self.eof), ptr::read_volatile(&self.cancelled)) }
+    }
+
+    pub fn read(&mut self, buffer: &mut [u8], tty: &Tty) -> i32 {
+        if buffer.is_empty() {
+            return 0;
+        }
+        self.waiting = true;
+        loop {
+            unsafe {
+                if TASK_MANAGER.is_interrupted() {
+                    self.waiting = false;
+                    return -EINTR;
+                }
+                if TASK_MANAGER.is_foreground() {
+                    let (count, eof, cancelled): (usize, bool, bool) = self.state();
+                    if cancelled {
+                        self.cancelled = false;
+                        self.waiting = false;
+                        return -EINTR;
+                    }
+                    if count > 0 || eof {
+                        break;
+                    }
+                }
+                TASK_MANAGER.yield_now();
+            }
+        }
+        self.waiting = false;
+        if self.count == 0 {
+            self.eof = false;
+            return 0;
+        }
+        let mut read: usize = 0;
+        while read < buffer.len() && self.count > 0 {
+            let c: u8 = self.queue[self.head];
+            self.head = (self.head + 1) % QUEUE_SIZE;
+            self.count -= 1;
+            buffer[read] = c;
+            read += 1;
+            if c == b'\n' && tty.is_set(ICANON) {
+                break;
+            }
+        }
+        read as i32
+    }
+
+    fn echo(tty: &mut Tty, printer: &mut Printer, buffer: &[u8]) {
+        if tty.is_set(ECHO) {
+            tty.write(printer, buffer);
+        }
+    }
+
+    fn commit(&mut self) {
+        for i in 0..self.length {
+            self.push(self.line[i]);
+        }
+        self.length = 0;
+    }
+
+    fn push(&mut self, c: u8) {
+        if self.count < QUEUE_SIZE {
+            self.queue[(self.head + self.count) % QUEUE_SIZE] = c;
+            self.count += 1;
+        }
+    }
+}
\ No newline at end of file
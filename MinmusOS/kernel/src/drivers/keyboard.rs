@@ -4,11 +4,34 @@
 // Author:        Jishen Lin
 // License:       MIT License
 
+use crate::drivers::console::{CONSOLES, MAX_CONSOLES};
+use crate::drivers::layout::LAYOUT;
 use crate::drivers::pic::PICS;
-use crate::shell::shell::SHELL;
+use crate::syscalls::print::VGA_HEIGHT;
 use core::arch::asm;
+use lib::keyboard::{KeyEvent, EVENT_PRESSED, EVENT_REPEAT, KEY_BACKSPACE, KEY_CAPS_LOCK, KEY_DELETE, KEY_DOWN, KEY_END, KEY_ENTER, KEY_ESCAPE, KEY_F1, KEY_F10, KEY_F11, KEY_F12, KEY_F2, KEY_F3, KEY_F4, KEY_F5, KEY_F6, KEY_F7, KEY_F8, KEY_F9, KEY_HOME, KEY_INSERT, KEY_KEYPAD_ENTER, KEY_LEFT, KEY_LEFT_ALT, KEY_LEFT_CTRL, KEY_LEFT_SHIFT, KEY_NUM_LOCK, KEY_PAGE_DOWN, KEY_PAGE_UP, KEY_RIGHT, KEY_RIGHT_ALT, KEY_RIGHT_CTRL, KEY_RIGHT_SHIFT, KEY_SCROLL_LOCK, KEY_TAB, KEY_UP, MOD_ALT, MOD_ALT_GR, MOD_CAPS_LOCK, MOD_CTRL, MOD_NUM_LOCK, MOD_SCROLL_LOCK, MOD_SHIFT};
 
 pub const KEYBOARD_INT: u8 = 33;
+const DATA_PORT: u16 = 0x60;
+const STATUS_PORT: u16 = 0x64;
+const COMMAND_SET_LEDS: u8 = 0xED;
+const RESPONSE_ACK: u8 = 0xFA;
+const RESPONSE_RESEND: u8 = 0xFE;
+const PREFIX_EXTENDED: u8 = 0xE0;
+const PREFIX_PAUSE: u8 = 0xE1;
+const PAUSE_LENGTH: u8 = 5;
+const RELEASED: u8 = 0x80;
+const EXTENDED: u8 = 0x80;
+const KEY_KEYPAD_STAR: u8 = 0x37;
+const KEY_KEYPAD_7: u8 = 0x47;
+const KEY_KEYPAD_MINUS: u8 = 0x4A;
+const KEY_KEYPAD_5: u8 = 0x4C;
+const KEY_KEYPAD_PLUS: u8 = 0x4E;
+const KEY_KEYPAD_PERIOD: u8 = 0x53;
+const KEY_FAKE_LEFT_SHIFT: u8 = EXTENDED | 0x2A;
+const KEY_KEYPAD_SLASH: u8 = EXTENDED | 0x35;
+const KEY_FAKE_RIGHT_SHIFT: u8 = EXTENDED | 0x36;
+const KEYPAD: &[u8; 13] = b"789-456+1230.";
 
 pub struct Keyboard {
     left_shift: bool,
@@ -18,6 +41,13 @@ pub struct Keyboard {
     left_alt: bool,
     right_alt: bool,
     caps_lock: bool,
+    num_lock: bool,
+    scroll_lock: bool,
+    extended: bool,
+    skip: u8,
+    leds_pending: bool,
+    dead: u8,
+    down: [u32; 8],
 }
 
 pub static mut KEYBOARD: Keyboard = Keyboard {
@@ -28,66 +58,15 @@ pub static mut KEYBOARD: Keyboard = Keyboard {
     left_alt: false,
     right_alt: false,
     caps_lock: false,
+    num_lock: false,
+    scroll_lock: false,
+    extended: false,
+    skip: 0,
+    leds_pending: false,
+    dead: 0,
+    down: [0; 8],
 };
 
-#[derive(Copy, Clone, Debug)]
-struct KeyMap {
-    scancode: u8,
-    normal: char,
-    shifted: char,
-}
-
-static KEYMAP: &[KeyMap] = &[
-    KeyMap { scancode: 0x02, normal: '1', shifted: '!' },
-    KeyMap { scancode: 0x03, normal: '2', shifted: '@' },
-    KeyMap { scancode: 0x04, normal: '3', shifted: '#' },
-    KeyMap { scancode: 0x05, normal: '4', shifted: '$' },
-    KeyMap { scancode: 0x06, normal: '5', shifted: '%' },
-    KeyMap { scancode: 0x07, normal: '6', shifted: '^' },
-    KeyMap { scancode: 0x08, normal: '7', shifted: '&' },
-    KeyMap { scancode: 0x09, normal: '8', shifted: '*' },
-    KeyMap { scancode: 0x0A, normal: '9', shifted: '(' },
-    KeyMap { scancode: 0x0B, normal: '0', shifted: ')' },
-    KeyMap { scancode: 0x0C, normal: '-', shifted: '_' },
-    KeyMap { scancode: 0x0D, normal: '=', shifted: '+' },
-    KeyMap { scancode: 0x10, normal: 'q', shifted: 'Q' },
-    KeyMap { scancode: 0x11, normal: 'w', shifted: 'W' },
-    KeyMap { scancode: 0x12, normal: 'e', shifted: 'E' },
-    KeyMap { scancode: 0x13, normal: 'r', shifted: 'R' },
-    KeyMap { scancode: 0x14, normal: 't', shifted: 'T' },
-    KeyMap { scancode: 0x15, normal: 'y', shifted: 'Y' },
-    KeyMap { scancode: 0x16, normal: 'u', shifted: 'U' },
-    KeyMap { scancode: 0x17, normal: 'i', shifted: 'I' },
-    KeyMap { scancode: 0x18, normal: 'o', shifted: 'O' },
-    KeyMap { scancode: 0x19, normal: 'p', shifted: 'P' },
-    KeyMap { scancode: 0x1E, normal: 'a', shifted: 'A' },
-    KeyMap { scancode: 0x1F, normal: 's', shifted: 'S' },
-    KeyMap { scancode: 0x20, normal: 'd', shifted: 'D' },
-    KeyMap { scancode: 0x21, normal: 'f', shifted: 'F' },
-    KeyMap { scancode: 0x22, normal: 'g', shifted: 'G' },
-    KeyMap { scancode: 0x23, normal: 'h', shifted: 'H' },
-    KeyMap { scancode: 0x24, normal: 'j', shifted: 'J' },
-    KeyMap { scancode: 0x25, normal: 'k', shifted: 'K' },
-    KeyMap { scancode: 0x26, normal: 'l', shifted: 'L' },
-    KeyMap { scancode: 0x2C, normal: 'z', shifted: 'Z' },
-    KeyMap { scancode: 0x2D, normal: 'x', shifted: 'X' },
-    KeyMap { scancode: 0x2E, normal: 'c', shifted: 'C' },
-    KeyMap { scancode: 0x2F, normal: 'v', shifted: 'V' },
-    KeyMap { scancode: 0x30, normal: 'b', shifted: 'B' },
-    KeyMap { scancode: 0x31, normal: 'n', shifted: 'N' },
-    KeyMap { scancode: 0x32, normal: 'm', shifted: 'M' },
-    KeyMap { scancode: 0x1A, normal: '[', shifted: '{' },
-    KeyMap { scancode: 0x1B, normal: ']', shifted: '}' },
-    KeyMap { scancode: 0x2B, normal: '\\', shifted: '|' },
-    KeyMap { scancode: 0x27, normal: ';', shifted: ':' },
-    KeyMap { scancode: 0x28, normal: '\'', shifted: '"' },
-    KeyMap { scancode: 0x33, normal: ',', shifted: '<' },
-    KeyMap { scancode: 0x34, normal: '.', shifted: '>' },
-    KeyMap { scancode: 0x35, normal: '/', shifted: '?' },
-    KeyMap { scancode: 0x29, normal: '`', shifted: '~' },
-    KeyMap { scancode: 0x39, normal: ' ', shifted: ' ' },
-];
-
 #[naked]
 pub extern "C" fn keyboard() {
     unsafe {
@@ -109,79 +88,278 @@ pub extern "C" fn keyboard() {
     }
 }
 
-fn scancode_to_char(scancode: u8) -> char {
-    let shift_pressed: bool = unsafe { KEYBOARD.left_shift || KEYBOARD.right_shift };
-    let caps_lock: bool = unsafe { KEYBOARD.caps_lock };
-    for key in KEYMAP.iter() {
-        if key.scancode == scancode {
-            let mut character = if shift_pressed {
-                key.shifted
-            } else {
-                key.normal
-            };
-            if character.is_ascii_alphabetic() {
-                if caps_lock && !shift_pressed || !caps_lock && shift_pressed {
-                    character = character.to_ascii_uppercase();
-                } else {
-                    character = character.to_ascii_lowercase();
-                }
-            }
-            return character;
-        }
-    }
-    '\0'
-}
-
 #[allow(improper_ctypes_definitions)]
 #[no_mangle]
 pub extern "C" fn keyboard_handler() {
-    static mut E0_PREFIX: bool = false;
-    let mut scancode: u8;
+    let scancode: u8;
     unsafe {
-        asm!("in al, dx", out("al") scancode, in("dx") 0x60u16);
-        if scancode == 0xE0 {
-            asm!("in al, dx", out("al") scancode, in("dx") 0x60u16);
-            E0_PREFIX = true;
-        }
-        // lib::print!("[0x{:X}]", scancode);
+        asm!("in al, dx", out("al") scancode, in("dx") DATA_PORT);
         PICS.end_interrupt(KEYBOARD_INT);
-        if E0_PREFIX {
-            match scancode {
-                0x1D => KEYBOARD.right_ctrl = true,
-                0x9D => KEYBOARD.right_ctrl = false,
-                0x38 => KEYBOARD.right_alt = true,
-                0xB8 => KEYBOARD.right_alt = false,
-                _ => {}
+        KEYBOARD.receive(scancode);
+    }
+}
+
+impl Keyboard {
+    fn receive(&mut self, scancode: u8) {
+        match scancode {
+            RESPONSE_ACK => {
+                if self.leds_pending {
+                    self.leds_pending = false;
+                    write_data(self.leds());
+                }
+                return;
+            }
+            RESPONSE_RESEND => return,
+            PREFIX_EXTENDED => {
+                self.extended = true;
+                return;
+            }
+            PREFIX_PAUSE => {
+                self.skip = PAUSE_LENGTH;
+                return;
+            }
+            _ => {}
+        }
+        if self.skip > 0 {
+            self.skip -= 1;
+            return;
+        }
+        let pressed: bool = scancode & RELEASED == 0;
+        let mut key: u8 = scancode & !RELEASED;
+        if self.extended {
+            key |= EXTENDED;
+            self.extended = false;
+        }
+        if key == KEY_FAKE_LEFT_SHIFT || key == KEY_FAKE_RIGHT_SHIFT {
+            return;
+        }
+        let repeat: bool = pressed && self.is_down(key);
+        self.set_down(key, pressed);
+        match key {
+            KEY_LEFT_SHIFT => self.left_shift = pressed,
+            KEY_RIGHT_SHIFT => self.right_shift = pressed,
+            KEY_LEFT_CTRL => self.left_ctrl = pressed,
+            KEY_RIGHT_CTRL => self.right_ctrl = pressed,
+            KEY_LEFT_ALT => self.left_alt = pressed,
+            KEY_RIGHT_ALT => self.right_alt = pressed,
+            KEY_CAPS_LOCK if pressed && !repeat => {
+                self.caps_lock = !self.caps_lock;
+                self.update_leds();
             }
-            E0_PREFIX = false;
+            KEY_NUM_LOCK if pressed && !repeat => {
+                self.num_lock = !self.num_lock;
+                self.update_leds();
+            }
+            KEY_SCROLL_LOCK if pressed && !repeat => {
+                self.scroll_lock = !self.scroll_lock;
+                self.update_leds();
+            }
+            _ => {}
+        }
+        let key: u8 = self.translate(key);
+        let mut character: u8 = self.character(key);
+        if pressed && character != 0 {
+            character = self.compose(key, character);
+        }
+        let mut flags: u8 = 0;
+        if pressed {
+            flags |= EVENT_PRESSED;
+        }
+        if repeat {
+            flags |= EVENT_REPEAT;
+        }
+        unsafe {
+            CONSOLES.active().events.push(KeyEvent {
+                key,
+                character,
+                modifiers: self.modifiers(),
+                flags,
+            });
+            if pressed {
+                self.press(key, character);
+            }
+        }
+    }
+
+    unsafe fn press(&self, key: u8, character: u8) {
+        if self.is_alt() && key >= KEY_F1 && key < KEY_F1 + MAX_CONSOLES as u8 {
+            CONSOLES.switch((key - KEY_F1) as usize);
+            return;
+        }
+        if self.is_shift() && key == KEY_PAGE_UP {
+            CONSOLES.active().printer.scroll_view(VGA_HEIGHT as i32 / 2);
+            return;
+        }
+        if self.is_shift() && key == KEY_PAGE_DOWN {
+            CONSOLES.active().printer.scroll_view(-(VGA_HEIGHT as i32 / 2));
+            return;
+        }
+        if character == 0x03 {
+            CONSOLES.active().interrupt();
+        } else if character == 0x1A {
+            CONSOLES.active().suspend();
+        } else if character != 0 {
+            CONSOLES.active().key(character);
         } else {
-            match scancode {
-                0x2A => KEYBOARD.left_shift = true,
-                0xAA => KEYBOARD.left_shift = false,
-                0x36 => KEYBOARD.right_shift = true,
-                0xB6 => KEYBOARD.right_shift = false,
-                0x1D => KEYBOARD.left_ctrl = true,
-                0x9D => KEYBOARD.left_ctrl = false,
-                0x38 => KEYBOARD.left_alt = true,
-                0xB8 => KEYBOARD.left_alt = false,
-                0x3A => {
-                    KEYBOARD.caps_lock = !KEYBOARD.caps_lock;
-                    return;
-                }
-                0x0E => {
-                    SHELL.backspace();
-                    return;
+            let sequence: &[u8] = sequence(key);
+            if !sequence.is_empty() {
+                CONSOLES.active().keys(sequence);
+            }
+        }
+    }
+
+    fn translate(&self, key: u8) -> u8 {
+        if key >= KEY_KEYPAD_7 && key <= KEY_KEYPAD_PERIOD && key != KEY_KEYPAD_MINUS && key != KEY_KEYPAD_PLUS && key != KEY_KEYPAD_5 {
+            if !self.num_lock || self.is_shift() {
+                return key | EXTENDED;
+            }
+        }
+        key
+    }
+
+    fn character(&self, key: u8) -> u8 {
+        let character: u8 = match key {
+            KEY_ESCAPE => 0x1B,
+            KEY_BACKSPACE => 0x08,
+            KEY_TAB => b'\t',
+            KEY_ENTER | KEY_KEYPAD_ENTER => b'\n',
+            KEY_KEYPAD_STAR => b'*',
+            KEY_KEYPAD_5 if !self.num_lock => 0,
+            KEY_KEYPAD_SLASH => b'/',
+            KEY_KEYPAD_7..=KEY_KEYPAD_PERIOD => KEYPAD[(key - KEY_KEYPAD_7) as usize],
+            _ if key & EXTENDED == 0 => unsafe { LAYOUT.get(key, self.level(key)) },
+            _ => 0,
+        };
+        if self.is_ctrl() && character.is_ascii_alphabetic() {
+            return character & 0x1F;
+        }
+        character
+    }
+
+    fn level(&self, key: u8) -> usize {
+        unsafe { LAYOUT.level(key, self.is_shift(), self.right_alt, self.caps_lock) }
+    }
+
+    fn compose(&mut self, key: u8, character: u8) -> u8 {
+        unsafe {
+            if self.dead != 0 {
+                let dead: u8 = self.dead;
+                self.dead = 0;
+                if character == b' ' {
+                    return dead;
                 }
-                0x1C => {
-                    SHELL.enter();
-                    return;
+                let result: u8 = LAYOUT.compose(dead, character);
+                if result != 0 {
+                    return result;
                 }
-                _ => {}
+                CONSOLES.active().key(dead);
+                return character;
+            }
+            if key & EXTENDED == 0 && LAYOUT.is_dead(key, self.level(key)) {
+                self.dead = character;
+                return 0;
             }
         }
-        let key: char = scancode_to_char(scancode);
-        if key != '\0' {
-            SHELL.add(key);
+        character
+    }
+
+    fn modifiers(&self) -> u8 {
+        let mut modifiers: u8 = 0;
+        if self.is_shift() {
+            modifiers |= MOD_SHIFT;
+        }
+        if self.is_ctrl() {
+            modifiers |= MOD_CTRL;
+        }
+        if self.is_alt() {
+            modifiers |= MOD_ALT;
+        }
+        if self.right_alt {
+            modifiers |= MOD_ALT_GR;
+        }
+        if self.caps_lock {
+            modifiers |= MOD_CAPS_LOCK;
+        }
+        if self.num_lock {
+            modifiers |= MOD_NUM_LOCK;
+        }
+        if self.scroll_lock {
+            modifiers |= MOD_SCROLL_LOCK;
+        }
+        modifiers
+    }
+
+    fn is_shift(&self) -> bool {
+        self.left_shift || self.right_shift
+    }
+
+    fn is_ctrl(&self) -> bool {
+        self.left_ctrl || self.right_ctrl
+    }
+
+    fn is_alt(&self) -> bool {
+        self.left_alt || self.right_alt
+    }
+
+    fn is_down(&self, key: u8) -> bool {
+        self.down[(key / 32) as usize] & (1 << (key % 32)) != 0
+    }
+
+    fn set_down(&mut self, key: u8, down: bool) {
+        if down {
+            self.down[(key / 32) as usize] |= 1 << (key % 32);
+        } else {
+            self.down[(key / 32) as usize] &= !(1 << (key % 32));
+        }
+    }
+
+    fn leds(&self) -> u8 {
+        (self.scroll_lock as u8) | (self.num_lock as u8) << 1 | (self.caps_lock as u8) << 2
+    }
+
+    fn update_leds(&mut self) {
+        self.leds_pending = true;
+        write_data(COMMAND_SET_LEDS);
+    }
+}
+
+fn sequence(key: u8) -> &'static [u8] {
+    match key {
+        KEY_UP => b"\x1B[A",
+        KEY_DOWN => b"\x1B[B",
+        KEY_RIGHT => b"\x1B[C",
+        KEY_LEFT => b"\x1B[D",
+        KEY_HOME => b"\x1B[H",
+        KEY_END => b"\x1B[F",
+        KEY_INSERT => b"\x1B[2~",
+        KEY_DELETE => b"\x1B[3~",
+        KEY_PAGE_UP => b"\x1B[5~",
+        KEY_PAGE_DOWN => b"\x1B[6~",
+        KEY_F1 => b"\x1BOP",
+        KEY_F2 => b"\x1BOQ",
+        KEY_F3 => b"\x1BOR",
+        KEY_F4 => b"\x1BOS",
+        KEY_F5 => b"\x1B[15~",
+        KEY_F6 => b"\x1B[17~",
+        KEY_F7 => b"\x1B[18~",
+        KEY_F8 => b"\x1B[19~",
+        KEY_F9 => b"\x1B[20~",
+        KEY_F10 => b"\x1B[21~",
+        KEY_F11 => b"\x1B[23~",
+        KEY_F12 => b"\x1B[24~",
+        _ => b"",
+    }
+}
+
+fn write_data(data: u8) {
+    unsafe {
+        for _ in 0..100000 {
+            let status: u8;
+            asm!("in al, dx", out("al") status, in("dx") STATUS_PORT);
+            if status & 0x02 == 0 {
+                break;
+            }
         }
+        asm!("out dx, al", in("dx") DATA_PORT, in("al") data);
     }
 }
\ No newline at end of file
@@ -0,0 +1,286 @@
+// Project Name:  MinmusOS
+// File Name:     layout.rs
+// File Function: Keyboard layouts
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::filesystem::file::{FILES, O_RDONLY};
+use crate::multitasking::mutex::Mutex;
+use lib::errno::{EINVAL, ENAMETOOLONG};
+
+pub const MAX_NAME_LENGTH: usize = 8;
+const KEY_COUNT: usize = 0x80;
+const LEVEL_COUNT: usize = 3;
+const LEVEL_NORMAL: usize = 0;
+const LEVEL_SHIFTED: usize = 1;
+const LEVEL_ALT_GR: usize = 2;
+const MAX_COMPOSITIONS: usize = 128;
+const LAYOUT_EXTENSION: &[u8] = b".kmp";
+const DEFAULT_LAYOUT: &[u8] = b"us";
+
+pub static mut LAYOUT: Layout = EMPTY_LAYOUT;
+static mut LOADING: Mutex<Layout> = Mutex::new(EMPTY_LAYOUT);
+
+#[derive(Copy, Clone, Debug)]
+struct KeyMap {
+    scancode: u8,
+    normal: char,
+    shifted: char,
+}
+
+static KEYMAP: &[KeyMap] = &[
+    KeyMap { scancode: 0x02, normal: '1', shifted: '!' },
+    KeyMap { scancode: 0x03, normal: '2', shifted: '@' },
+    KeyMap { scancode: 0x04, normal: '3', shifted: '#' },
+    KeyMap { scancode: 0x05, normal: '4', shifted: '$' },
+    KeyMap { scancode: 0x06, normal: '5', shifted: '%' },
+    KeyMap { scancode: 0x07, normal: '6', shifted: '^' },
+    KeyMap { scancode: 0x08, normal: '7', shifted: '&' },
+    KeyMap { scancode: 0x09, normal: '8', shifted: '*' },
+    KeyMap { scancode: 0x0A, normal: '9', shifted: '(' },
+    KeyMap { scancode: 0x0B, normal: '0', shifted: ')' },
+    KeyMap { scancode: 0x0C, normal: '-', shifted: '_' },
+    KeyMap { scancode: 0x0D, normal: '=', shifted: '+' },
+    KeyMap { scancode: 0x10, normal: 'q', shifted: 'Q' },
+    KeyMap { scancode: 0x11, normal: 'w', shifted: 'W' },
+    KeyMap { scancode: 0x12, normal: 'e', shifted: 'E' },
+    KeyMap { scancode: 0x13, normal: 'r', shifted: 'R' },
+    KeyMap { scancode: 0x14, normal: 't', shifted: 'T' },
+    KeyMap { scancode: 0x15, normal: 'y', shifted: 'Y' },
+    KeyMap { scancode: 0x16, normal: 'u', shifted: 'U' },
+    KeyMap { scancode: 0x17, normal: 'i', shifted: 'I' },
+    KeyMap { scancode: 0x18, normal: 'o', shifted: 'O' },
+    KeyMap { scancode: 0x19, normal: 'p', shifted: 'P' },
+    KeyMap { scancode: 0x1E, normal: 'a', shifted: 'A' },
+    KeyMap { scancode: 0x1F, normal: 's', shifted: 'S' },
+    KeyMap { scancode: 0x20, normal: 'd', shifted: 'D' },
+    KeyMap { scancode: 0x21, normal: 'f', shifted: 'F' },
+    KeyMap { scancode: 0x22, normal: 'g', shifted: 'G' },
+    KeyMap { scancode: 0x23, normal: 'h', shifted: 'H' },
+    KeyMap { scancode: 0x24, normal: 'j', shifted: 'J' },
+    KeyMap { scancode: 0x25, normal: 'k', shifted: 'K' },
+    KeyMap { scancode: 0x26, normal: 'l', shifted: 'L' },
+    KeyMap { scancode: 0x2C, normal: 'z', shifted: 'Z' },
+    KeyMap { scancode: 0x2D, normal: 'x', shifted: 'X' },
+    KeyMap { scancode: 0x2E, normal: 'c', shifted: 'C' },
+    KeyMap { scancode: 0x2F, normal: 'v', shifted: 'V' },
+    KeyMap { scancode: 0x30, normal: 'b', shifted: 'B' },
+    KeyMap { scancode: 0x31, normal: 'n', shifted: 'N' },
+    KeyMap { scancode: 0x32, normal: 'm', shifted: 'M' },
+    KeyMap { scancode: 0x1A, normal: '[', shifted: '{' },
+    KeyMap { scancode: 0x1B, normal: ']', shifted: '}' },
+    KeyMap { scancode: 0x2B, normal: '\\', shifted: '|' },
+    KeyMap { scancode: 0x27, normal: ';', shifted: ':' },
+    KeyMap { scancode: 0x28, normal: '\'', shifted: '"' },
+    KeyMap { scancode: 0x33, normal: ',', shifted: '<' },
+    KeyMap { scancode: 0x34, normal: '.', shifted: '>' },
+    KeyMap { scancode: 0x35, normal: '/', shifted: '?' },
+    KeyMap { scancode: 0x29, normal: '`', shifted: '~' },
+    KeyMap { scancode: 0x39, normal: ' ', shifted: ' ' },
+];
+
+#[derive(Copy, Clone, Debug)]
+struct Composition {
+    dead: u8,
+    base: u8,
+    result: u8,
+}
+
+#[derive(Copy, Clone)]
+pub struct Layout {
+    name: [u8; MAX_NAME_LENGTH],
+    length: usize,
+    keys: [[u8; LEVEL_COUNT]; KEY_COUNT],
+    dead: [u8; KEY_COUNT],
+    compositions: [Composition; MAX_COMPOSITIONS],
+    composition_count: usize,
+}
+
+static EMPTY_LAYOUT: Layout = Layout {
+    name: [0; MAX_NAME_LENGTH],
+    length: 0,
+    keys: [[0; LEVEL_COUNT]; KEY_COUNT],
+    dead: [0; KEY_COUNT],
+    compositions: [Composition { dead: 0, base: 0, result: 0 }; MAX_COMPOSITIONS],
+    composition_count: 0,
+};
+
+impl Layout {
+    pub fn reset(&mut self) {
+        *self = EMPTY_LAYOUT;
+        self.set_name(DEFAULT_LAYOUT);
+        for key in KEYMAP.iter() {
+            self.keys[key.scancode as usize][LEVEL_NORMAL] = key.normal as u8;
+            self.keys[key.scancode as usize][LEVEL_SHIFTED] = key.shifted as u8;
+        }
+    }
+
+    pub fn restore(&mut self, name: &[u8]) {
+        self.reset();
+        if !name.is_empty() {
+            self.load(name);
+        }
+    }
+
+    pub fn load(&mut self, name: &[u8]) -> i32 {
+        if name.is_empty() {
+            return -EINVAL;
+        }
+        if name.len() > MAX_NAME_LENGTH {
+            return -ENAMETOOLONG;
+        }
+        let mut path: [u8; MAX_NAME_LENGTH + 4] = [0; MAX_NAME_LENGTH + 4];
+        path[..name.len()].copy_from_slice(name);
+        path[name.len()..name.len() + LAYOUT_EXTENSION.len()].copy_from_slice(LAYOUT_EXTENSION);
+        let id: i32 = unsafe { FILES.open(&path[..name.len() + LAYOUT_EXTENSION.len()], O_RDONLY) };
+        if id < 0 {
+            if name == DEFAULT_LAYOUT {
+                self.reset();
+                return 0;
+            }
+            return id;
+        }
+        let layout: &mut Layout = unsafe { LOADING.acquire_mut() };
+        layout.reset();
+        layout.set_name(name);
+        let mut chunk: [u8; 128] = [0; 128];
+        let mut line: [u8; 96] = [0; 96];
+        let mut length: usize = 0;
+        let mut result: i32 = 0;
+        loop {
+            let read: i32 = unsafe { FILES.read(id as usize, &mut chunk) };
+            if read <= 0 {
+                if read < 0 {
+                    result = read;
+                }
+                break;
+            }
+            for &c in chunk[..read as usize].iter() {
+                if c == b'\n' {
+                    if !layout.parse_line(&line[..length]) {
+                        result = -EINVAL;
+                    }
+                    length = 0;
+                } else if c != b'\r' && length < line.len() {
+                    line[length] = c;
+                    length += 1;
+                }
+            }
+        }
+        if length > 0 && !layout.parse_line(&line[..length]) {
+            result = -EINVAL;
+        }
+        unsafe {
+            FILES.release(id as usize);
+        }
+        if result == 0 {
+            *self = *layout;
+        }
+        unsafe {
+            LOADING.free();
+        }
+        result
+    }
+
+    pub fn get_name(&self) -> &[u8] {
+        &self.name[..self.length]
+    }
+
+    pub fn level(&self, scancode: u8, shift: bool, alt_gr: bool, caps_lock: bool) -> usize {
+        let levels: &[u8; LEVEL_COUNT] = &self.keys[scancode as usize % KEY_COUNT];
+        if alt_gr && levels[LEVEL_ALT_GR] != 0 {
+            return LEVEL_ALT_GR;
+        }
+        if shift != (caps_lock && is_letter(levels[LEVEL_NORMAL])) { LEVEL_SHIFTED } else { LEVEL_NORMAL }
+    }
+
+    pub fn get(&self, scancode: u8, level: usize) -> u8 {
+        self.keys[scancode as usize % KEY_COUNT][level]
+    }
+
+    pub fn is_dead(&self, scancode: u8, level: usize) -> bool {
+        self.dead[scancode as usize % KEY_COUNT] & (1 << level) != 0
+    }
+
+    pub fn compose(&self, dead: u8, base: u8) -> u8 {
+        for composition in self.compositions[..self.composition_count].iter() {
+            if composition.dead == dead && composition.base == base {
+                return composition.result;
+            }
+        }
+        0
+    }
+
+    fn set_name(&mut self, name: &[u8]) {
+        self.length = name.len().min(MAX_NAME_LENGTH);
+        self.name[..self.length].copy_from_slice(&name[..self.length]);
+    }
+
+    fn parse_line(&mut self, line: &[u8]) -> bool {
+        let mut tokens: [&[u8]; 5] = [&[]; 5];
+        let mut count: usize = 0;
+        for token in line.split(|&c| c == b' ' || c == b'\t').filter(|t| !t.is_empty()) {
+            if count == tokens.len() {
+                return false;
+            }
+            tokens[count] = token;
+            count += 1;
+        }
+        if count == 0 || tokens[0][0] == b'#' {
+            return true;
+        }
+        match tokens[0] {
+            b"key" if count >= 3 => {
+                let scancode: usize = match parse_value(tokens[1]) {
+                    Some(scancode) if (scancode as usize) < KEY_COUNT => scancode as usize,
+                    _ => return false,
+                };
+                for level in 0..(count - 2).min(LEVEL_COUNT) {
+                    match parse_value(tokens[level + 2]) {
+                        Some(value) => self.keys[scancode][level] = value,
+                        None => return false,
+                    }
+                }
+                true
+            }
+            b"dead" if count == 3 => match (parse_value(tokens[1]), parse_value(tokens[2])) {
+                (Some(scancode), Some(level)) if (scancode as usize) < KEY_COUNT && (level as usize) < LEVEL_COUNT => {
+                    self.dead[scancode as usize] |= 1 << level;
+                    true
+                }
+                _ => false,
+            },
+            b"compose" if count == 4 && self.composition_count < MAX_COMPOSITIONS => {
+                match (parse_value(tokens[1]), parse_value(tokens[2]), parse_value(tokens[3])) {
+                    (Some(dead), Some(base), Some(result)) => {
+                        self.compositions[self.composition_count] = Composition { dead, base, result };
+                        self.composition_count += 1;
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+fn parse_value(token: &[u8]) -> Option<u8> {
+    match token {
+        b"-" => Some(0),
+        b"space" => Some(b' '),
+        [c] => Some(*c),
+        [b'0', b'x', digits @ ..] if !digits.is_empty() && digits.len() <= 2 => {
+            let mut value: u8 = 0;
+            for &digit in digits.iter() {
+                value = value * 16 + (digit as char).to_digit(16)? as u8;
+            }
+            Some(value)
+        }
+        _ => None,
+    }
+}
+
+fn is_letter(c: u8) -> bool {
+    c.is_ascii_lowercase() || (0x80..=0xA5).contains(&c)
+}
\ No newline at end of file
@@ -4,6 +4,12 @@
 // Author:        Jishen Lin
 // License:       MIT License
 
+pub mod console;
 pub mod disk;
+pub mod event;
+pub mod input;
 pub mod keyboard;
-pub mod pic;
\ No newline at end of file
+pub mod layout;
+pub mod mouse;
+pub mod pic;
+pub mod tty;
\ No newline at end of file
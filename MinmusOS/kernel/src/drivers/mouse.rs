@@ -0,0 +1,224 @@
+// Project Name:  MinmusOS
+// File Name:     mouse.rs
+// File Function: Mouse driver
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::drivers::event::MouseEvent;
+use crate::drivers::pic::PICS;
+use crate::syscalls::print::{VGA_HEIGHT, VGA_WIDTH};
+use core::arch::asm;
+
+pub const MOUSE_INT: u8 = 44;
+const DATA_PORT: u16 = 0x60;
+const STATUS_PORT: u16 = 0x64;
+const STATUS_OUTPUT_FULL: u8 = 0x01;
+const STATUS_INPUT_FULL: u8 = 0x02;
+const COMMAND_READ_CONFIG: u8 = 0x20;
+const COMMAND_WRITE_CONFIG: u8 = 0x60;
+const COMMAND_ENABLE_AUXILIARY: u8 = 0xA8;
+const COMMAND_WRITE_AUXILIARY: u8 = 0xD4;
+const CONFIG_AUXILIARY_INTERRUPT: u8 = 0x02;
+const CONFIG_AUXILIARY_CLOCK_DISABLED: u8 = 0x20;
+const MOUSE_GET_ID: u8 = 0xF2;
+const MOUSE_SET_SAMPLE_RATE: u8 = 0xF3;
+const MOUSE_ENABLE_STREAMING: u8 = 0xF4;
+const MOUSE_SET_DEFAULTS: u8 = 0xF6;
+const MOUSE_ID_WHEEL: u8 = 0x03;
+const RESPONSE_ACK: u8 = 0xFA;
+const WHEEL_SEQUENCE: [u8; 3] = [200, 100, 80];
+const SAMPLE_RATE: u8 = 100;
+const PACKET_BUTTONS: u8 = 0x07;
+const PACKET_SYNC: u8 = 0x08;
+const PACKET_X_SIGN: u8 = 0x10;
+const PACKET_Y_SIGN: u8 = 0x20;
+const PACKET_OVERFLOW: u8 = 0xC0;
+const CELL_WIDTH: i32 = 8;
+const CELL_HEIGHT: i32 = 16;
+const VGA_START: u32 = 0x000B8000;
+const TIMEOUT: u32 = 100000;
+
+pub struct Mouse {
+    wheel: bool,
+    packet: [u8; 4],
+    index: usize,
+    x: i32,
+    y: i32,
+    pointer: usize,
+    saved: u16,
+    drawn: bool,
+}
+
+pub static mut MOUSE: Mouse = Mouse {
+    wheel: false,
+    packet: [0; 4],
+    index: 0,
+    x: VGA_WIDTH as i32 * CELL_WIDTH / 2,
+    y: VGA_HEIGHT as i32 * CELL_HEIGHT / 2,
+    pointer: 0,
+    saved: 0,
+    drawn: false,
+};
+
+#[naked]
+pub extern "C" fn mouse() {
+    unsafe {
+        asm!(
+        "push eax",
+        "push ecx",
+        "push edx",
+        "call mouse_handler",
+        "pop edx",
+        "pop ecx",
+        "pop eax",
+        "iretd",
+        options(noreturn),
+        );
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn mouse_handler() {
+    let data: u8;
+    unsafe {
+        asm!("in al, dx", out("al") data, in("dx") DATA_PORT);
+        PICS.end_interrupt(MOUSE_INT);
+        MOUSE.receive(data);
+    }
+}
+
+impl Mouse {
+    pub fn init(&mut self) -> bool {
+        while read_status() & STATUS_OUTPUT_FULL != 0 {
+            read_data();
+        }
+        write_command(COMMAND_ENABLE_AUXILIARY);
+        write_command(COMMAND_READ_CONFIG);
+        let config: u8 = match read_data() {
+            Some(config) => config,
+            None => return false,
+        };
+        write_command(COMMAND_WRITE_CONFIG);
+        write_data((config | CONFIG_AUXILIARY_INTERRUPT) & !CONFIG_AUXILIARY_CLOCK_DISABLED);
+        if !send(MOUSE_SET_DEFAULTS) {
+            return false;
+        }
+        for &rate in WHEEL_SEQUENCE.iter() {
+            send(MOUSE_SET_SAMPLE_RATE);
+            send(rate);
+        }
+        if send(MOUSE_GET_ID) {
+            self.wheel = read_data() == Some(MOUSE_ID_WHEEL);
+        }
+        send(MOUSE_SET_SAMPLE_RATE);
+        send(SAMPLE_RATE);
+        if !send(MOUSE_ENABLE_STREAMING) {
+            return false;
+        }
+        self.draw();
+        PICS.enable(MOUSE_INT);
+        true
+    }
+
+    fn receive(&mut self, data: u8) {
+        if self.index == 0 && data & PACKET_SYNC == 0 {
+            return;
+        }
+        self.packet[self.index] = data;
+        self.index += 1;
+        let size: usize = if self.wheel { 4 } else { 3 };
+        if self.index < size {
+            return;
+        }
+        self.index = 0;
+        let flags: u8 = self.packet[0];
+        if flags & PACKET_OVERFLOW != 0 {
+            return;
+        }
+        let dx: i32 = self.packet[1] as i32 - if flags & PACKET_X_SIGN != 0 { 0x100 } else { 0 };
+        let dy: i32 = -(self.packet[2] as i32 - if flags & PACKET_Y_SIGN != 0 { 0x100 } else { 0 });
+        let wheel: i8 = if self.wheel { self.packet[3] as i8 } else { 0 };
+        self.x = (self.x + dx).clamp(0, VGA_WIDTH as i32 * CELL_WIDTH - 1);
+        self.y = (self.y + dy).clamp(0, VGA_HEIGHT as i32 * CELL_HEIGHT - 1);
+        self.draw();
+        unsafe {
+            CONSOLES.active().mouse.push(MouseEvent {
+                x: (self.x / CELL_WIDTH) as u16,
+                y: (self.y / CELL_HEIGHT) as u16,
+                dx: dx as i16,
+                dy: dy as i16,
+                wheel,
+                buttons: flags & PACKET_BUTTONS,
+            });
+        }
+    }
+
+    fn draw(&mut self) {
+        let pointer: usize = ((self.y / CELL_HEIGHT) * VGA_WIDTH as i32 + self.x / CELL_WIDTH) as usize;
+        let vga: *mut u16 = VGA_START as *mut u16;
+        unsafe {
+            if self.drawn && *vga.add(self.pointer) == invert(self.saved) {
+                *vga.add(self.pointer) = self.saved;
+            }
+            self.saved = *vga.add(pointer);
+            *vga.add(pointer) = invert(self.saved);
+        }
+        self.pointer = pointer;
+        self.drawn = true;
+    }
+}
+
+fn invert(cell: u16) -> u16 {
+    let attribute: u8 = (cell >> 8) as u8;
+    (attribute.rotate_left(4) as u16) << 8 | (cell & 0xFF)
+}
+
+fn send(data: u8) -> bool {
+    write_command(COMMAND_WRITE_AUXILIARY);
+    write_data(data);
+    read_data() == Some(RESPONSE_ACK)
+}
+
+fn read_status() -> u8 {
+    let status: u8;
+    unsafe {
+        asm!("in al, dx", out("al") status, in("dx") STATUS_PORT);
+    }
+    status
+}
+
+fn read_data() -> Option<u8> {
+    for _ in 0..TIMEOUT {
+        if read_status() & STATUS_OUTPUT_FULL != 0 {
+            let data: u8;
+            unsafe {
+                asm!("in al, dx", out("al") data, in("dx") DATA_PORT);
+            }
+            return Some(data);
+        }
+    }
+    None
+}
+
+fn wait_input() {
+    for _ in 0..TIMEOUT {
+        if read_status() & STATUS_INPUT_FULL == 0 {
+            break;
+        }
+    }
+}
+
+fn write_command(command: u8) {
+    wait_input();
+    unsafe {
+        asm!("out dx, al", in("dx") STATUS_PORT, in("al") command);
+    }
+}
+
+fn write_data(data: u8) {
+    wait_input();
+    unsafe {
+        asm!("out dx, al", in("dx") DATA_PORT, in("al") data);
+    }
+}
\ No newline at end of file
@@ -15,6 +15,7 @@ const COMMAND_EOF: u8 = 0x20;
 const MODE: u8 = 0x01;
 const OFFSET: u8 = 32;
 const IRQ_COUNT: u8 = 8;
+const CASCADE_IRQ: u8 = 2;
 
 pub static PICS: Pics = Pics {
     master: Pic {
@@ -62,6 +63,10 @@ impl Pic {
         }
     }
 
+    pub fn unmask(&self, irq: u8) {
+        self.write_data(self.read_data() & !(1 << irq));
+    }
+
     pub fn handles_interrupt(&self, interrupt: u8) -> bool {
         self.offset <= interrupt && interrupt < self.offset + IRQ_COUNT
     }
@@ -100,6 +105,15 @@ impl Pics {
         self.master.handles_interrupt(interrupt) || self.slave.handles_interrupt(interrupt)
     }
 
+    pub fn enable(&self, interrupt: u8) {
+        if self.slave.handles_interrupt(interrupt) {
+            self.slave.unmask(interrupt - self.slave.offset);
+            self.master.unmask(CASCADE_IRQ);
+        } else if self.master.handles_interrupt(interrupt) {
+            self.master.unmask(interrupt - self.master.offset);
+        }
+    }
+
     pub fn end_interrupt(&self, interrupt: u8) {
         if self.handles_interrupt(interrupt) {
             if self.slave.handles_interrupt(interrupt) {
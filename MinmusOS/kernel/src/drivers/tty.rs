@@ -0,0 +1,171 @@
+// Project Name:  MinmusOS
+// File Name:     tty.rs
+// File Function: Terminal line discipline
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::syscalls::print::{Printer, VGA_HEIGHT, VGA_WIDTH};
+
+pub const ECHO: u32 = 0x0001;
+pub const ICANON: u32 = 0x0002;
+pub const ISIG: u32 = 0x0004;
+const MAX_PARAMETERS: usize = 8;
+const ANSI_COLORS: [u8; 8] = [0x0, 0x4, 0x2, 0x6, 0x1, 0x5, 0x3, 0x7];
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct TtyAttributes {
+    pub flags: u32,
+    pub columns: u32,
+    pub rows: u32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum State {
+    Normal,
+    Escape,
+    Csi,
+}
+
+#[derive(Copy, Clone)]
+pub struct Tty {
+    attributes: TtyAttributes,
+    state: State,
+    parameters: [u32; MAX_PARAMETERS],
+    count: usize,
+    saved: (u16, u16),
+}
+
+impl Tty {
+    pub const fn new() -> Self {
+        Tty {
+            attributes: TtyAttributes {
+                flags: ECHO | ICANON | ISIG,
+                columns: VGA_WIDTH as u32,
+                rows: VGA_HEIGHT as u32,
+            },
+            state: State::Normal,
+            parameters: [0; MAX_PARAMETERS],
+            count: 0,
+            saved: (0, 0),
+        }
+    }
+
+    pub fn get_attributes(&self) -> TtyAttributes {
+        self.attributes
+    }
+
+    pub fn set_attributes(&mut self, attributes: &TtyAttributes) {
+        self.attributes.flags = attributes.flags & (ECHO | ICANON | ISIG);
+    }
+
+    pub fn is_set(&self, flag: u32) -> bool {
+        self.attributes.flags & flag != 0
+    }
+
+    pub fn set_flags(&mut self, flags: u32) {
+        self.attributes.flags = flags;
+    }
+
+    pub fn write(&mut self, printer: &mut Printer, buffer: &[u8]) -> i32 {
+        for &c in buffer.iter() {
+            self.put(printer, c);
+        }
+        buffer.len() as i32
+    }
+
+    fn put(&mut self, printer: &mut Printer, c: u8) {
+        match self.state {
+            State::Normal => match c {
+                0x1B => self.state = State::Escape,
+                b'\n' => printer.new_line(),
+                b'\r' => printer.carriage_return(),
+                b'\t' => printer.tab(),
+                0x08 => printer.back(),
+                0x07 => {}
+                _ => printer.printc(c as char),
+            },
+            State::Escape => match c {
+                b'[' => {
+                    self.parameters = [0; MAX_PARAMETERS];
+                    self.count = 0;
+                    self.state = State::Csi;
+                }
+                b'c' => {
+                    printer.reset_colors();
+                    printer.clear();
+                    self.state = State::Normal;
+                }
+                _ => self.state = State::Normal,
+            },
+            State::Csi => match c {
+                b'0'..=b'9' => {
+                    if self.count == 0 {
+                        self.count = 1;
+                    }
+                    let parameter: &mut u32 = &mut self.parameters[self.count - 1];
+                    *parameter = parameter.saturating_mul(10).saturating_add((c - b'0') as u32);
+                }
+                b';' => {
+                    if self.count == 0 {
+                        self.count = 1;
+                    }
+                    if self.count < MAX_PARAMETERS {
+                        self.count += 1;
+                    }
+                }
+                b'?' => {}
+                0x40..=0x7E => {
+                    self.execute(printer, c);
+                    self.state = State::Normal;
+                }
+                _ => self.state = State::Normal,
+            },
+        }
+    }
+
+    fn execute(&mut self, printer: &mut Printer, command: u8) {
+        let first: u32 = self.parameters[0];
+        let amount: i32 = first.max(1) as i32;
+        let (x, y): (u16, u16) = printer.get_cursor();
+        match command {
+            b'A' => printer.move_by(0, -amount),
+            b'B' => printer.move_by(0, amount),
+            b'C' => printer.move_by(amount, 0),
+            b'D' => printer.move_by(-amount, 0),
+            b'E' => printer.move_to(0, y as i32 + amount),
+            b'F' => printer.move_to(0, y as i32 - amount),
+            b'G' => printer.move_to(amount - 1, y as i32),
+            b'H' | b'f' => printer.move_to(self.parameters[1].max(1) as i32 - 1, amount - 1),
+            b'J' => printer.erase_screen(first),
+            b'K' => printer.erase_line(first),
+            b'm' => self.select_graphic_rendition(printer),
+            b's' => self.saved = (x, y),
+            b'u' => printer.move_to(self.saved.0 as i32, self.saved.1 as i32),
+            _ => {}
+        }
+    }
+
+    fn select_graphic_rendition(&self, printer: &mut Printer) {
+        let (mut fg_color, mut bg_color): (u8, u8) = printer.get_colors();
+        let (default_fg, default_bg): (u8, u8) = printer.get_default_colors();
+        for i in 0..self.count.max(1) {
+            match self.parameters[i] {
+                0 => {
+                    fg_color = default_fg;
+                    bg_color = default_bg;
+                }
+                1 => fg_color |= 0x8,
+                22 => fg_color &= 0x7,
+                30..=37 => fg_color = (fg_color & 0x8) | ANSI_COLORS[(self.parameters[i] - 30) as usize],
+                39 => fg_color = default_fg,
+                40..=47 => bg_color = ANSI_COLORS[(self.parameters[i] - 40) as usize],
+                49 => bg_color = default_bg,
+                90..=97 => fg_color = ANSI_COLORS[(self.parameters[i] - 90) as usize] | 0x8,
+                100..=107 => bg_color = ANSI_COLORS[(self.parameters[i] - 100) as usize] | 0x8,
+                _ => {}
+            }
+        }
+        printer.set_colors(fg_color, bg_color);
+    }
+}
\ No newline at end of file
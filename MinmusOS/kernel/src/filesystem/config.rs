@@ -0,0 +1,293 @@
+// Project Name:  MinmusOS
+// File Name:     config.rs
+// File Function: System configuration file
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::drivers::layout::{LAYOUT, MAX_NAME_LENGTH};
+use crate::filesystem::file::{FILES, O_CREAT, O_RDONLY, O_TRUNC, O_WRONLY};
+use crate::multitasking::task::TASK_MANAGER;
+use crate::syscalls::print::{COLOR_BLACK, COLOR_WHITE};
+use core::fmt::{self, Write};
+use core::str;
+use lib::errno::{EINVAL, ENOSPC, EPERM};
+
+pub const SYSTEM_NAME: &str = "MinmusOS";
+pub const SYSTEM_VERSION: &str = "v1.0";
+pub const CONFIG_FILE: &[u8] = b"system.cfg";
+pub const HOSTNAME_SIZE: usize = 32;
+pub const KEYS: [&str; 7] = ["hostname", "timezone", "prompt", "keymap", "foreground", "background", "startup"];
+const PROMPT_SIZE: usize = 64;
+const STARTUP_SIZE: usize = 64;
+const FILE_SIZE: usize = 512;
+const MAX_OFFSET: i32 = 14 * 60;
+const DEFAULT_PROMPT: &[u8] = b"\\e[92m\\u@\\h\\e[97m:\\e[96m\\w\\e[97m\\$\\e[0m ";
+const COLOR_NAMES: [&str; 16] = [
+    "black", "blue", "green", "cyan", "red", "magenta", "yellow", "white",
+    "gray", "lightblue", "lightgreen", "lightcyan", "lightred", "lightmagenta", "lightyellow", "lightwhite",
+];
+
+pub static mut CONFIG: Config = Config::new();
+
+#[derive(Copy, Clone)]
+struct Text<const N: usize> {
+    bytes: [u8; N],
+    length: usize,
+}
+
+impl<const N: usize> Text<N> {
+    const fn from(text: &[u8]) -> Self {
+        let mut bytes: [u8; N] = [0; N];
+        let mut i: usize = 0;
+        while i < text.len() {
+            bytes[i] = text[i];
+            i += 1;
+        }
+        Text { bytes, length: text.len() }
+    }
+
+    fn get(&self) -> &[u8] {
+        &self.bytes[..self.length]
+    }
+
+    fn set(&mut self, text: &[u8]) -> bool {
+        if text.len() > N {
+            return false;
+        }
+        self.bytes[..text.len()].copy_from_slice(text);
+        self.length = text.len();
+        true
+    }
+}
+
+pub struct Timezone(pub i32);
+
+#[derive(Copy, Clone)]
+pub struct Config {
+    hostname: Text<HOSTNAME_SIZE>,
+    timezone: i32,
+    prompt: Text<PROMPT_SIZE>,
+    keymap: Text<MAX_NAME_LENGTH>,
+    foreground: u8,
+    background: u8,
+    startup: Text<STARTUP_SIZE>,
+}
+
+struct Output {
+    buffer: [u8; FILE_SIZE],
+    length: usize,
+}
+
+impl Write for Output {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let bytes: &[u8] = s.as_bytes();
+        if self.length + bytes.len() > FILE_SIZE {
+            return Err(fmt::Error);
+        }
+        self.buffer[self.length..self.length + bytes.len()].copy_from_slice(bytes);
+        self.length += bytes.len();
+        Ok(())
+    }
+}
+
+impl Config {
+    pub const fn new() -> Self {
+        Config {
+            hostname: Text::from(SYSTEM_NAME.as_bytes()),
+            timezone: 0,
+            prompt: Text::from(DEFAULT_PROMPT),
+            keymap: Text::from(b"us"),
+            foreground: COLOR_WHITE,
+            background: COLOR_BLACK,
+            startup: Text::from(b"autoexec.sh"),
+        }
+    }
+
+    pub fn restore(&mut self) {
+        *self = Config::new();
+        let id: i32 = unsafe { FILES.open(CONFIG_FILE, O_RDONLY) };
+        if id < 0 {
+            return;
+        }
+        let mut buffer: [u8; FILE_SIZE] = [0; FILE_SIZE];
+        let mut length: usize = 0;
+        while length < FILE_SIZE {
+            let read: i32 = unsafe { FILES.read(id as usize, &mut buffer[length..]) };
+            if read <= 0 {
+                break;
+            }
+            length += read as usize;
+        }
+        unsafe {
+            FILES.release(id as usize);
+        }
+        for line in buffer[..length].split(|&c| c == b'\n') {
+            let line: &[u8] = line.strip_suffix(b"\r").unwrap_or(line).trim_ascii_start();
+            if line.is_empty() || line[0] == b'#' {
+                continue;
+            }
+            if let Some(equals) = line.iter().position(|&c| c == b'=') {
+                self.set(line[..equals].trim_ascii(), line[equals + 1..].trim_ascii_start());
+            }
+        }
+    }
+
+    pub fn save(&self) -> i32 {
+        let mut output: Output = Output { buffer: [0; FILE_SIZE], length: 0 };
+        let _ = writeln!(output, "# {} system configuration", SYSTEM_NAME);
+        for key in KEYS.iter() {
+            let _ = write!(output, "{}=", key);
+            let _ = self.write_value(key.as_bytes(), &mut output);
+            if writeln!(output).is_err() {
+                return -ENOSPC;
+            }
+        }
+        let id: i32 = unsafe { FILES.open(CONFIG_FILE, O_WRONLY | O_CREAT | O_TRUNC) };
+        if id < 0 {
+            return id;
+        }
+        let result: i32 = unsafe { FILES.write(id as usize, &output.buffer[..output.length]) };
+        unsafe {
+            FILES.release(id as usize);
+        }
+        if result < 0 { result } else { 0 }
+    }
+
+    pub fn apply(&self) -> i32 {
+        unsafe {
+            CONSOLES.set_default_colors(self.foreground, self.background);
+            LAYOUT.load(self.keymap.get())
+        }
+    }
+
+    pub fn update(&mut self, key: &[u8], value: &[u8]) -> i32 {
+        if unsafe { TASK_MANAGER.get_credentials() }.0 != 0 {
+            return -EPERM;
+        }
+        let previous: Config = *self;
+        if !self.set(key, value) {
+            return -EINVAL;
+        }
+        let mut result: i32 = self.apply();
+        if result == 0 {
+            result = self.save();
+        }
+        if result < 0 {
+            *self = previous;
+            self.apply();
+        }
+        result
+    }
+
+    pub fn set(&mut self, key: &[u8], value: &[u8]) -> bool {
+        let value: &[u8] = if key == b"prompt" { value } else { value.trim_ascii() };
+        match key {
+            b"hostname" => is_hostname(value) && self.hostname.set(value),
+            b"timezone" => match parse_timezone(value) {
+                Some(minutes) => {
+                    self.timezone = minutes;
+                    true
+                }
+                None => false,
+            },
+            b"prompt" => self.prompt.set(value),
+            b"keymap" => !value.is_empty() && value.iter().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()) && self.keymap.set(value),
+            b"foreground" | b"background" => match parse_color(value) {
+                Some(color) if key == b"foreground" => {
+                    self.foreground = color;
+                    true
+                }
+                Some(color) => {
+                    self.background = color;
+                    true
+                }
+                None => false,
+            },
+            b"startup" => self.startup.set(value),
+            _ => false,
+        }
+    }
+
+    pub fn write_value(&self, key: &[u8], output: &mut dyn Write) -> fmt::Result {
+        match key {
+            b"hostname" => output.write_str(self.get_hostname()),
+            b"timezone" => write!(output, "{}", Timezone(self.timezone)),
+            b"prompt" => output.write_str(str::from_utf8(self.prompt.get()).unwrap_or("")),
+            b"keymap" => output.write_str(str::from_utf8(self.keymap.get()).unwrap_or("")),
+            b"foreground" => output.write_str(COLOR_NAMES[self.foreground as usize & 0x0F]),
+            b"background" => output.write_str(COLOR_NAMES[self.background as usize & 0x0F]),
+            b"startup" => output.write_str(str::from_utf8(self.startup.get()).unwrap_or("")),
+            _ => Err(fmt::Error),
+        }
+    }
+
+    pub fn get_colors(&self) -> (u8, u8) {
+        (self.foreground, self.background)
+    }
+
+    pub fn get_hostname(&self) -> &str {
+        str::from_utf8(self.hostname.get()).unwrap_or(SYSTEM_NAME)
+    }
+
+    pub fn get_timezone(&self) -> i32 {
+        self.timezone
+    }
+
+    pub fn get_prompt(&self) -> &[u8] {
+        self.prompt.get()
+    }
+
+    pub fn get_keymap(&self) -> &[u8] {
+        self.keymap.get()
+    }
+
+    pub fn get_startup(&self) -> &[u8] {
+        self.startup.get()
+    }
+}
+
+pub fn is_hostname(name: &[u8]) -> bool {
+    !name.is_empty() && name.len() <= HOSTNAME_SIZE && name[0] != b'-' && name.iter().all(|&c| c.is_ascii_alphanumeric() || c == b'-' || c == b'.')
+}
+
+impl fmt::Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0 == 0 {
+            return f.write_str("UTC");
+        }
+        let sign: char = if self.0 < 0 { '-' } else { '+' };
+        write!(f, "UTC{}{:02}:{:02}", sign, self.0.abs() / 60, self.0.abs() % 60)
+    }
+}
+
+fn parse_timezone(text: &[u8]) -> Option<i32> {
+    let text: &[u8] = if text.len() >= 3 && (text[..3].eq_ignore_ascii_case(b"UTC") || text[..3].eq_ignore_ascii_case(b"GMT")) { &text[3..] } else { text };
+    let (sign, text): (i32, &[u8]) = match text.split_first() {
+        None => return Some(0),
+        Some((b'+', rest)) => (1, rest),
+        Some((b'-', rest)) => (-1, rest),
+        _ => return None,
+    };
+    let (hours, minutes): (&[u8], &[u8]) = match text.iter().position(|&c| c == b':') {
+        Some(colon) => (&text[..colon], &text[colon + 1..]),
+        None => (text, b"0"),
+    };
+    let hours: i32 = str::from_utf8(hours).ok()?.parse::<u8>().ok()? as i32;
+    let minutes: i32 = str::from_utf8(minutes).ok()?.parse::<u8>().ok()? as i32;
+    let offset: i32 = hours * 60 + minutes;
+    if minutes >= 60 || offset > MAX_OFFSET {
+        return None;
+    }
+    Some(sign * offset)
+}
+
+fn parse_color(text: &[u8]) -> Option<u8> {
+    if let Some(index) = COLOR_NAMES.iter().position(|name| name.as_bytes().eq_ignore_ascii_case(text)) {
+        return Some(index as u8);
+    }
+    match str::from_utf8(text).ok()?.parse::<u8>() {
+        Ok(color) if color < 16 => Some(color),
+        _ => None,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+// Project Name:  MinmusOS
+// File Name:     descriptor.rs
+// File Function: File descriptors
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::file::FILES;
+use crate::ipc::pipe::PIPES;
+use lib::errno::{EBADF, ESPIPE};
+
+pub const MAX_FILES: usize = 8;
+pub const STDIN: usize = 0;
+pub const STDOUT: usize = 1;
+pub const STDERR: usize = 2;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Descriptor {
+    Closed,
+    Console,
+    PipeReader(usize),
+    PipeWriter(usize),
+    File(usize),
+}
+
+impl Descriptor {
+    pub fn read(&self, buffer: &mut [u8]) -> i32 {
+        match *self {
+            Descriptor::Console => unsafe {
+                CONSOLES.current().read(buffer)
+            },
+            Descriptor::PipeReader(id) => unsafe {
+                PIPES.read(id, buffer)
+            },
+            Descriptor::File(id) => unsafe {
+                FILES.read(id, buffer)
+            },
+            _ => -EBADF,
+        }
+    }
+
+    pub fn write(&self, buffer: &[u8]) -> i32 {
+        match *self {
+            Descriptor::Console => unsafe {
+                CONSOLES.current().write(buffer)
+            },
+            Descriptor::PipeWriter(id) => unsafe {
+                PIPES.write(id, buffer)
+            },
+            Descriptor::File(id) => unsafe {
+                FILES.write(id, buffer)
+            },
+            _ => -EBADF,
+        }
+    }
+
+    pub fn seek(&self, offset: i32, whence: u32) -> i32 {
+        match *self {
+            Descriptor::File(id) => unsafe {
+                FILES.seek(id, offset, whence)
+            },
+            Descriptor::Closed => -EBADF,
+            _ => -ESPIPE,
+        }
+    }
+
+    pub fn duplicate(&self) {
+        match *self {
+            Descriptor::PipeReader(id) => unsafe {
+                PIPES.add_reader(id);
+            },
+            Descriptor::PipeWriter(id) => unsafe {
+                PIPES.add_writer(id);
+            },
+            Descriptor::File(id) => unsafe {
+                FILES.add_reference(id);
+            },
+            _ => {}
+        }
+    }
+
+    pub fn close(&self) {
+        match *self {
+            Descriptor::PipeReader(id) => unsafe {
+                PIPES.close_reader(id);
+            },
+            Descriptor::PipeWriter(id) => unsafe {
+                PIPES.close_writer(id);
+            },
+            Descriptor::File(id) => unsafe {
+                FILES.release(id);
+            },
+            _ => {}
+        }
+    }
+}
\ No newline at end of file
@@ -5,11 +5,27 @@
 // License:       MIT License
 
 use crate::drivers::disk::DISK;
-use lib::mutex::Mutex;
+use crate::multitasking::mutex::Mutex;
+use crate::timer::time::Time;
+use lib::errno::{EINVAL, ENAMETOOLONG, ENOENT, ENOSPC};
 
-const ENTRY_COUNT: usize = 512;
+pub const ATTRIBUTE_HIDDEN: u8 = 0x02;
+pub const ATTRIBUTE_VOLUME: u8 = 0x08;
+pub const ATTRIBUTE_DIRECTORY: u8 = 0x10;
+pub const ATTRIBUTE_ARCHIVE: u8 = 0x20;
+pub const ENTRY_COUNT: usize = 512;
+pub const ACCESS_READ: u16 = 0o4;
+pub const ACCESS_WRITE: u16 = 0o2;
+pub const ACCESS_EXECUTE: u16 = 0o1;
+pub const DEFAULT_MODE: u16 = 0o644;
+const LEGACY_MODE: u16 = 0o755;
+const MODE_VALID: u16 = 0x8000;
+pub const MAX_OWNER: u16 = 0xFF;
 const FAT_START: u16 = 36864;
 const FAT_SIZE: usize = 256;
+const DELETED_ENTRY: u8 = 0xE5;
+const FREE_CLUSTER: u16 = 0x0000;
+const END_OF_CHAIN: u16 = 0xFFFF;
 
 pub static mut FAT: Mutex<FatDriver> = Mutex::new(FatDriver {
     header: NULL_HEADER,
@@ -72,17 +88,17 @@ static NULL_HEADER: Header = Header {
 #[repr(C, packed)]
 pub struct Entry {
     pub name: [u8; 11],
-    attributes: u8,
+    pub attributes: u8,
     reserved: u8,
     created_time_tenths: u8,
     created_time: u16,
     created_date: u16,
     accessed_date: u16,
     first_cluster_high: u16,
-    modified_time: u16,
-    modified_date: u16,
-    first_cluster_low: u16,
-    size: u32,
+    pub modified_time: u16,
+    pub modified_date: u16,
+    pub first_cluster_low: u16,
+    pub size: u32,
 }
 
 static NULL_ENTRY: Entry = Entry {
@@ -100,6 +116,30 @@ static NULL_ENTRY: Entry = Entry {
     size: 0,
 };
 
+impl Entry {
+    pub fn is_visible(&self) -> bool {
+        self.name[0] != 0 && self.name[0] != DELETED_ENTRY && self.attributes & ATTRIBUTE_VOLUME == 0
+    }
+
+    pub fn owner(&self) -> (u16, u16) {
+        if self.first_cluster_high & MODE_VALID == 0 { (0, 0) } else { (self.reserved as u16, self.created_time_tenths as u16) }
+    }
+
+    pub fn mode(&self) -> u16 {
+        let mode: u16 = self.first_cluster_high;
+        if mode & MODE_VALID == 0 { LEGACY_MODE } else { mode & 0o777 }
+    }
+
+    pub fn allows(&self, uid: u16, gid: u16, access: u16) -> bool {
+        if uid == 0 {
+            return true;
+        }
+        let (owner, group): (u16, u16) = self.owner();
+        let shift: u16 = if uid == owner { 6 } else if gid == group { 3 } else { 0 };
+        (self.mode() >> shift) & access == access
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct FatDriver {
     pub header: Header,
@@ -129,19 +169,6 @@ impl FatDriver {
         }
     }
 
-    pub fn list_entries(&self) {
-        lib::println!("Filename     Size");
-        for i in 0..ENTRY_COUNT {
-            if self.entries[i].name[0] != 0 {
-                for c in self.entries[i].name {
-                    lib::print!("{}", c as char);
-                }
-                let size = self.entries[i].size;
-                lib::println!("  {} bytes", size);
-            }
-        }
-    }
-
     pub fn load_table(&mut self) {
         let target: *mut u16 = &mut self.table as *mut u16;
         let lba: u64 = FAT_START as u64 + self.header.reserved_sectors as u64;
@@ -182,24 +209,274 @@ impl FatDriver {
         }
     }
 
-    pub fn search_file(&self, name: &[char]) -> &Entry {
-        for entry in self.entries.iter() {
-            let mut found: bool = true;
-            let mut i: usize = 0;
-            for n in name {
-                let mut c: char = n.clone();
-                if c.is_ascii_lowercase() {
-                    c = c.to_ascii_uppercase();
+    pub fn search_file(&self, name: &[u8]) -> &Entry {
+        let index: i32 = match short_name(name) {
+            Ok(short) => self.find_entry(&short),
+            Err(error) => error,
+        };
+        if index < 0 { &NULL_ENTRY } else { &self.entries[index as usize] }
+    }
+
+    pub fn find_entry(&self, name: &[u8; 11]) -> i32 {
+        match self.entries.iter().position(|entry| entry.is_visible() && entry.name == *name) {
+            Some(index) => index as i32,
+            None => -ENOENT,
+        }
+    }
+
+    pub fn create_entry(&mut self, name: &[u8; 11]) -> i32 {
+        for i in 0..ENTRY_COUNT {
+            if self.entries[i].name[0] == 0 || self.entries[i].name[0] == DELETED_ENTRY {
+                self.entries[i] = NULL_ENTRY;
+                self.entries[i].name = *name;
+                self.entries[i].attributes = ATTRIBUTE_ARCHIVE;
+                self.touch_entry(i);
+                return i as i32;
+            }
+        }
+        -ENOSPC
+    }
+
+    pub fn remove_entry(&mut self, index: usize) {
+        self.truncate(index);
+        self.entries[index].name[0] = DELETED_ENTRY;
+        self.save_entry(index);
+    }
+
+    pub fn rename_entry(&mut self, index: usize, name: &[u8; 11]) {
+        self.entries[index].name = *name;
+        self.save_entry(index);
+    }
+
+    // Ownership lives in spare directory entry bytes: reserved (the NT case-flags byte) holds the uid,
+    // created_time_tenths holds the gid and first_cluster_high holds the mode plus the MODE_VALID flag.
+    pub fn set_owner(&mut self, index: usize, uid: u16, gid: u16, mode: u16) {
+        self.entries[index].reserved = uid as u8;
+        self.entries[index].created_time_tenths = gid as u8;
+        self.entries[index].first_cluster_high = MODE_VALID | (mode & 0o777);
+        self.save_entry(index);
+    }
+
+    pub fn touch_entry(&mut self, index: usize) {
+        let time: Time = Time::local();
+        self.entries[index].modified_date = (time.get_year().saturating_sub(1980) << 9) | ((time.get_month() as u16) << 5) | time.get_day() as u16;
+        self.entries[index].modified_time = ((time.get_hour() as u16) << 11) | ((time.get_minute() as u16) << 5) | (time.get_second() as u16 / 2);
+        self.save_entry(index);
+    }
+
+    pub fn truncate(&mut self, index: usize) {
+        let mut cluster: u16 = self.entries[index].first_cluster_low;
+        while cluster >= 2 && cluster < END_OF_CHAIN - 8 {
+            let next: u16 = self.get_cluster(cluster);
+            self.set_cluster(cluster, FREE_CLUSTER);
+            cluster = next;
+        }
+        self.entries[index].first_cluster_low = 0;
+        self.entries[index].size = 0;
+        self.save_entry(index);
+    }
+
+    pub fn read_at(&mut self, index: usize, offset: u32, buffer: &mut [u8]) -> i32 {
+        let size: u32 = self.entries[index].size;
+        if offset >= size {
+            return 0;
+        }
+        let cluster_size: u32 = self.cluster_size() as u32;
+        let length: u32 = (buffer.len() as u32).min(size - offset);
+        let mut cluster: u16 = self.entries[index].first_cluster_low;
+        for _ in 0..offset / cluster_size {
+            cluster = self.get_cluster(cluster);
+        }
+        let mut done: u32 = 0;
+        while done < length && cluster >= 2 && cluster < END_OF_CHAIN - 8 {
+            let start: u32 = (offset + done) % cluster_size;
+            let count: u32 = (cluster_size - start).min(length - done);
+            self.read_cluster(cluster);
+            buffer[done as usize..(done + count) as usize].copy_from_slice(&self.buffer[start as usize..(start + count) as usize]);
+            done += count;
+            cluster = self.get_cluster(cluster);
+        }
+        done as i32
+    }
+
+    pub fn write_at(&mut self, index: usize, offset: u32, data: &[u8]) -> i32 {
+        let cluster_size: u32 = self.cluster_size() as u32;
+        if self.entries[index].first_cluster_low == 0 {
+            let cluster: u16 = self.allocate_cluster();
+            if cluster == 0 {
+                return -ENOSPC;
+            }
+            self.entries[index].first_cluster_low = cluster;
+        }
+        let mut cluster: u16 = self.entries[index].first_cluster_low;
+        for _ in 0..offset / cluster_size {
+            cluster = self.next_or_allocate(cluster);
+            if cluster == 0 {
+                return -ENOSPC;
+            }
+        }
+        let mut done: u32 = 0;
+        while done < data.len() as u32 {
+            let start: u32 = (offset + done) % cluster_size;
+            let count: u32 = (cluster_size - start).min(data.len() as u32 - done);
+            self.read_cluster(cluster);
+            self.buffer[start as usize..(start + count) as usize].copy_from_slice(&data[done as usize..(done + count) as usize]);
+            self.write_cluster(cluster);
+            done += count;
+            if done < data.len() as u32 {
+                cluster = self.next_or_allocate(cluster);
+                if cluster == 0 {
+                    break;
                 }
-                if (c != entry.name[i] as char) && (name[i] != '\0') {
-                    found = false;
+            }
+        }
+        if offset + done > self.entries[index].size {
+            self.entries[index].size = offset + done;
+        }
+        self.touch_entry(index);
+        if done == 0 && !data.is_empty() {
+            return -ENOSPC;
+        }
+        done as i32
+    }
+
+    fn next_or_allocate(&mut self, cluster: u16) -> u16 {
+        let next: u16 = self.get_cluster(cluster);
+        if next >= 2 && next < END_OF_CHAIN - 8 {
+            return next;
+        }
+        let next: u16 = self.allocate_cluster();
+        if next != 0 {
+            self.set_cluster(cluster, next);
+        }
+        next
+    }
+
+    fn allocate_cluster(&mut self) -> u16 {
+        let entries_per_sector: usize = self.header.bytes_per_sector as usize / 2;
+        let mut sector: [u16; FAT_SIZE] = [0; FAT_SIZE];
+        for i in 0..self.header.sectors_per_fat as usize {
+            self.read_table_sector(i, &mut sector);
+            for (j, &value) in sector.iter().enumerate().take(entries_per_sector) {
+                let cluster: usize = i * entries_per_sector + j;
+                if cluster >= 2 && cluster < (END_OF_CHAIN - 8) as usize && value == FREE_CLUSTER {
+                    self.set_cluster(cluster as u16, END_OF_CHAIN);
+                    self.buffer = [0; 2048];
+                    self.write_cluster(cluster as u16);
+                    return cluster as u16;
                 }
-                i += 1;
             }
-            if found {
-                return entry;
+        }
+        0
+    }
+
+    fn get_cluster(&self, cluster: u16) -> u16 {
+        let entries_per_sector: usize = self.header.bytes_per_sector as usize / 2;
+        let mut sector: [u16; FAT_SIZE] = [0; FAT_SIZE];
+        self.read_table_sector(cluster as usize / entries_per_sector, &mut sector);
+        sector[cluster as usize % entries_per_sector]
+    }
+
+    fn set_cluster(&mut self, cluster: u16, value: u16) {
+        let entries_per_sector: usize = self.header.bytes_per_sector as usize / 2;
+        let index: usize = cluster as usize / entries_per_sector;
+        let mut sector: [u16; FAT_SIZE] = [0; FAT_SIZE];
+        self.read_table_sector(index, &mut sector);
+        sector[cluster as usize % entries_per_sector] = value;
+        if index == 0 {
+            self.table = sector;
+        }
+        for i in 0..self.header.fat_count as u64 {
+            let lba: u64 = FAT_START as u64 + self.header.reserved_sectors as u64 + i * self.header.sectors_per_fat as u64 + index as u64;
+            unsafe {
+                DISK.write(sector.as_ptr(), lba, 1);
             }
         }
-        &NULL_ENTRY
     }
+
+    fn read_table_sector(&self, index: usize, sector: &mut [u16; FAT_SIZE]) {
+        if index == 0 {
+            *sector = self.table;
+            return;
+        }
+        let lba: u64 = FAT_START as u64 + self.header.reserved_sectors as u64 + index as u64;
+        unsafe {
+            DISK.read(sector.as_mut_ptr(), lba, 1);
+        }
+    }
+
+    fn save_entry(&self, index: usize) {
+        let entries_per_sector: usize = self.header.bytes_per_sector as usize / size_of::<Entry>();
+        let first: usize = index - index % entries_per_sector;
+        let lba: u64 = self.root_lba() + (index / entries_per_sector) as u64;
+        unsafe {
+            DISK.write(&self.entries[first] as *const Entry, lba, 1);
+        }
+    }
+
+    fn read_cluster(&mut self, cluster: u16) {
+        let target: *mut u8 = self.buffer.as_mut_ptr();
+        unsafe {
+            DISK.read(target, self.cluster_lba(cluster), self.header.sectors_per_cluster as u16);
+        }
+    }
+
+    fn write_cluster(&self, cluster: u16) {
+        unsafe {
+            DISK.write(self.buffer.as_ptr(), self.cluster_lba(cluster), self.header.sectors_per_cluster as u16);
+        }
+    }
+
+    fn root_lba(&self) -> u64 {
+        FAT_START as u64 + (self.header.reserved_sectors + self.header.sectors_per_fat * self.header.fat_count as u16) as u64
+    }
+
+    fn cluster_lba(&self, cluster: u16) -> u64 {
+        self.root_lba() + 32 + (cluster as u64 - 2) * self.header.sectors_per_cluster as u64
+    }
+
+    fn cluster_size(&self) -> usize {
+        self.header.sectors_per_cluster as usize * self.header.bytes_per_sector as usize
+    }
+}
+
+pub fn short_name(path: &[u8]) -> Result<[u8; 11], i32> {
+    let path: &[u8] = if path.first() == Some(&b'/') { &path[1..] } else { path };
+    if path.is_empty() {
+        return Err(-EINVAL);
+    }
+    let mut name: [u8; 11] = [b' '; 11];
+    let dot: usize = path.iter().rposition(|&c| c == b'.').unwrap_or(path.len());
+    if dot == 0 {
+        return Err(-EINVAL);
+    }
+    let extension: &[u8] = if dot < path.len() { &path[dot + 1..] } else { &[] };
+    if dot > 8 || extension.len() > 3 {
+        return Err(-ENAMETOOLONG);
+    }
+    for (i, &c) in path[..dot].iter().chain(extension.iter()).enumerate() {
+        if !c.is_ascii_graphic() || b"\"*+,./:;<=>?[]|".contains(&c) {
+            return Err(-EINVAL);
+        }
+        let position: usize = if i < dot { i } else { 8 + i - dot };
+        name[position] = c.to_ascii_uppercase();
+    }
+    Ok(name)
+}
+
+pub fn display_name(name: &[u8; 11], target: &mut [u8; 12]) -> usize {
+    let mut length: usize = 0;
+    for &c in name[..8].iter().filter(|&&c| c != b' ') {
+        target[length] = c;
+        length += 1;
+    }
+    if name[8] != b' ' {
+        target[length] = b'.';
+        length += 1;
+        for &c in name[8..].iter().filter(|&&c| c != b' ') {
+            target[length] = c;
+            length += 1;
+        }
+    }
+    length
 }
\ No newline at end of file
@@ -0,0 +1,378 @@
+// Project Name:  MinmusOS
+// File Name:     file.rs
+// File Function: Open files
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::filesystem::fat::{display_name, short_name, Entry, FatDriver, ACCESS_READ, ACCESS_WRITE, ATTRIBUTE_DIRECTORY, DEFAULT_MODE, FAT, MAX_OWNER};
+use crate::multitasking::task::TASK_MANAGER;
+use lib::errno::{EACCES, EBADF, EBUSY, EINVAL, EISDIR, ENFILE, EPERM};
+
+pub const O_RDONLY: u32 = 0x0000;
+pub const O_WRONLY: u32 = 0x0001;
+pub const O_CREAT: u32 = 0x0040;
+pub const O_TRUNC: u32 = 0x0200;
+pub const O_APPEND: u32 = 0x0400;
+pub const SEEK_SET: u32 = 0;
+pub const SEEK_CUR: u32 = 1;
+pub const SEEK_END: u32 = 2;
+const O_ACCMODE: u32 = 0x0003;
+const MAX_OPEN_FILES: usize = 16;
+
+pub static mut FILES: FileManager = FileManager {
+    files: [NULL_FILE; MAX_OPEN_FILES],
+};
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct Stat {
+    pub size: u32,
+    pub attributes: u32,
+    pub cluster: u32,
+    pub modified_date: u32,
+    pub modified_time: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub mode: u32,
+}
+
+impl Stat {
+    pub const fn new() -> Self {
+        Stat {
+            size: 0,
+            attributes: 0,
+            cluster: 0,
+            modified_date: 0,
+            modified_time: 0,
+            uid: 0,
+            gid: 0,
+            mode: 0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct DirectoryEntry {
+    pub name: [u8; 12],
+    pub length: u32,
+    pub size: u32,
+    pub attributes: u32,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct OpenFile {
+    entry: usize,
+    position: u32,
+    flags: u32,
+    references: u8,
+}
+
+static NULL_FILE: OpenFile = OpenFile {
+    entry: 0,
+    position: 0,
+    flags: 0,
+    references: 0,
+};
+
+pub struct FileManager {
+    files: [OpenFile; MAX_OPEN_FILES],
+}
+
+impl FileManager {
+    pub fn open(&mut self, path: &[u8], flags: u32) -> i32 {
+        self.open_mode(path, flags, DEFAULT_MODE)
+    }
+
+    pub fn open_mode(&mut self, path: &[u8], flags: u32, mode: u16) -> i32 {
+        let name: [u8; 11] = match short_name(path) {
+            Ok(name) => name,
+            Err(error) => return error,
+        };
+        if flags & O_ACCMODE == O_ACCMODE {
+            return -EINVAL;
+        }
+        let slot: usize = match self.files.iter().position(|f| f.references == 0) {
+            Some(slot) => slot,
+            None => return -ENFILE,
+        };
+        let fat: &mut FatDriver = unsafe { FAT.acquire_mut() };
+        let (uid, gid): (u16, u16) = credentials();
+        let mut index: i32 = fat.find_entry(&name);
+        if index < 0 && flags & O_CREAT != 0 {
+            index = fat.create_entry(&name);
+            if index >= 0 {
+                fat.set_owner(index as usize, uid, gid, mode);
+            }
+        } else if index >= 0 && !fat.entries[index as usize].allows(uid, gid, access(flags)) {
+            index = -EACCES;
+        }
+        if index >= 0 && fat.entries[index as usize].attributes & ATTRIBUTE_DIRECTORY != 0 {
+            index = -EISDIR;
+        }
+        if index >= 0 && flags & O_TRUNC != 0 && flags & O_ACCMODE != O_RDONLY {
+            fat.truncate(index as usize);
+        }
+        unsafe {
+            FAT.free();
+        }
+        if index < 0 {
+            return index;
+        }
+        self.files[slot] = OpenFile {
+            entry: index as usize,
+            position: 0,
+            flags,
+            references: 1,
+        };
+        slot as i32
+    }
+
+    pub fn add_reference(&mut self, id: usize) {
+        self.files[id].references += 1;
+    }
+
+    pub fn release(&mut self, id: usize) {
+        if self.files[id].references > 0 {
+            self.files[id].references -= 1;
+        }
+    }
+
+    pub fn read(&mut self, id: usize, buffer: &mut [u8]) -> i32 {
+        let file: &mut OpenFile = &mut self.files[id];
+        if file.flags & O_ACCMODE == O_WRONLY {
+            return -EBADF;
+        }
+        let fat: &mut FatDriver = unsafe { FAT.acquire_mut() };
+        let result: i32 = fat.read_at(file.entry, file.position, buffer);
+        unsafe {
+            FAT.free();
+        }
+        if result > 0 {
+            file.position += result as u32;
+        }
+        result
+    }
+
+    pub fn write(&mut self, id: usize, buffer: &[u8]) -> i32 {
+        let file: &mut OpenFile = &mut self.files[id];
+        if file.flags & O_ACCMODE == O_RDONLY {
+            return -EBADF;
+        }
+        let fat: &mut FatDriver = unsafe { FAT.acquire_mut() };
+        if file.flags & O_APPEND != 0 {
+            file.position = fat.entries[file.entry].size;
+        }
+        let result: i32 = fat.write_at(file.entry, file.position, buffer);
+        unsafe {
+            FAT.free();
+        }
+        if result > 0 {
+            file.position += result as u32;
+        }
+        result
+    }
+
+    pub fn seek(&mut self, id: usize, offset: i32, whence: u32) -> i32 {
+        let size: u32 = unsafe {
+            let size: u32 = FAT.acquire().entries[self.files[id].entry].size;
+            FAT.free();
+            size
+        };
+        let base: i64 = match whence {
+            SEEK_SET => 0,
+            SEEK_CUR => self.files[id].position as i64,
+            SEEK_END => size as i64,
+            _ => return -EINVAL,
+        };
+        let position: i64 = base + offset as i64;
+        if position < 0 || position > i32::MAX as i64 {
+            return -EINVAL;
+        }
+        self.files[id].position = position as u32;
+        position as i32
+    }
+
+    pub fn stat(&self, path: &[u8], stat: &mut Stat) -> i32 {
+        let name: [u8; 11] = match short_name(path) {
+            Ok(name) => name,
+            Err(error) => return error,
+        };
+        let fat: &FatDriver = unsafe { FAT.acquire() };
+        let index: i32 = fat.find_entry(&name);
+        if index >= 0 {
+            let entry: &Entry = &fat.entries[index as usize];
+            let (uid, gid): (u16, u16) = entry.owner();
+            *stat = Stat {
+                size: entry.size,
+                attributes: entry.attributes as u32,
+                cluster: entry.first_cluster_low as u32,
+                modified_date: entry.modified_date as u32,
+                modified_time: entry.modified_time as u32,
+                uid: uid as u32,
+                gid: gid as u32,
+                mode: entry.mode() as u32,
+            };
+        }
+        unsafe {
+            FAT.free();
+        }
+        if index < 0 { index } else { 0 }
+    }
+
+    pub fn read_directory(&self, cursor: usize, target: &mut DirectoryEntry) -> i32 {
+        let fat: &FatDriver = unsafe { FAT.acquire() };
+        let mut next: i32 = 0;
+        for (i, entry) in fat.entries.iter().enumerate().skip(cursor) {
+            if entry.name[0] == 0 {
+                break;
+            }
+            if entry.is_visible() {
+                target.name = [0; 12];
+                target.length = display_name(&entry.name, &mut target.name) as u32;
+                target.size = entry.size;
+                target.attributes = entry.attributes as u32;
+                next = i as i32 + 1;
+                break;
+            }
+        }
+        unsafe {
+            FAT.free();
+        }
+        next
+    }
+
+    pub fn unlink(&self, path: &[u8]) -> i32 {
+        let name: [u8; 11] = match short_name(path) {
+            Ok(name) => name,
+            Err(error) => return error,
+        };
+        let fat: &mut FatDriver = unsafe { FAT.acquire_mut() };
+        let mut index: i32 = fat.find_entry(&name);
+        if index >= 0 && self.files.iter().any(|f| f.references > 0 && f.entry == index as usize) {
+            index = -EBUSY;
+        }
+        if index >= 0 && fat.entries[index as usize].attributes & ATTRIBUTE_DIRECTORY != 0 {
+            index = -EISDIR;
+        }
+        if index >= 0 && !writable(&fat.entries[index as usize]) {
+            index = -EACCES;
+        }
+        if index >= 0 {
+            fat.remove_entry(index as usize);
+        }
+        unsafe {
+            FAT.free();
+        }
+        if index < 0 { index } else { 0 }
+    }
+
+    pub fn touch(&self, path: &[u8]) -> i32 {
+        let name: [u8; 11] = match short_name(path) {
+            Ok(name) => name,
+            Err(error) => return error,
+        };
+        let fat: &mut FatDriver = unsafe { FAT.acquire_mut() };
+        let (uid, gid): (u16, u16) = credentials();
+        let mut index: i32 = fat.find_entry(&name);
+        if index < 0 {
+            index = fat.create_entry(&name);
+            if index >= 0 {
+                fat.set_owner(index as usize, uid, gid, DEFAULT_MODE);
+            }
+        } else if fat.entries[index as usize].attributes & ATTRIBUTE_DIRECTORY != 0 {
+            index = -EISDIR;
+        } else if !writable(&fat.entries[index as usize]) {
+            index = -EACCES;
+        } else {
+            fat.touch_entry(index as usize);
+        }
+        unsafe {
+            FAT.free();
+        }
+        if index < 0 { index } else { 0 }
+    }
+
+    pub fn rename(&self, from: &[u8], to: &[u8]) -> i32 {
+        let source: [u8; 11] = match short_name(from) {
+            Ok(name) => name,
+            Err(error) => return error,
+        };
+        let target: [u8; 11] = match short_name(to) {
+            Ok(name) => name,
+            Err(error) => return error,
+        };
+        let fat: &mut FatDriver = unsafe { FAT.acquire_mut() };
+        let mut index: i32 = fat.find_entry(&source);
+        let existing: i32 = fat.find_entry(&target);
+        if index >= 0 && !writable(&fat.entries[index as usize]) {
+            index = -EACCES;
+        }
+        if index >= 0 && existing >= 0 && existing != index {
+            if !writable(&fat.entries[existing as usize]) {
+                index = -EACCES;
+            } else if self.files.iter().any(|f| f.references > 0 && f.entry == existing as usize) {
+                index = -EBUSY;
+            } else if fat.entries[existing as usize].attributes & ATTRIBUTE_DIRECTORY != 0 {
+                index = -EISDIR;
+            } else {
+                fat.remove_entry(existing as usize);
+            }
+        }
+        if index >= 0 {
+            fat.rename_entry(index as usize, &target);
+        }
+        unsafe {
+            FAT.free();
+        }
+        if index < 0 { index } else { 0 }
+    }
+
+    pub fn chmod(&self, path: &[u8], mode: u16) -> i32 {
+        self.change_owner(path, |entry: &Entry, uid: u16| uid == 0 || entry.owner().0 == uid, |entry: &Entry| (entry.owner(), mode))
+    }
+
+    pub fn chown(&self, path: &[u8], owner: u16, group: u16) -> i32 {
+        if owner > MAX_OWNER || group > MAX_OWNER {
+            return -EINVAL;
+        }
+        self.change_owner(path, |_entry: &Entry, uid: u16| uid == 0, |entry: &Entry| ((owner, group), entry.mode()))
+    }
+
+    fn change_owner(&self, path: &[u8], permitted: impl Fn(&Entry, u16) -> bool, change: impl Fn(&Entry) -> ((u16, u16), u16)) -> i32 {
+        let name: [u8; 11] = match short_name(path) {
+            Ok(name) => name,
+            Err(error) => return error,
+        };
+        let fat: &mut FatDriver = unsafe { FAT.acquire_mut() };
+        let mut index: i32 = fat.find_entry(&name);
+        if index >= 0 && !permitted(&fat.entries[index as usize], credentials().0) {
+            index = -EPERM;
+        }
+        if index >= 0 {
+            let ((uid, gid), mode): ((u16, u16), u16) = change(&fat.entries[index as usize]);
+            fat.set_owner(index as usize, uid, gid, mode);
+        }
+        unsafe {
+            FAT.free();
+        }
+        if index < 0 { index } else { 0 }
+    }
+}
+
+fn credentials() -> (u16, u16) {
+    unsafe { TASK_MANAGER.get_credentials() }
+}
+
+fn writable(entry: &Entry) -> bool {
+    let (uid, gid): (u16, u16) = credentials();
+    entry.allows(uid, gid, ACCESS_WRITE)
+}
+
+fn access(flags: u32) -> u16 {
+    match flags & O_ACCMODE {
+        O_RDONLY => ACCESS_READ,
+        O_WRONLY => ACCESS_WRITE,
+        _ => ACCESS_READ | ACCESS_WRITE,
+    }
+}
\ No newline at end of file
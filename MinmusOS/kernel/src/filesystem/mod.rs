@@ -4,4 +4,7 @@
 // Author:        Jishen Lin
 // License:       MIT License
 
-pub mod fat;
\ No newline at end of file
+pub mod config;
+pub mod descriptor;
+pub mod fat;
+pub mod file;
\ No newline at end of file
@@ -5,13 +5,19 @@
 // License:       MIT License
 
 use core::arch::asm;
-use crate::syscalls::print::{COLOR_BLUE, COLOR_LIGHT_WHITE, PRINTER};
+use crate::drivers::console::CONSOLES;
+use crate::ipc::signal;
+use crate::syscalls::print::{COLOR_BLUE, COLOR_LIGHT_WHITE};
 
 #[no_mangle]
 pub extern "C" fn exception_handler(int: u32, eip: u32, cs: u32, eflags: u32) {
     unsafe {
-        PRINTER.set_colors(COLOR_LIGHT_WHITE, COLOR_BLUE);
-        PRINTER.clear();
+        if int >= 0x0B && int <= 0x0E {
+            signal::fault(int, cs);
+        }
+        CONSOLES.switch(CONSOLES.get_current_id());
+        CONSOLES.printer().set_colors(COLOR_LIGHT_WHITE, COLOR_BLUE);
+        CONSOLES.printer().clear();
     }
     lib::println!();
     lib::println!("  ========== MinmusOS v1.0 ==========                            By Jishen Lin");
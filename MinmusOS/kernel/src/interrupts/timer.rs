@@ -9,8 +9,10 @@ use crate::multitasking::task::CPUState;
 use crate::multitasking::task::TASK_MANAGER;
 use crate::memory::paging::PAGING;
 use crate::memory::paging::TABLES;
+use crate::memory::shared::SHARED;
 
 pub const TIMER_INT: u8 = 32;
+pub const YIELD_INT: u8 = 0x81;
 const APP_TARGET: u32 = 0x00a0_0000;
 const APP_SIZE: u32 = 0x0001_0000;
 
@@ -43,15 +45,54 @@ pub extern "C" fn timer() {
     }
 }
 
+#[naked]
+pub extern "C" fn yield_task() {
+    unsafe {
+        core::arch::asm!(
+        "push ebp",
+        "push edi",
+        "push esi",
+        "push edx",
+        "push ecx",
+        "push ebx",
+        "push eax",
+        "push esp",
+        "call yield_handler",
+        "mov esp, eax",
+        "pop eax",
+        "pop ebx",
+        "pop ecx",
+        "pop edx",
+        "pop esi",
+        "pop edi",
+        "pop ebp",
+        "iretd",
+        options(noreturn),
+        );
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn timer_handler(esp: u32) -> u32 {
+    let new_esp: u32 = switch_task(esp);
+    PICS.end_interrupt(TIMER_INT);
+    new_esp
+}
+
+#[no_mangle]
+pub extern "C" fn yield_handler(esp: u32) -> u32 {
+    switch_task(esp)
+}
+
+fn switch_task(esp: u32) -> u32 {
     unsafe {
         let new_esp: u32 = TASK_MANAGER.schedule(esp as *mut CPUState) as u32;
-        let slot = TASK_MANAGER.get_current_slot();
-        let target = APP_TARGET + (slot as u32 * APP_SIZE);
+        let process = TASK_MANAGER.get_current_process();
+        let target = APP_TARGET + (process as u32 * APP_SIZE);
         TABLES[8].set(target);
         PAGING.set_table(8, &TABLES[8]);
-        PICS.end_interrupt(TIMER_INT);
+        SHARED.load(process as usize);
+        PAGING.flush();
         new_esp
     }
 }
\ No newline at end of file
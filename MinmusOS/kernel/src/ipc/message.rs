@@ -0,0 +1,167 @@
+// Project Name:  MinmusOS
+// File Name:     message.rs
+// File Function: Message-passing between tasks
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::multitasking::task::{MAX_TASKS, TASK_MANAGER};
+use core::ptr;
+use lib::errno::{E2BIG, EAGAIN, EINTR, EINVAL, ESRCH};
+
+pub const MAX_MESSAGE_SIZE: usize = 64;
+const MAILBOX_SIZE: usize = 4;
+
+pub static mut MAILBOXES: MailboxManager = MailboxManager {
+    mailboxes: [NULL_MAILBOX; MAX_TASKS as usize],
+};
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct Message {
+    pub sender: u32,
+    pub length: u32,
+    pub data: [u8; MAX_MESSAGE_SIZE],
+}
+
+static NULL_MESSAGE: Message = Message {
+    sender: 0,
+    length: 0,
+    data: [0; MAX_MESSAGE_SIZE],
+};
+
+#[derive(Copy, Clone, Debug)]
+pub struct Mailbox {
+    messages: [Message; MAILBOX_SIZE],
+    head: usize,
+    count: usize,
+    reply: Message,
+    reply_ready: bool,
+    awaiting: i8,
+}
+
+static NULL_MAILBOX: Mailbox = Mailbox {
+    messages: [NULL_MESSAGE; MAILBOX_SIZE],
+    head: 0,
+    count: 0,
+    reply: NULL_MESSAGE,
+    reply_ready: false,
+    awaiting: -1,
+};
+
+pub struct MailboxManager {
+    mailboxes: [Mailbox; MAX_TASKS as usize],
+}
+
+impl MailboxManager {
+    pub fn reset(&mut self, id: usize) {
+        self.mailboxes[id] = NULL_MAILBOX;
+    }
+
+    pub fn send(&mut self, target: usize, message: &Message, blocking: bool) -> i32 {
+        let sender: i8 = unsafe { TASK_MANAGER.get_current_slot() };
+        if !Self::is_alive(target) || target == sender as usize {
+            return -ESRCH;
+        }
+        if message.length as usize > MAX_MESSAGE_SIZE {
+            return -E2BIG;
+        }
+        while self.count(target) == MAILBOX_SIZE {
+            if !blocking {
+                return -EAGAIN;
+            }
+            unsafe {
+                if TASK_MANAGER.is_interrupted() {
+                    return -EINTR;
+                }
+                TASK_MANAGER.yield_now();
+            }
+            if !Self::is_alive(target) {
+                return -ESRCH;
+            }
+        }
+        let mailbox: &mut Mailbox = &mut self.mailboxes[target];
+        let slot: usize = (mailbox.head + mailbox.count) % MAILBOX_SIZE;
+        mailbox.messages[slot] = *message;
+        mailbox.messages[slot].sender = sender as u32;
+        mailbox.count += 1;
+        0
+    }
+
+    pub fn receive(&mut self, message: &mut Message, blocking: bool) -> i32 {
+        let id: usize = unsafe { TASK_MANAGER.get_current_slot() } as usize;
+        while self.count(id) == 0 {
+            if !blocking {
+                return -EAGAIN;
+            }
+            unsafe {
+                if TASK_MANAGER.is_interrupted() {
+                    return -EINTR;
+                }
+                TASK_MANAGER.yield_now();
+            }
+        }
+        let mailbox: &mut Mailbox = &mut self.mailboxes[id];
+        *message = mailbox.messages[mailbox.head];
+        mailbox.head = (mailbox.head + 1) % MAILBOX_SIZE;
+        mailbox.count -= 1;
+        0
+    }
+
+    pub fn call(&mut self, target: usize, message: &mut Message) -> i32 {
+        let id: usize = unsafe { TASK_MANAGER.get_current_slot() } as usize;
+        self.mailboxes[id].reply_ready = false;
+        self.mailboxes[id].awaiting = target as i8;
+        let result: i32 = self.send(target, message, true);
+        if result < 0 {
+            self.mailboxes[id].awaiting = -1;
+            return result;
+        }
+        while !self.reply_ready(id) {
+            if !Self::is_alive(target) {
+                self.mailboxes[id].awaiting = -1;
+                return -ESRCH;
+            }
+            unsafe {
+                if TASK_MANAGER.is_interrupted() {
+                    self.mailboxes[id].awaiting = -1;
+                    return -EINTR;
+                }
+                TASK_MANAGER.yield_now();
+            }
+        }
+        *message = self.mailboxes[id].reply;
+        self.mailboxes[id].reply_ready = false;
+        self.mailboxes[id].awaiting = -1;
+        0
+    }
+
+    pub fn reply(&mut self, target: usize, message: &Message) -> i32 {
+        let sender: i8 = unsafe { TASK_MANAGER.get_current_slot() };
+        if !Self::is_alive(target) {
+            return -ESRCH;
+        }
+        if message.length as usize > MAX_MESSAGE_SIZE {
+            return -E2BIG;
+        }
+        let mailbox: &mut Mailbox = &mut self.mailboxes[target];
+        if mailbox.awaiting != sender || mailbox.reply_ready {
+            return -EINVAL;
+        }
+        mailbox.reply = *message;
+        mailbox.reply.sender = sender as u32;
+        mailbox.reply_ready = true;
+        0
+    }
+
+    fn count(&self, id: usize) -> usize {
+        unsafe { ptr::read_volatile(&self.mailboxes[id].count) }
+    }
+
+    fn reply_ready(&self, id: usize) -> bool {
+        unsafe { ptr::read_volatile(&self.mailboxes[id].reply_ready) }
+    }
+
+    fn is_alive(id: usize) -> bool {
+        id < MAX_TASKS as usize && unsafe { TASK_MANAGER.tasks[id].running }
+    }
+}
\ No newline at end of file
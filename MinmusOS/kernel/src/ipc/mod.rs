@@ -0,0 +1,9 @@
+// Project Name:  MinmusOS
+// File Name:     mod.rs
+// File Function: The module file of ipc
+// Author:        Jishen Lin
+// License:       MIT License
+
+pub mod message;
+pub mod pipe;
+pub mod signal;
\ No newline at end of file
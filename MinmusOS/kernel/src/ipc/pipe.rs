@@ -0,0 +1,139 @@
+// Project Name:  MinmusOS
+// File Name:     pipe.rs
+// File Function: Inter-process pipes
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::multitasking::task::TASK_MANAGER;
+use core::ptr;
+use lib::errno::{EINTR, EPIPE};
+
+pub const MAX_PIPES: usize = 16;
+const PIPE_SIZE: usize = 512;
+
+pub static mut PIPES: PipeManager = PipeManager {
+    pipes: [NULL_PIPE; MAX_PIPES],
+};
+
+#[derive(Copy, Clone, Debug)]
+pub struct Pipe {
+    buffer: [u8; PIPE_SIZE],
+    head: usize,
+    count: usize,
+    readers: u8,
+    writers: u8,
+    used: bool,
+}
+
+static NULL_PIPE: Pipe = Pipe {
+    buffer: [0; PIPE_SIZE],
+    head: 0,
+    count: 0,
+    readers: 0,
+    writers: 0,
+    used: false,
+};
+
+pub struct PipeManager {
+    pipes: [Pipe; MAX_PIPES],
+}
+
+impl PipeManager {
+    pub fn create(&mut self) -> i8 {
+        match self.pipes.iter().position(|pipe| !pipe.used) {
+            Some(id) => {
+                self.pipes[id] = NULL_PIPE;
+                self.pipes[id].used = true;
+                self.pipes[id].readers = 1;
+                self.pipes[id].writers = 1;
+                id as i8
+            }
+            None => -1,
+        }
+    }
+
+    pub fn read(&mut self, id: usize, buffer: &mut [u8]) -> i32 {
+        loop {
+            let (count, _, writers): (usize, u8, u8) = self.state(id);
+            if count > 0 || writers == 0 {
+                break;
+            }
+            unsafe {
+                if TASK_MANAGER.is_interrupted() {
+                    return -EINTR;
+                }
+                TASK_MANAGER.yield_now();
+            }
+        }
+        let pipe: &mut Pipe = &mut self.pipes[id];
+        let mut read: usize = 0;
+        while read < buffer.len() && pipe.count > 0 {
+            buffer[read] = pipe.buffer[pipe.head];
+            pipe.head = (pipe.head + 1) % PIPE_SIZE;
+            pipe.count -= 1;
+            read += 1;
+        }
+        read as i32
+    }
+
+    pub fn write(&mut self, id: usize, buffer: &[u8]) -> i32 {
+        let mut written: usize = 0;
+        while written < buffer.len() {
+            loop {
+                let (count, readers, _): (usize, u8, u8) = self.state(id);
+                if count < PIPE_SIZE || readers == 0 {
+                    break;
+                }
+                unsafe {
+                    if TASK_MANAGER.is_interrupted() {
+                        return -EINTR;
+                    }
+                    TASK_MANAGER.yield_now();
+                }
+            }
+            let pipe: &mut Pipe = &mut self.pipes[id];
+            if pipe.readers == 0 {
+                return -EPIPE;
+            }
+            while written < buffer.len() && pipe.count < PIPE_SIZE {
+                pipe.buffer[(pipe.head + pipe.count) % PIPE_SIZE] = buffer[written];
+                pipe.count += 1;
+                written += 1;
+            }
+        }
+        written as i32
+    }
+
+    fn state(&self, id: usize) -> (usize, u8, u8) {
+        let pipe: &Pipe = &self.pipes[id];
+        unsafe { (ptr::read_volatile(&pipe.count), ptr::read_volatile(&pipe.readers), ptr::read_volatile(&pipe.writers)) }
+    }
+
+    pub fn add_reader(&mut self, id: usize) {
+        self.pipes[id].readers += 1;
+    }
+
+    pub fn add_writer(&mut self, id: usize) {
+        self.pipes[id].writers += 1;
+    }
+
+    pub fn close_reader(&mut self, id: usize) {
+        if self.pipes[id].readers > 0 {
+            self.pipes[id].readers -= 1;
+        }
+        self.release(id);
+    }
+
+    pub fn close_writer(&mut self, id: usize) {
+        if self.pipes[id].writers > 0 {
+            self.pipes[id].writers -= 1;
+        }
+        self.release(id);
+    }
+
+    fn release(&mut self, id: usize) {
+        if self.pipes[id].readers == 0 && self.pipes[id].writers == 0 {
+            self.pipes[id].used = false;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,225 @@
+// Project Name:  MinmusOS
+// File Name:     signal.rs
+// File Function: POSIX-like signals
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::multitasking::task::{exit, is_kernel_address, MAX_TASKS, TASK_MANAGER};
+use core::arch::asm;
+use lib::errno::{EINVAL, EPERM, ESRCH};
+
+pub const MAX_SIGNALS: usize = 32;
+pub const SIGINT: u32 = 2;
+pub const SIGKILL: u32 = 9;
+pub const SIGSEGV: u32 = 11;
+pub const SIGTERM: u32 = 15;
+pub const SIGCHLD: u32 = 17;
+pub const SIGCONT: u32 = 18;
+pub const SIGSTOP: u32 = 19;
+pub const SIGTSTP: u32 = 20;
+pub const SIG_DFL: u32 = 0;
+pub const SIG_IGN: u32 = 1;
+
+pub static SIGNAL_NAMES: &[(u32, &str)] = &[
+    (SIGINT, "INT"),
+    (SIGKILL, "KILL"),
+    (SIGSEGV, "SEGV"),
+    (SIGTERM, "TERM"),
+    (SIGCHLD, "CHLD"),
+    (SIGCONT, "CONT"),
+    (SIGSTOP, "STOP"),
+    (SIGTSTP, "TSTP"),
+];
+
+pub static mut SIGNALS: SignalManager = SignalManager {
+    states: [NULL_STATE; MAX_TASKS as usize],
+};
+
+pub enum Action {
+    Continue,
+    Terminate,
+    Stop,
+    Handle(u32, u32),
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct SignalState {
+    pending: u32,
+    handlers: [u32; MAX_SIGNALS],
+}
+
+static NULL_STATE: SignalState = SignalState {
+    pending: 0,
+    handlers: [SIG_DFL; MAX_SIGNALS],
+};
+
+pub struct SignalManager {
+    states: [SignalState; MAX_TASKS as usize],
+}
+
+impl SignalManager {
+    pub fn reset(&mut self, id: usize) {
+        self.states[id] = NULL_STATE;
+    }
+
+    pub fn set_handler(&mut self, id: usize, signal: u32, handler: u32) -> i32 {
+        if signal == 0 || signal as usize >= MAX_SIGNALS || signal == SIGKILL || signal == SIGSTOP {
+            return -EINVAL;
+        }
+        let previous: u32 = self.states[id].handlers[signal as usize];
+        self.states[id].handlers[signal as usize] = handler;
+        previous as i32
+    }
+
+    pub fn send(&mut self, id: usize, signal: u32) -> i32 {
+        if signal == 0 || signal as usize >= MAX_SIGNALS {
+            return -EINVAL;
+        }
+        if id >= MAX_TASKS as usize || unsafe { !TASK_MANAGER.tasks[id].running } {
+            return -ESRCH;
+        }
+        if signal == SIGCONT || signal == SIGKILL {
+            self.states[id].pending &= !((1 << SIGSTOP) | (1 << SIGTSTP));
+            unsafe {
+                TASK_MANAGER.tasks[id].stopped = false;
+            }
+        }
+        self.states[id].pending |= 1 << signal;
+        0
+    }
+
+    pub fn kill(&mut self, id: usize, signal: u32) -> i32 {
+        if id < MAX_TASKS as usize && unsafe { TASK_MANAGER.tasks[id].running && !TASK_MANAGER.may_signal(id) } {
+            return -EPERM;
+        }
+        self.send(id, signal)
+    }
+
+    pub fn send_group(&mut self, group: i8, signal: u32) {
+        for id in 0..MAX_TASKS as usize {
+            if unsafe { TASK_MANAGER.is_in_group(id, group) } {
+                self.send(id, signal);
+            }
+        }
+    }
+
+    pub fn is_interrupted(&self, id: usize) -> bool {
+        let state: &SignalState = &self.states[id];
+        for signal in 1..MAX_SIGNALS {
+            if state.pending & (1 << signal) != 0 && !is_ignored(signal as u32, state.handlers[signal]) && !is_stop(signal as u32, state.handlers[signal]) {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn take(&mut self, id: usize, user_mode: bool) -> Action {
+        let state: &mut SignalState = &mut self.states[id];
+        for signal in 1..MAX_SIGNALS {
+            if state.pending & (1 << signal) == 0 {
+                continue;
+            }
+            let handler: u32 = state.handlers[signal];
+            if signal as u32 == SIGKILL {
+                state.pending &= !(1 << signal);
+                return Action::Terminate;
+            }
+            if is_ignored(signal as u32, handler) {
+                state.pending &= !(1 << signal);
+                continue;
+            }
+            if is_stop(signal as u32, handler) {
+                state.pending &= !(1 << signal);
+                return Action::Stop;
+            }
+            if !user_mode {
+                continue;
+            }
+            state.pending &= !(1 << signal);
+            if handler == SIG_DFL {
+                return Action::Terminate;
+            }
+            return Action::Handle(handler, signal as u32);
+        }
+        Action::Continue
+    }
+}
+
+fn is_ignored(signal: u32, handler: u32) -> bool {
+    handler == SIG_IGN || (handler == SIG_DFL && (signal == SIGCHLD || signal == SIGCONT))
+}
+
+fn is_stop(signal: u32, handler: u32) -> bool {
+    signal == SIGSTOP || (signal == SIGTSTP && handler == SIG_DFL)
+}
+
+pub fn signal_number(name: &[u8]) -> i32 {
+    let mut offset: usize = 0;
+    if name.len() > 3 && name[..3].eq_ignore_ascii_case(b"SIG") {
+        offset = 3;
+    }
+    if name.len() > offset && name[offset].is_ascii_digit() {
+        let mut number: u32 = 0;
+        for &c in name[offset..].iter() {
+            if !c.is_ascii_digit() {
+                return -1;
+            }
+            number = number.saturating_mul(10).saturating_add((c - b'0') as u32);
+        }
+        if number == 0 || number as usize >= MAX_SIGNALS {
+            return -1;
+        }
+        return number as i32;
+    }
+    for &(number, signal_name) in SIGNAL_NAMES.iter() {
+        if signal_name.len() == name.len() - offset && signal_name.as_bytes().eq_ignore_ascii_case(&name[offset..]) {
+            return number as i32;
+        }
+    }
+    -1
+}
+
+pub fn signal_name(signal: u32) -> &'static str {
+    for &(number, name) in SIGNAL_NAMES.iter() {
+        if number == signal {
+            return name;
+        }
+    }
+    "UNKNOWN"
+}
+
+pub unsafe fn fault(int: u32, eip: u32) {
+    let id: i8 = TASK_MANAGER.get_current_slot();
+    if id <= 0 || is_kernel_address(eip) {
+        return;
+    }
+    let signal: u32 = match int {
+        0x0B | 0x0C | 0x0D | 0x0E => SIGSEGV,
+        _ => return,
+    };
+    let handler: u32 = SIGNALS.states[id as usize].handlers[signal as usize];
+    if handler != SIG_DFL && handler != SIG_IGN {
+        let handler: extern "C" fn(u32) = core::mem::transmute(handler as usize);
+        asm!("sti");
+        handler(signal);
+    }
+    exit();
+}
+
+#[naked]
+pub extern "C" fn signal_return() {
+    unsafe {
+        asm!(
+        "add esp, 4",
+        "pop eax",
+        "pop ebx",
+        "pop ecx",
+        "pop edx",
+        "pop esi",
+        "pop edi",
+        "pop ebp",
+        "iretd",
+        options(noreturn),
+        );
+    }
+}
\ No newline at end of file
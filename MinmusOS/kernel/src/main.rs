@@ -13,6 +13,7 @@ extern crate alloc;
 mod drivers;
 mod filesystem;
 mod interrupts;
+mod ipc;
 mod memory;
 mod multitasking;
 mod shell;
@@ -21,13 +22,15 @@ mod timer;
 
 use core::arch::asm;
 use core::panic::PanicInfo;
+use drivers::console::{CONSOLES, MAX_CONSOLES};
 use drivers::disk::DISK;
+use drivers::layout::LAYOUT;
+use drivers::mouse::MOUSE;
 use drivers::pic::PICS;
 use interrupts::idt::IDT;
 use memory::allocator::Allocator;
 use memory::paging::PAGING;
-use shell::shell::SHELL;
-use syscalls::print::PRINTER;
+use filesystem::config::{CONFIG, SYSTEM_NAME, SYSTEM_VERSION};
 use filesystem::fat::FAT;
 use multitasking::task::TASK_MANAGER;
 use lib;
@@ -45,21 +48,26 @@ const STACK_START: u32 = KERNEL_START + KERNEL_SIZE + STACK_SIZE;
 #[link_section = ".start"]
 pub extern "C" fn _start() -> ! {
     unsafe {
-        PRINTER.prints("[INFO] Initializing Stack...\n");
+        CONSOLES.init();
+        CONSOLES.printer().prints("[INFO] Initializing Stack...\n");
         asm!("mov esp, {}", in(reg) STACK_START);
 
-        PRINTER.prints("[INFO] Initializing Paging...\n");
+        CONSOLES.printer().prints("[INFO] Initializing Paging...\n");
         PAGING.identity();
         PAGING.enable();
         asm!("xchg bx, bx");
 
-        PRINTER.prints("[INFO] Loading Interrupt Descriptor Table...\n");
+        CONSOLES.printer().prints("[INFO] Loading Interrupt Descriptor Table...\n");
         IDT.init();
         IDT.add_exceptions();
         IDT.add(
             interrupts::timer::TIMER_INT as usize,
             interrupts::timer::timer as u32,
         );
+        IDT.add(
+            interrupts::timer::YIELD_INT as usize,
+            interrupts::timer::yield_task as u32,
+        );
         IDT.add(
             syscalls::handler::SYSCALL_INT as usize,
             syscalls::handler::syscall as u32,
@@ -68,12 +76,21 @@ pub extern "C" fn _start() -> ! {
             drivers::keyboard::KEYBOARD_INT as usize,
             drivers::keyboard::keyboard as u32,
         );
+        IDT.add(
+            drivers::mouse::MOUSE_INT as usize,
+            drivers::mouse::mouse as u32,
+        );
         IDT.load();
 
-        PRINTER.prints("[INFO] Initializing Programmable Interrupt Controllers...\n");
+        CONSOLES.printer().prints("[INFO] Initializing Programmable Interrupt Controllers...\n");
         PICS.init();
 
-        PRINTER.prints("[INFO] Initializing FAT16 File System...\n");
+        CONSOLES.printer().prints("[INFO] Initializing PS/2 Mouse...\n");
+        if !MOUSE.init() {
+            CONSOLES.printer().prints("[INFO] No PS/2 mouse detected.\n");
+        }
+
+        CONSOLES.printer().prints("[INFO] Initializing FAT16 File System...\n");
         DISK.check();
         if DISK.enabled {
             let fat = FAT.acquire_mut();
@@ -83,12 +100,25 @@ pub extern "C" fn _start() -> ! {
             FAT.free();
         }
 
-        PRINTER.prints("[INFO] Initializing Multitasking...\n");
+        CONSOLES.printer().prints("[INFO] Loading System Configuration...\n");
+        CONFIG.restore();
+        let (fg_color, bg_color): (u8, u8) = CONFIG.get_colors();
+        CONSOLES.set_default_colors(fg_color, bg_color);
+
+        CONSOLES.printer().prints("[INFO] Loading Keyboard Layout...\n");
+        LAYOUT.restore(CONFIG.get_keymap());
+
+        CONSOLES.printer().prints("[INFO] Initializing Multitasking...\n");
         TASK_MANAGER.init();
 
-        PRINTER.prints("[INFO] Initializing Shell...\n");
+        CONSOLES.printer().prints("[INFO] Initializing Shell...\n");
         print_info();
-        SHELL.init();
+        for i in 0..MAX_CONSOLES {
+            let slot: i8 = TASK_MANAGER.add_kernel_thread(shell::shell::shell as u32, i as u32);
+            if slot >= 0 {
+                TASK_MANAGER.set_console(slot as usize, i);
+            }
+        }
         asm!("xchg bx, bx");
         asm!("sti");
 
@@ -99,8 +129,9 @@ pub extern "C" fn _start() -> ! {
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
     unsafe {
-        PRINTER.set_colors(COLOR_LIGHT_WHITE, COLOR_RED);
-        PRINTER.clear();
+        CONSOLES.switch(CONSOLES.get_current_id());
+        CONSOLES.printer().set_colors(COLOR_LIGHT_WHITE, COLOR_RED);
+        CONSOLES.printer().clear();
     }
     lib::println!();
     lib::println!("  ========== MinmusOS v1.0 ==========                            By Jishen Lin");
@@ -131,15 +162,15 @@ fn panic(info: &PanicInfo) -> ! {
 
 fn print_info() {
     unsafe {
-        PRINTER.set_colors(COLOR_LIGHT_YELLOW, COLOR_BLACK);
+        CONSOLES.printer().set_colors(COLOR_LIGHT_YELLOW, COLOR_BLACK);
     }
     lib::println!();
-    lib::println!("  Welcome to MinmusOS v1.0!");
+    lib::println!("  Welcome to {} {}!", SYSTEM_NAME, SYSTEM_VERSION);
     lib::println!("  MIT License - Copyright (c) 2024 Jishen Lin");
     lib::println!();
     lib::println!("  Type \"help\" and press enter to show available commands.");
     lib::println!();
     unsafe {
-        PRINTER.reset_colors();
+        CONSOLES.printer().reset_colors();
     }
 }
\ No newline at end of file
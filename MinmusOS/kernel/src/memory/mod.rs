@@ -5,4 +5,5 @@
 // License:       MIT License
 
 pub mod allocator;
-pub mod paging;
\ No newline at end of file
+pub mod paging;
+pub mod shared;
\ No newline at end of file
@@ -31,6 +31,16 @@ impl PageDirectory {
         }
     }
 
+    pub fn flush(&self) {
+        unsafe {
+            core::arch::asm!(
+            "mov eax, cr3",
+            "mov cr3, eax",
+            out("eax") _,
+            );
+        }
+    }
+
     pub fn identity(&mut self) {
         unsafe {
             for i in 0..8 {
@@ -0,0 +1,249 @@
+// Project Name:  MinmusOS
+// File Name:     shared.rs
+// File Function: Shared memory regions
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::memory::paging::{PAGING, TABLES};
+use crate::multitasking::task::MAX_TASKS;
+use lib::errno::{EEXIST, EINVAL, ENOMEM};
+
+pub const SHARED_WINDOW: u32 = 0x02400000;
+pub const MAX_NAME_LENGTH: usize = 16;
+const SHARED_TABLE: usize = 9;
+const POOL_START: u32 = 0x00C00000;
+const POOL_PAGES: usize = 256;
+const PAGE_SIZE: u32 = 0x1000;
+const WINDOW_PAGES: usize = 1024;
+const MAX_REGIONS: usize = 16;
+const MAX_MAPPINGS: usize = 8;
+
+pub static mut SHARED: SharedMemoryManager = SharedMemoryManager {
+    regions: [NULL_REGION; MAX_REGIONS],
+    mappings: [[NULL_MAPPING; MAX_MAPPINGS]; MAX_TASKS as usize],
+    frames: [false; POOL_PAGES],
+};
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct SharedMemory {
+    pub name: [u8; MAX_NAME_LENGTH],
+    pub size: u32,
+    pub address: u32,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Region {
+    name: [u8; MAX_NAME_LENGTH],
+    base: u32,
+    pages: usize,
+    references: u8,
+    used: bool,
+}
+
+static NULL_REGION: Region = Region {
+    name: [0; MAX_NAME_LENGTH],
+    base: 0,
+    pages: 0,
+    references: 0,
+    used: false,
+};
+
+#[derive(Copy, Clone, Debug)]
+pub struct Mapping {
+    region: i8,
+    page: usize,
+}
+
+static NULL_MAPPING: Mapping = Mapping {
+    region: -1,
+    page: 0,
+};
+
+pub struct SharedMemoryManager {
+    regions: [Region; MAX_REGIONS],
+    mappings: [[Mapping; MAX_MAPPINGS]; MAX_TASKS as usize],
+    frames: [bool; POOL_PAGES],
+}
+
+impl SharedMemoryManager {
+    pub fn map(&mut self, id: usize, request: &mut SharedMemory) -> i32 {
+        if request.name[0] == 0 {
+            return -EINVAL;
+        }
+        let mut region: i8 = self.find(&request.name);
+        if region < 0 {
+            if request.size == 0 {
+                return -EINVAL;
+            }
+            region = self.create(&request.name, request.size);
+            if region < 0 {
+                return -ENOMEM;
+            }
+        }
+        let pages: usize = self.regions[region as usize].pages;
+        let page: i32 = if request.address == 0 {
+            self.find_window(id, pages)
+        } else if request.address < SHARED_WINDOW || request.address >= SHARED_WINDOW + WINDOW_PAGES as u32 * PAGE_SIZE || request.address % PAGE_SIZE != 0 {
+            -EINVAL
+        } else {
+            let page: usize = ((request.address - SHARED_WINDOW) / PAGE_SIZE) as usize;
+            if self.is_window_free(id, page, pages) { page as i32 } else { -EEXIST }
+        };
+        let slot: Option<usize> = self.mappings[id].iter().position(|m| m.region < 0);
+        if page < 0 || slot.is_none() {
+            self.release(region as usize);
+            return if page < 0 { page } else { -ENOMEM };
+        }
+        self.mappings[id][slot.unwrap()] = Mapping {
+            region,
+            page: page as usize,
+        };
+        self.regions[region as usize].references += 1;
+        request.size = (pages as u32) * PAGE_SIZE;
+        request.address = SHARED_WINDOW + page as u32 * PAGE_SIZE;
+        self.load(id);
+        0
+    }
+
+    pub fn unmap(&mut self, id: usize, address: u32) -> i32 {
+        if address < SHARED_WINDOW {
+            return -EINVAL;
+        }
+        let page: usize = ((address - SHARED_WINDOW) / PAGE_SIZE) as usize;
+        for i in 0..MAX_MAPPINGS {
+            let mapping: Mapping = self.mappings[id][i];
+            if mapping.region >= 0 && mapping.page == page {
+                self.mappings[id][i] = NULL_MAPPING;
+                self.regions[mapping.region as usize].references -= 1;
+                self.release(mapping.region as usize);
+                self.load(id);
+                return 0;
+            }
+        }
+        -EINVAL
+    }
+
+    pub fn unmap_all(&mut self, id: usize) {
+        for i in 0..MAX_MAPPINGS {
+            let mapping: Mapping = self.mappings[id][i];
+            if mapping.region >= 0 {
+                self.mappings[id][i] = NULL_MAPPING;
+                self.regions[mapping.region as usize].references -= 1;
+                self.release(mapping.region as usize);
+            }
+        }
+    }
+
+    pub fn is_mapped(&self, id: usize, start: u32, end: u32) -> bool {
+        for mapping in self.mappings[id].iter() {
+            if mapping.region >= 0 {
+                let base: u32 = SHARED_WINDOW + mapping.page as u32 * PAGE_SIZE;
+                let size: u32 = self.regions[mapping.region as usize].pages as u32 * PAGE_SIZE;
+                if start >= base && end <= base + size {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    pub fn load(&self, id: usize) {
+        unsafe {
+            let table = &mut TABLES[SHARED_TABLE];
+            for entry in table.entries.iter_mut() {
+                *entry = 0;
+            }
+            for mapping in self.mappings[id].iter() {
+                if mapping.region < 0 {
+                    continue;
+                }
+                let region: &Region = &self.regions[mapping.region as usize];
+                for i in 0..region.pages {
+                    table.entries[mapping.page + i] = (region.base + i as u32 * PAGE_SIZE) | 0b011;
+                }
+            }
+            PAGING.set_table(SHARED_TABLE, &TABLES[SHARED_TABLE]);
+        }
+    }
+
+    fn find(&self, name: &[u8; MAX_NAME_LENGTH]) -> i8 {
+        match self.regions.iter().position(|region| region.used && region.name == *name) {
+            Some(index) => index as i8,
+            None => -1,
+        }
+    }
+
+    fn create(&mut self, name: &[u8; MAX_NAME_LENGTH], size: u32) -> i8 {
+        let pages: usize = size.div_ceil(PAGE_SIZE) as usize;
+        if pages == 0 || pages > POOL_PAGES {
+            return -1;
+        }
+        let slot: usize = match self.regions.iter().position(|r| !r.used) {
+            Some(slot) => slot,
+            None => return -1,
+        };
+        let mut start: usize = 0;
+        while start + pages <= POOL_PAGES {
+            match self.frames[start..start + pages].iter().position(|&used| used) {
+                Some(offset) => start += offset + 1,
+                None => {
+                    for frame in self.frames[start..start + pages].iter_mut() {
+                        *frame = true;
+                    }
+                    let base: u32 = POOL_START + start as u32 * PAGE_SIZE;
+                    unsafe {
+                        core::ptr::write_bytes(base as *mut u8, 0, pages * PAGE_SIZE as usize);
+                    }
+                    self.regions[slot] = Region {
+                        name: *name,
+                        base,
+                        pages,
+                        references: 0,
+                        used: true,
+                    };
+                    return slot as i8;
+                }
+            }
+        }
+        -1
+    }
+
+    fn release(&mut self, region: usize) {
+        if self.regions[region].references > 0 {
+            return;
+        }
+        let start: usize = ((self.regions[region].base - POOL_START) / PAGE_SIZE) as usize;
+        for frame in self.frames[start..start + self.regions[region].pages].iter_mut() {
+            *frame = false;
+        }
+        self.regions[region] = NULL_REGION;
+    }
+
+    fn find_window(&self, id: usize, pages: usize) -> i32 {
+        let mut page: usize = 0;
+        while page + pages <= WINDOW_PAGES {
+            if self.is_window_free(id, page, pages) {
+                return page as i32;
+            }
+            page += 1;
+        }
+        -ENOMEM
+    }
+
+    fn is_window_free(&self, id: usize, page: usize, pages: usize) -> bool {
+        if page + pages > WINDOW_PAGES {
+            return false;
+        }
+        for mapping in self.mappings[id].iter() {
+            if mapping.region < 0 {
+                continue;
+            }
+            let end: usize = mapping.page + self.regions[mapping.region as usize].pages;
+            if page < end && mapping.page < page + pages {
+                return false;
+            }
+        }
+        true
+    }
+}
\ No newline at end of file
@@ -4,4 +4,5 @@
 // Author:        Jishen Lin
 // License:       MIT License
 
+pub mod mutex;
 pub mod task;
\ No newline at end of file
@@ -0,0 +1,39 @@
+// Project Name:  MinmusOS
+// File Name:     mutex.rs
+// File Function: Kernel mutex that defers signals while it is held
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::multitasking::task::TASK_MANAGER;
+use lib::mutex::Mutex as Lock;
+
+pub struct Mutex<T> {
+    lock: Lock<T>,
+}
+
+impl<T> Mutex<T> {
+    pub const fn new(value: T) -> Self {
+        Self { lock: Lock::new(value) }
+    }
+
+    pub fn acquire_mut(&mut self) -> &mut T {
+        unsafe {
+            TASK_MANAGER.enter_critical();
+        }
+        self.lock.acquire_mut()
+    }
+
+    pub fn acquire(&mut self) -> &T {
+        unsafe {
+            TASK_MANAGER.enter_critical();
+        }
+        self.lock.acquire()
+    }
+
+    pub fn free(&self) {
+        self.lock.free();
+        unsafe {
+            TASK_MANAGER.leave_critical();
+        }
+    }
+}
\ No newline at end of file
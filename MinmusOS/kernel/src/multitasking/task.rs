@@ -4,10 +4,19 @@
 // Author:        Jishen Lin
 // License:       MIT License
 
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::descriptor::{Descriptor, MAX_FILES, STDERR, STDIN, STDOUT};
+use crate::ipc::message::MAILBOXES;
+use crate::ipc::signal::{signal_return, Action, SIGCHLD, SIGKILL, SIGNALS};
+use crate::memory::shared::SHARED;
 use core::arch::asm;
+use core::ptr;
+use lib::errno::{EBADF, EINTR, EINVAL, EMFILE, ESRCH};
 
 pub const MAX_TASKS: i8 = 32;
-const STACK_SIZE: usize = 4096;
+const STACK_SIZE: usize = 8192;
+const KERNEL_START: u32 = 0x00100000;
+const KERNEL_END: u32 = 0x00200000;
 
 #[repr(C, packed)]
 pub struct CPUState {
@@ -30,17 +39,38 @@ pub struct Task {
     pub stack: [u8; STACK_SIZE],
     pub cpu_state_ptr: u32,
     pub running: bool,
+    pub stopped: bool,
+    pub parent: i8,
+    pub group: i8,
+    pub process: i8,
+    pub console: u8,
+    pub uid: u16,
+    pub gid: u16,
+    pub files: [Descriptor; MAX_FILES],
+    pub status: i32,
+    pub critical: u8,
 }
 
 static NULL_TASK: Task = Task {
     stack: [0; STACK_SIZE],
     cpu_state_ptr: 0u32,
     running: false,
+    stopped: false,
+    parent: -1,
+    group: -1,
+    process: -1,
+    console: 0,
+    uid: 0,
+    gid: 0,
+    files: [Descriptor::Closed; MAX_FILES],
+    status: 0,
+    critical: 0,
 };
 
 impl Task {
-    pub fn init(&mut self, entry_point: u32) {
+    pub fn init(&mut self, entry_point: u32, argument: u32) {
         self.running = true;
+        self.stopped = false;
         let mut state = &self.stack as *const u8;
         unsafe {
             state = state.byte_add(STACK_SIZE);
@@ -59,8 +89,52 @@ impl Task {
             (*cpu_state).eip = entry_point;
             (*cpu_state).cs = 0x8;
             (*cpu_state).eflags = 0x202;
+            (*cpu_state).esp = exit as u32;
+            (*cpu_state).ss = argument;
         }
     }
+
+    pub fn in_user_mode(&self) -> bool {
+        let cpu_state = self.cpu_state_ptr as *const CPUState;
+        let eip: u32 = unsafe { (*cpu_state).eip };
+        !is_kernel_address(eip)
+    }
+
+    pub fn push_signal_frame(&mut self, handler: u32, signal: u32) {
+        let mut state = self.cpu_state_ptr as *const u8;
+        unsafe {
+            state = state.byte_sub(size_of::<CPUState>());
+        }
+        self.cpu_state_ptr = state as u32;
+        let cpu_state = self.cpu_state_ptr as *mut CPUState;
+        unsafe {
+            (*cpu_state).eax = 0;
+            (*cpu_state).ebx = 0;
+            (*cpu_state).ecx = 0;
+            (*cpu_state).edx = 0;
+            (*cpu_state).esi = 0;
+            (*cpu_state).edi = 0;
+            (*cpu_state).ebp = 0;
+            (*cpu_state).eip = handler;
+            (*cpu_state).cs = 0x8;
+            (*cpu_state).eflags = 0x202;
+            (*cpu_state).esp = signal_return as u32;
+            (*cpu_state).ss = signal;
+        }
+    }
+
+    pub fn push_argument<T: Copy>(&mut self, value: &T) {
+        let size: usize = size_of::<T>().next_multiple_of(4);
+        let frame = self.cpu_state_ptr as *mut CPUState;
+        let argument = unsafe { frame.byte_add(size_of::<CPUState>()).byte_sub(size) } as *mut T;
+        let cpu_state = unsafe { frame.byte_sub(size) };
+        unsafe {
+            ptr::copy(frame, cpu_state, 1);
+            argument.write(*value);
+            (*cpu_state).ss = argument as u32;
+        }
+        self.cpu_state_ptr = cpu_state as u32;
+    }
 }
 
 pub struct TaskManager {
@@ -77,18 +151,115 @@ pub static mut TASK_MANAGER: TaskManager = TaskManager {
 
 impl TaskManager {
     pub fn init(&mut self) {
-        self.add_task(idle as u32);
+        self.add_task(idle as u32, 0);
     }
 
-    pub fn add_task(&mut self, entry_point: u32) {
-        self.tasks[self.get_free_slot() as usize].init(entry_point);
+    pub fn add_task(&mut self, entry_point: u32, argument: u32) -> i8 {
+        self.create_task(entry_point, argument, false)
+    }
+
+    pub fn add_task_with<T: Copy>(&mut self, entry_point: u32, argument: &T) -> i8 {
+        let slot: i8 = self.create_task(entry_point, 0, false);
+        if slot >= 0 {
+            self.tasks[slot as usize].push_argument(argument);
+        }
+        slot
+    }
+
+    pub fn add_thread(&mut self, entry_point: u32, argument: u32) -> i8 {
+        self.create_task(entry_point, argument, true)
+    }
+
+    pub fn add_kernel_thread(&mut self, entry_point: u32, argument: u32) -> i8 {
+        let current: i8 = self.current_task;
+        self.current_task = -1;
+        let slot: i8 = self.create_task(entry_point, argument, false);
+        self.current_task = current;
+        if slot >= 0 {
+            self.tasks[slot as usize].process = 0;
+        }
+        slot
+    }
+
+    fn create_task(&mut self, entry_point: u32, argument: u32, thread: bool) -> i8 {
+        let slot: i8 = self.get_free_slot();
+        if slot < 0 {
+            return slot;
+        }
+        let mut files: [Descriptor; MAX_FILES] = [Descriptor::Closed; MAX_FILES];
+        let mut group: i8 = slot;
+        let mut process: i8 = slot;
+        let mut console: u8 = 0;
+        let mut credentials: (u16, u16) = (0, 0);
+        if self.current_task >= 0 {
+            let current: &Task = &self.tasks[self.current_task as usize];
+            if current.group >= 0 {
+                group = current.group;
+            }
+            if thread {
+                process = current.process;
+            }
+            console = current.console;
+            credentials = (current.uid, current.gid);
+            files = current.files;
+            for file in files.iter() {
+                file.duplicate();
+            }
+        } else {
+            group = -1;
+            files[STDIN] = Descriptor::Console;
+            files[STDOUT] = Descriptor::Console;
+            files[STDERR] = Descriptor::Console;
+        }
+        let task: &mut Task = &mut self.tasks[slot as usize];
+        task.init(entry_point, argument);
+        task.parent = self.current_task;
+        task.group = group;
+        task.process = process;
+        task.console = console;
+        (task.uid, task.gid) = credentials;
+        task.files = files;
+        task.status = 0;
+        task.critical = 0;
+        unsafe {
+            MAILBOXES.reset(slot as usize);
+            SIGNALS.reset(slot as usize);
+        }
         self.task_count += 1;
+        slot
     }
 
     pub fn remove_task(&mut self, id: usize) {
-        if id != 0 {
-            self.tasks[id] = NULL_TASK;
+        if id != 0 && self.tasks[id].running {
+            for file in self.tasks[id].files.iter_mut() {
+                file.close();
+                *file = Descriptor::Closed;
+            }
+            self.tasks[id].running = false;
             self.task_count -= 1;
+            unsafe {
+                MAILBOXES.reset(id);
+                SIGNALS.reset(id);
+                if self.tasks[id].process == id as i8 {
+                    SHARED.unmap_all(id);
+                }
+                if self.tasks[id].parent >= 0 {
+                    SIGNALS.send(self.tasks[id].parent as usize, SIGCHLD);
+                }
+            }
+            for (i, task) in self.tasks.iter().enumerate().skip(1) {
+                if i != id && task.running && task.process == id as i8 {
+                    unsafe {
+                        SIGNALS.send(i, SIGKILL);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn stop_task(&mut self, id: usize) {
+        if id != 0 && self.tasks[id].running {
+            self.tasks[id].stopped = true;
         }
     }
 
@@ -96,6 +267,160 @@ impl TaskManager {
         self.remove_task(self.current_task as usize);
     }
 
+    pub fn enter_critical(&mut self) {
+        if self.current_task >= 0 {
+            self.tasks[self.current_task as usize].critical += 1;
+        }
+    }
+
+    pub fn leave_critical(&mut self) {
+        if self.current_task >= 0 {
+            let task: &mut Task = &mut self.tasks[self.current_task as usize];
+            task.critical = task.critical.saturating_sub(1);
+        }
+    }
+
+    pub fn yield_now(&self) {
+        unsafe {
+            asm!("int 0x81");
+        }
+    }
+
+    pub fn wait_task(&self, id: usize) {
+        while self.is_task_running(id) && !self.is_interrupted() {
+            self.yield_now();
+        }
+    }
+
+    pub fn join_thread(&self, id: usize) -> i32 {
+        if id >= MAX_TASKS as usize || !self.tasks[id].running {
+            return -ESRCH;
+        }
+        let task: &Task = &self.tasks[id];
+        if id == self.current_task as usize || task.process != self.get_current_process() || task.process == id as i8 {
+            return -EINVAL;
+        }
+        self.wait_task(id);
+        if self.is_task_running(id) {
+            return -EINTR;
+        }
+        0
+    }
+
+    pub fn wait_job(&mut self, group: i8, id: usize) {
+        if !self.is_foreground() {
+            self.wait_task(id);
+            return;
+        }
+        unsafe {
+            let previous: i8 = CONSOLES.current().foreground;
+            CONSOLES.current().foreground = group;
+            self.wait_task(id);
+            CONSOLES.current().foreground = previous;
+        }
+    }
+
+    pub fn wait_group(&mut self, group: i8) -> bool {
+        unsafe {
+            let previous: i8 = CONSOLES.current().foreground;
+            CONSOLES.current().foreground = group;
+            while self.is_group_running(group) && !self.is_group_stopped(group) && !self.is_interrupted() {
+                self.yield_now();
+            }
+            CONSOLES.current().foreground = previous;
+        }
+        self.is_group_stopped(group)
+    }
+
+    pub fn is_task_running(&self, id: usize) -> bool {
+        unsafe { ptr::read_volatile(&self.tasks[id].running) }
+    }
+
+    pub fn is_group_running(&self, group: i8) -> bool {
+        (0..MAX_TASKS as usize).any(|id| self.is_task_running(id) && self.tasks[id].group == group)
+    }
+
+    pub fn is_in_group(&self, id: usize, group: i8) -> bool {
+        self.tasks[id].running && self.tasks[id].group == group
+    }
+
+    pub fn is_group_stopped(&self, group: i8) -> bool {
+        (0..MAX_TASKS as usize).any(|id| self.is_task_running(id) && unsafe { ptr::read_volatile(&self.tasks[id].stopped) } && self.tasks[id].group == group)
+    }
+
+    pub fn is_process_running(&self, process: i8) -> bool {
+        self.tasks.iter().any(|task| task.running && task.process == process)
+    }
+
+    pub fn is_foreground(&self) -> bool {
+        if self.current_task < 0 {
+            return true;
+        }
+        self.tasks[self.current_task as usize].group == unsafe { CONSOLES.current().foreground }
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        self.current_task >= 0 && unsafe { SIGNALS.is_interrupted(self.current_task as usize) }
+    }
+
+    pub fn wait_children(&self) {
+        loop {
+            let mut waiting: bool = false;
+            for i in 0..MAX_TASKS {
+                if self.is_task_running(i as usize) && self.tasks[i as usize].parent == self.current_task && i != self.current_task {
+                    waiting = true;
+                }
+            }
+            if !waiting || self.is_interrupted() {
+                return;
+            }
+            self.yield_now();
+        }
+    }
+
+    pub fn get_file(&self, fd: usize) -> Descriptor {
+        if self.current_task < 0 {
+            return Descriptor::Console;
+        }
+        if fd >= MAX_FILES {
+            return Descriptor::Closed;
+        }
+        self.tasks[self.current_task as usize].files[fd]
+    }
+
+    pub fn set_file(&mut self, id: usize, fd: usize, file: Descriptor) {
+        file.duplicate();
+        self.tasks[id].files[fd].close();
+        self.tasks[id].files[fd] = file;
+    }
+
+    pub fn open_file(&mut self, file: Descriptor) -> i32 {
+        if self.current_task < 0 {
+            return -EMFILE;
+        }
+        let files: &mut [Descriptor; MAX_FILES] = &mut self.tasks[self.current_task as usize].files;
+        match files.iter().position(|&descriptor| descriptor == Descriptor::Closed) {
+            Some(fd) => {
+                files[fd] = file;
+                fd as i32
+            }
+            None => -EMFILE,
+        }
+    }
+
+    pub fn close_file(&mut self, fd: usize) -> i32 {
+        if self.current_task < 0 || fd >= MAX_FILES {
+            return -EBADF;
+        }
+        let file: &mut Descriptor = &mut self.tasks[self.current_task as usize].files[fd];
+        if *file == Descriptor::Closed {
+            return -EBADF;
+        }
+        file.close();
+        *file = Descriptor::Closed;
+        0
+    }
+
     pub fn schedule(&mut self, cpu_state: *mut CPUState) -> *mut CPUState {
         if self.task_count <= 0 {
             return cpu_state;
@@ -103,17 +428,31 @@ impl TaskManager {
         if self.current_task >= 0 {
             self.tasks[self.current_task as usize].cpu_state_ptr = cpu_state as u32;
         }
-        self.current_task = self.get_next_task();
+        loop {
+            self.current_task = self.get_next_task();
+            let id: usize = self.current_task as usize;
+            if self.tasks[id].critical > 0 {
+                break;
+            }
+            match unsafe { SIGNALS.take(id, self.tasks[id].in_user_mode()) } {
+                Action::Continue => break,
+                Action::Terminate => self.remove_task(id),
+                Action::Stop => self.stop_task(id),
+                Action::Handle(handler, signal) => {
+                    self.tasks[id].push_signal_frame(handler, signal);
+                    break;
+                }
+            }
+        }
         self.tasks[self.current_task as usize].cpu_state_ptr as *mut CPUState
     }
 
     pub fn get_next_task(&self) -> i8 {
-        let mut i = self.current_task + 1;
-        while i < MAX_TASKS {
-            if self.tasks[i as usize].running {
+        for offset in 1..=MAX_TASKS {
+            let i: i8 = (self.current_task + offset) % MAX_TASKS;
+            if self.tasks[i as usize].running && !self.tasks[i as usize].stopped {
                 return i;
             }
-            i = (i + 1) % MAX_TASKS;
         }
         -1
     }
@@ -133,11 +472,79 @@ impl TaskManager {
         self.current_task
     }
 
+    pub fn is_process_stack(&self, start: u32, end: u32) -> bool {
+        let process: i8 = self.get_current_process();
+        for task in self.tasks.iter() {
+            if task.running && task.process == process {
+                let stack: u32 = task.stack.as_ptr() as u32;
+                if start >= stack && end <= stack + STACK_SIZE as u32 {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    pub fn get_current_console(&self) -> i8 {
+        if self.current_task < 0 {
+            return -1;
+        }
+        self.tasks[self.current_task as usize].console as i8
+    }
+
+    pub fn set_console(&mut self, id: usize, console: usize) {
+        self.tasks[id].console = console as u8;
+    }
+
+    pub fn get_credentials(&self) -> (u16, u16) {
+        if self.current_task < 0 {
+            return (0, 0);
+        }
+        let task: &Task = &self.tasks[self.current_task as usize];
+        (task.uid, task.gid)
+    }
+
+    pub fn may_signal(&self, id: usize) -> bool {
+        let uid: u16 = self.get_credentials().0;
+        uid == 0 || self.tasks[id].uid == uid
+    }
+
+    pub fn set_credentials(&mut self, uid: u16, gid: u16) {
+        if self.current_task >= 0 {
+            let task: &mut Task = &mut self.tasks[self.current_task as usize];
+            task.uid = uid;
+            task.gid = gid;
+        }
+    }
+
+    pub fn get_status(&self, id: usize) -> i32 {
+        self.tasks[id].status
+    }
+
+    pub fn set_status(&mut self, status: i32) {
+        if self.current_task >= 0 {
+            self.tasks[self.current_task as usize].status = status;
+        }
+    }
+
+    pub fn get_current_process(&self) -> i8 {
+        if self.current_task < 0 {
+            return -1;
+        }
+        self.tasks[self.current_task as usize].process
+    }
+
     pub fn list_tasks(&self) {
         lib::println!("Running tasks:");
         for i in 0..MAX_TASKS {
-            if self.tasks[i as usize].running {
-                lib::println!("PID: {}", i);
+            let task: &Task = &self.tasks[i as usize];
+            if task.running {
+                let state: &str = if task.stopped { " [stopped]" } else { "" };
+                if task.process == i {
+                    lib::println!("PID: {}{}", i, state);
+                } else {
+                    lib::println!("PID: {} (thread of PID {}){}", i, task.process, state);
+                }
             }
         }
     }
@@ -149,4 +556,17 @@ fn idle() {
             asm!("hlt");
         }
     }
+}
+
+pub fn is_kernel_address(address: u32) -> bool {
+    address >= KERNEL_START && address < KERNEL_END
+}
+
+pub extern "C" fn exit() {
+    unsafe {
+        TASK_MANAGER.remove_current_task();
+        loop {
+            TASK_MANAGER.yield_now();
+        }
+    }
 }
\ No newline at end of file
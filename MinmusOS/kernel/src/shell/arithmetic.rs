@@ -0,0 +1,134 @@
+// Project Name:  MinmusOS
+// File Name:     arithmetic.rs
+// File Function: Shell arithmetic expansion
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::shell::variables::Variables;
+
+struct Evaluator<'a> {
+    text: &'a [u8],
+    position: usize,
+    variables: &'a Variables,
+}
+
+pub fn evaluate(text: &[u8], variables: &Variables) -> Option<i32> {
+    let mut evaluator: Evaluator = Evaluator {
+        text,
+        position: 0,
+        variables,
+    };
+    let value: i32 = evaluator.comparison()?;
+    evaluator.skip_spaces();
+    if evaluator.position != text.len() {
+        return None;
+    }
+    Some(value)
+}
+
+impl Evaluator<'_> {
+    fn comparison(&mut self) -> Option<i32> {
+        let left: i32 = self.sum()?;
+        let operators: [(&[u8], fn(i32, i32) -> bool); 6] = [
+            (b"==", |a, b| a == b),
+            (b"!=", |a, b| a != b),
+            (b"<=", |a, b| a <= b),
+            (b">=", |a, b| a >= b),
+            (b"<", |a, b| a < b),
+            (b">", |a, b| a > b),
+        ];
+        for (symbol, compare) in operators.iter() {
+            if self.accept(symbol) {
+                let right: i32 = self.sum()?;
+                return Some(compare(left, right) as i32);
+            }
+        }
+        Some(left)
+    }
+
+    fn sum(&mut self) -> Option<i32> {
+        let mut value: i32 = self.product()?;
+        loop {
+            if self.accept(b"+") {
+                value = value.wrapping_add(self.product()?);
+            } else if self.accept(b"-") {
+                value = value.wrapping_sub(self.product()?);
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn product(&mut self) -> Option<i32> {
+        let mut value: i32 = self.unary()?;
+        loop {
+            if self.accept(b"*") {
+                value = value.wrapping_mul(self.unary()?);
+            } else if self.accept(b"/") {
+                value = value.checked_div(self.unary()?)?;
+            } else if self.accept(b"%") {
+                value = value.checked_rem(self.unary()?)?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Option<i32> {
+        if self.accept(b"-") {
+            return Some(self.unary()?.wrapping_neg());
+        }
+        if self.accept(b"+") {
+            return self.unary();
+        }
+        if self.accept(b"!") {
+            return Some((self.unary()? == 0) as i32);
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Option<i32> {
+        if self.accept(b"(") {
+            let value: i32 = self.comparison()?;
+            return if self.accept(b")") { Some(value) } else { None };
+        }
+        self.skip_spaces();
+        self.accept(b"$");
+        let start: usize = self.position;
+        let c: u8 = *self.text.get(start)?;
+        if c.is_ascii_digit() && start > 0 && self.text[start - 1] == b'$' {
+            self.position += 1;
+            return Some(self.variables.number(&self.text[start..self.position]));
+        }
+        if c.is_ascii_digit() {
+            let mut value: i32 = 0;
+            while self.position < self.text.len() && self.text[self.position].is_ascii_digit() {
+                value = value.wrapping_mul(10).wrapping_add((self.text[self.position] - b'0') as i32);
+                self.position += 1;
+            }
+            return Some(value);
+        }
+        while self.position < self.text.len() && (self.text[self.position].is_ascii_alphanumeric() || self.text[self.position] == b'_') {
+            self.position += 1;
+        }
+        if self.position == start {
+            return None;
+        }
+        Some(self.variables.number(&self.text[start..self.position]))
+    }
+
+    fn accept(&mut self, symbol: &[u8]) -> bool {
+        self.skip_spaces();
+        if self.text[self.position..].starts_with(symbol) {
+            self.position += symbol.len();
+            return true;
+        }
+        false
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.position < self.text.len() && (self.text[self.position] == b' ' || self.text[self.position] == b'\t') {
+            self.position += 1;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,338 @@
+// Project Name:  MinmusOS
+// File Name:     builtins.rs
+// File Function: Shell command registry
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::config::{Timezone, CONFIG, SYSTEM_NAME, SYSTEM_VERSION};
+use crate::multitasking::task::TASK_MANAGER;
+use crate::shell::parser::MAX_ARGS;
+use crate::shell::script::{Flow, ARENA_SIZE};
+use crate::shell::shell::{Shell, SHELLS};
+use crate::shell::variables::split_assignment;
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_MAGENTA, COLOR_LIGHT_RED};
+use crate::timer::time::Time;
+use core::str;
+use lib::errno::{E2BIG, EISDIR, ENOENT};
+
+pub struct Builtin {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub description: &'static str,
+    pub min_args: usize,
+    pub max_args: usize,
+    pub job: bool,
+    pub run: fn(&[&[u8]]) -> i32,
+}
+
+pub static BUILTINS: &[Builtin] = &[
+    Builtin { name: "[", usage: "[ <expression> ]", description: "Evaluates a conditional expression", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::test::test },
+    Builtin { name: "bg", usage: "bg [%job]", description: "Resumes a stopped job in the background", min_args: 0, max_args: 1, job: false, run: crate::shell::jobs::bg },
+    Builtin { name: "break", usage: "break", description: "Exits the innermost loop", min_args: 0, max_args: 0, job: false, run: break_loop },
+    Builtin { name: "cal", usage: "cal", description: "Shows current month's calendar", min_args: 0, max_args: 0, job: false, run: cal },
+    Builtin { name: "cat", usage: "cat [filename...]", description: "Shows content of files or standard input", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::cat::cat },
+    Builtin { name: "chmod", usage: "chmod <mode> <filename...>", description: "Changes file permissions", min_args: 2, max_args: MAX_ARGS, job: false, run: crate::shell::chmod::chmod },
+    Builtin { name: "chown", usage: "chown <user> <filename...>", description: "Changes file owner", min_args: 2, max_args: MAX_ARGS, job: false, run: crate::shell::chmod::chown },
+    Builtin { name: "clear", usage: "clear", description: "Clears terminal screen", min_args: 0, max_args: 0, job: false, run: clear },
+    Builtin { name: "color", usage: "color", description: "Shows VGA text mode color", min_args: 0, max_args: 0, job: false, run: color },
+    Builtin { name: "config", usage: "config [key [value]]", description: "Shows or changes system configuration", min_args: 0, max_args: 2, job: false, run: crate::shell::config::config },
+    Builtin { name: "continue", usage: "continue", description: "Starts the next loop iteration", min_args: 0, max_args: 0, job: false, run: continue_loop },
+    Builtin { name: "cp", usage: "cp <source> <target>", description: "Copies a file", min_args: 2, max_args: 2, job: false, run: crate::shell::cp::cp },
+    Builtin { name: "date", usage: "date", description: "Shows current datetime", min_args: 0, max_args: 0, job: false, run: date },
+    Builtin { name: "echo", usage: "echo [text...]", description: "Outputs text", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::echo::echo },
+    Builtin { name: "edit", usage: "edit <filename>", description: "Edits a text file", min_args: 1, max_args: 1, job: false, run: crate::shell::edit::edit },
+    Builtin { name: "exit", usage: "exit", description: "Exits current session", min_args: 0, max_args: 0, job: false, run: shutdown },
+    Builtin { name: "export", usage: "export [name[=value]...]", description: "Marks variables for export", min_args: 0, max_args: MAX_ARGS, job: false, run: export },
+    Builtin { name: "false", usage: "false", description: "Returns an unsuccessful status", min_args: 0, max_args: MAX_ARGS, job: false, run: failure },
+    Builtin { name: "fg", usage: "fg [%job]", description: "Resumes a job in the foreground", min_args: 0, max_args: 1, job: false, run: crate::shell::jobs::fg },
+    Builtin { name: "grep", usage: "grep [-inv] <regex> [filename...]", description: "Searches lines matching a pattern", min_args: 1, max_args: MAX_ARGS, job: false, run: crate::shell::grep::grep },
+    Builtin { name: "head", usage: "head [-n N] [filename...]", description: "Shows first lines of files or input", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::head::head },
+    Builtin { name: "help", usage: "help [command]", description: "Shows available commands", min_args: 0, max_args: 1, job: false, run: help },
+    Builtin { name: "hexdump", usage: "hexdump [filename...]", description: "Shows files or input in hexadecimal", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::hexdump::hexdump },
+    Builtin { name: "hostname", usage: "hostname [name]", description: "Shows or sets hostname", min_args: 0, max_args: 1, job: false, run: crate::shell::config::hostname },
+    Builtin { name: "jobs", usage: "jobs", description: "Lists background and stopped jobs", min_args: 0, max_args: 0, job: false, run: crate::shell::jobs::jobs },
+    Builtin { name: "kill", usage: "kill [-SIGNAL] <pid>", description: "Sends a signal to a process", min_args: 1, max_args: 2, job: false, run: crate::shell::kill::kill },
+    Builtin { name: "less", usage: "less [filename...]", description: "Pages through files or standard input", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::more::more },
+    Builtin { name: "loadkeys", usage: "loadkeys [map]", description: "Sets keyboard layout", min_args: 0, max_args: 1, job: false, run: crate::shell::loadkeys::loadkeys },
+    Builtin { name: "ls", usage: "ls [-ahlrSt]", description: "Lists root directory entries", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::ls::ls },
+    Builtin { name: "more", usage: "more [filename...]", description: "Pages through files or standard input", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::more::more },
+    Builtin { name: "mv", usage: "mv <source> <target>", description: "Moves or renames a file", min_args: 2, max_args: 2, job: false, run: crate::shell::mv::mv },
+    Builtin { name: "nano", usage: "nano <filename>", description: "Edits a text file", min_args: 1, max_args: 1, job: false, run: crate::shell::edit::edit },
+    Builtin { name: "ps", usage: "ps", description: "Lists running tasks", min_args: 0, max_args: 0, job: false, run: ps },
+    Builtin { name: "pwd", usage: "pwd", description: "Shows current directory", min_args: 0, max_args: 0, job: false, run: pwd },
+    Builtin { name: "reboot", usage: "reboot", description: "Reboot system", min_args: 0, max_args: 0, job: false, run: reboot },
+    Builtin { name: "return", usage: "return [status]", description: "Returns from a function or script", min_args: 0, max_args: 1, job: false, run: return_function },
+    Builtin { name: "rm", usage: "rm <filename...>", description: "Removes files", min_args: 1, max_args: MAX_ARGS, job: false, run: crate::shell::rm::rm },
+    Builtin { name: "run", usage: "run <appname>", description: "Runs an application", min_args: 1, max_args: 1, job: true, run: crate::shell::run::run },
+    Builtin { name: "set", usage: "set", description: "Lists shell variables", min_args: 0, max_args: 0, job: false, run: set },
+    Builtin { name: "sh", usage: "sh <script> [args...]", description: "Runs a shell script", min_args: 1, max_args: MAX_ARGS, job: false, run: sh },
+    Builtin { name: "shutdown", usage: "shutdown", description: "Shutdowns system", min_args: 0, max_args: 0, job: false, run: shutdown },
+    Builtin { name: "sort", usage: "sort [-fnru] [filename...]", description: "Sorts lines of text", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::sort::sort },
+    Builtin { name: "stat", usage: "stat <filename...>", description: "Shows file status", min_args: 1, max_args: MAX_ARGS, job: false, run: crate::shell::stat::stat },
+    Builtin { name: "su", usage: "su [user]", description: "Switches to another user", min_args: 0, max_args: 1, job: false, run: crate::shell::users::su },
+    Builtin { name: "tail", usage: "tail [-n N] [filename...]", description: "Shows last lines of files or input", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::tail::tail },
+    Builtin { name: "test", usage: "test <expression>", description: "Evaluates a conditional expression", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::test::test },
+    Builtin { name: "ticks", usage: "ticks", description: "Shows current CPU ticks", min_args: 0, max_args: 0, job: false, run: ticks },
+    Builtin { name: "timestamp", usage: "timestamp", description: "Shows current timestamp", min_args: 0, max_args: 0, job: false, run: timestamp },
+    Builtin { name: "touch", usage: "touch <filename...>", description: "Creates files or updates modification time", min_args: 1, max_args: MAX_ARGS, job: false, run: crate::shell::touch::touch },
+    Builtin { name: "true", usage: "true", description: "Returns a successful status", min_args: 0, max_args: MAX_ARGS, job: false, run: success },
+    Builtin { name: "uname", usage: "uname", description: "Shows system information", min_args: 0, max_args: 0, job: false, run: uname },
+    Builtin { name: "uniq", usage: "uniq [-cdiu] [filename...]", description: "Filters adjacent repeated lines", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::uniq::uniq },
+    Builtin { name: "unset", usage: "unset <name...>", description: "Removes shell variables", min_args: 1, max_args: MAX_ARGS, job: false, run: unset },
+    Builtin { name: "useradd", usage: "useradd <user>", description: "Creates a user account", min_args: 1, max_args: 1, job: false, run: crate::shell::users::useradd },
+    Builtin { name: "wc", usage: "wc [-lwc] [filename...]", description: "Counts lines, words and bytes", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::wc::wc },
+    Builtin { name: "whoami", usage: "whoami", description: "Shows current user", min_args: 0, max_args: 0, job: false, run: crate::shell::users::whoami },
+    Builtin { name: "xxd", usage: "xxd [filename...]", description: "Shows files or input as a hex dump", min_args: 0, max_args: MAX_ARGS, job: false, run: crate::shell::hexdump::xxd },
+];
+
+pub fn find(name: &[u8]) -> Option<&'static Builtin> {
+    BUILTINS.iter().find(|builtin| builtin.name.as_bytes().eq_ignore_ascii_case(name))
+}
+
+pub fn execute(argv: &[&[u8]]) -> i32 {
+    let builtin: &Builtin = match find(argv[0]) {
+        Some(builtin) => builtin,
+        None => {
+            unsafe {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            }
+            lib::eprintln!("Command not found!");
+            unsafe {
+                CONSOLES.printer().reset_colors();
+            }
+            return 127;
+        }
+    };
+    let count: usize = argv.len() - 1;
+    if count < builtin.min_args || count > builtin.max_args {
+        return usage(builtin);
+    }
+    (builtin.run)(argv)
+}
+
+pub fn usage(builtin: &Builtin) -> i32 {
+    unsafe {
+        CONSOLES.printer().set_colors(COLOR_LIGHT_MAGENTA, COLOR_BLACK);
+    }
+    lib::eprintln!("Usage: {}", builtin.usage);
+    unsafe {
+        CONSOLES.printer().reset_colors();
+    }
+    2
+}
+
+fn help(args: &[&[u8]]) -> i32 {
+    if args.len() > 1 {
+        return match find(args[1]) {
+            Some(builtin) => {
+                lib::println!("{} - {}", builtin.usage, builtin.description);
+                0
+            }
+            None => {
+                unsafe {
+                    CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+                }
+                lib::eprintln!("No help for {}!", str::from_utf8(args[1]).unwrap_or("?"));
+                unsafe {
+                    CONSOLES.printer().reset_colors();
+                }
+                1
+            }
+        };
+    }
+    let width: usize = BUILTINS.iter().map(|builtin| builtin.usage.len()).max().unwrap_or(0);
+    lib::println!("Available commands:");
+    for builtin in BUILTINS.iter() {
+        lib::println!("{:<width$} - {}", builtin.usage, builtin.description, width = width);
+    }
+    0
+}
+
+fn cal(_args: &[&[u8]]) -> i32 {
+    unsafe {
+        crate::shell::cal::cal();
+    }
+    0
+}
+
+fn clear(_args: &[&[u8]]) -> i32 {
+    unsafe {
+        CONSOLES.printer().clear();
+    }
+    0
+}
+
+fn color(_args: &[&[u8]]) -> i32 {
+    crate::shell::color::color();
+    0
+}
+
+fn date(_args: &[&[u8]]) -> i32 {
+    let time: Time = Time::local();
+    let timezone: Timezone = Timezone(unsafe { CONFIG.get_timezone() });
+    lib::println!("{:04}-{:02}-{:02} {:02}:{:02}:{:02} {}", time.get_year(), time.get_month(), time.get_day(), time.get_hour(), time.get_minute(), time.get_second(), timezone);
+    0
+}
+
+fn ps(_args: &[&[u8]]) -> i32 {
+    unsafe {
+        TASK_MANAGER.list_tasks();
+    }
+    0
+}
+
+fn pwd(_args: &[&[u8]]) -> i32 {
+    lib::println!("/");
+    0
+}
+
+fn reboot(_args: &[&[u8]]) -> i32 {
+    unsafe {
+        core::arch::asm!("mov al, 0xFE", "out 0x64, al", options(nostack, nomem));
+    }
+    0
+}
+
+fn shutdown(_args: &[&[u8]]) -> i32 {
+    unsafe {
+        core::arch::asm!("mov dx, 0x604", "mov ax, 0x2000", "out dx, ax", options(nostack, nomem));
+    }
+    0
+}
+
+fn ticks(_args: &[&[u8]]) -> i32 {
+    lib::println!("{}", Time::init().get_ticks());
+    0
+}
+
+fn timestamp(_args: &[&[u8]]) -> i32 {
+    lib::println!("{}", Time::init().get_timestamp());
+    0
+}
+
+fn uname(_args: &[&[u8]]) -> i32 {
+    lib::println!("{} {} IA-32 x86", SYSTEM_NAME, SYSTEM_VERSION);
+    0
+}
+
+fn shell() -> &'static mut Shell {
+    unsafe { &mut SHELLS[CONSOLES.get_current_id()] }
+}
+
+fn failure(_args: &[&[u8]]) -> i32 {
+    1
+}
+
+fn success(_args: &[&[u8]]) -> i32 {
+    0
+}
+
+fn set(_args: &[&[u8]]) -> i32 {
+    shell().variables.list(false);
+    0
+}
+
+fn export(args: &[&[u8]]) -> i32 {
+    if args.len() == 1 {
+        shell().variables.list(true);
+        return 0;
+    }
+    let mut status: i32 = 0;
+    for &arg in args[1..].iter() {
+        let exported: bool = match split_assignment(arg) {
+            Some((name, value)) => shell().variables.set(name, value) && shell().variables.export(name),
+            None => shell().variables.export(arg),
+        };
+        if !exported {
+            unsafe {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            }
+            lib::eprintln!("Cannot export {}!", str::from_utf8(arg).unwrap_or("?"));
+            unsafe {
+                CONSOLES.printer().reset_colors();
+            }
+            status = 1;
+        }
+    }
+    status
+}
+
+fn unset(args: &[&[u8]]) -> i32 {
+    for &name in args[1..].iter() {
+        shell().variables.unset(name);
+    }
+    0
+}
+
+fn sh(args: &[&[u8]]) -> i32 {
+    match shell().source(&args[1..]) {
+        Ok(status) => status,
+        Err(error) => {
+            unsafe {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            }
+            match -error {
+                ENOENT => {
+                    lib::eprintln!("Script not found!");
+                }
+                EISDIR => {
+                    lib::eprintln!("Script is a directory!");
+                }
+                E2BIG => {
+                    lib::eprintln!("Script too large (max {} bytes)!", ARENA_SIZE);
+                }
+                _ => {
+                    lib::eprintln!("Cannot read script!");
+                }
+            }
+            unsafe {
+                CONSOLES.printer().reset_colors();
+            }
+            1
+        }
+    }
+}
+
+fn break_loop(_args: &[&[u8]]) -> i32 {
+    control(Flow::Break, "break")
+}
+
+fn continue_loop(_args: &[&[u8]]) -> i32 {
+    control(Flow::Continue, "continue")
+}
+
+fn return_function(args: &[&[u8]]) -> i32 {
+    let status: i32 = match args.get(1) {
+        Some(status) => match str::from_utf8(status).ok().and_then(|status| status.parse::<i32>().ok()) {
+            Some(status) => status,
+            None => return usage(find(b"return").unwrap()),
+        },
+        None => shell().variables.status,
+    };
+    if control(Flow::Return, "return") != 0 {
+        return 1;
+    }
+    status
+}
+
+fn control(flow: Flow, name: &str) -> i32 {
+    let shell: &mut Shell = shell();
+    let valid: bool = if flow == Flow::Return { shell.script.calls > 0 } else { shell.script.loops > 0 };
+    if !valid {
+        unsafe {
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+        }
+        if flow == Flow::Return {
+            lib::eprintln!("{}: only valid in a function or script!", name);
+        } else {
+            lib::eprintln!("{}: only valid in a loop!", name);
+        }
+        unsafe {
+            CONSOLES.printer().reset_colors();
+        }
+        return 1;
+    }
+    shell.script.flow = flow;
+    0
+}
\ No newline at end of file
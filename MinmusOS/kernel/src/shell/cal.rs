@@ -4,11 +4,12 @@
 // Author:        Jishen Lin
 // License:       MIT License
 
-use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_YELLOW, PRINTER};
+use crate::drivers::console::CONSOLES;
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_YELLOW};
 use crate::timer::time::Time;
 
 pub unsafe fn cal() {
-    let time: Time = Time::init();
+    let time: Time = Time::local();
     let year: u16 = time.get_year();
     let month: u8 = time.get_month();
     let day: u8 = time.get_day();
@@ -53,9 +54,9 @@ pub unsafe fn cal() {
 
     for date in 1..=days_in_month {
         if date == day {
-            PRINTER.set_colors(COLOR_LIGHT_YELLOW, COLOR_BLACK);
+            CONSOLES.printer().set_colors(COLOR_LIGHT_YELLOW, COLOR_BLACK);
             lib::print!("{:>3} ", date);
-            PRINTER.reset_colors();
+            CONSOLES.printer().reset_colors();
         } else {
             lib::print!("{:>3} ", date);
         }
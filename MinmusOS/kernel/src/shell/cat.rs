@@ -0,0 +1,57 @@
+// Project Name:  MinmusOS
+// File Name:     cat.rs
+// File Function: The implementation of the command cat
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::descriptor::{Descriptor, STDIN};
+use crate::filesystem::fat::{Entry, FatDriver, FAT};
+use crate::multitasking::task::TASK_MANAGER;
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+use core::str;
+
+pub fn cat(args: &[&[u8]]) -> i32 {
+    if args.len() == 1 {
+        return cat_stdin();
+    }
+    let mut status: i32 = 0;
+    for &name in args[1..].iter() {
+        unsafe {
+            let fat: &FatDriver = FAT.acquire();
+            let entry: &Entry = fat.search_file(name);
+            if entry.name[0] != 0 {
+                fat.read_file_to_buffer(entry);
+                for &c in fat.buffer.iter() {
+                    if c != 0 {
+                        lib::print!("{}", c as char);
+                    }
+                }
+                lib::println!();
+            } else {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+                lib::eprintln!("{}: File not found!", str::from_utf8(name).unwrap_or("?"));
+                CONSOLES.printer().reset_colors();
+                status = 1;
+            }
+            FAT.free();
+        }
+    }
+    status
+}
+
+fn cat_stdin() -> i32 {
+    if unsafe { TASK_MANAGER.get_file(STDIN) } == Descriptor::Console {
+        return crate::shell::builtins::usage(crate::shell::builtins::find(b"cat").unwrap());
+    }
+    let mut buffer: [u8; 128] = [0; 128];
+    let mut read: i32 = lib::io::read(lib::io::STDIN, &mut buffer);
+    if read < 0 {
+        return 1;
+    }
+    while read > 0 {
+        lib::io::write(lib::io::STDOUT, &buffer[..read as usize]);
+        read = lib::io::read(lib::io::STDIN, &mut buffer);
+    }
+    0
+}
\ No newline at end of file
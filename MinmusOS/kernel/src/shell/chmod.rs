@@ -0,0 +1,50 @@
+// Project Name:  MinmusOS
+// File Name:     chmod.rs
+// File Function: The implementation of the commands chmod and chown
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::file::FILES;
+use crate::shell::builtins::{find, usage};
+use crate::shell::files::error;
+use crate::shell::users::{self, User};
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+use core::str;
+
+pub fn chmod(args: &[&[u8]]) -> i32 {
+    let mode: u16 = match str::from_utf8(args[1]).ok().and_then(|mode| u16::from_str_radix(mode, 8).ok()) {
+        Some(mode) if mode <= 0o777 => mode,
+        _ => return usage(find(b"chmod").unwrap()),
+    };
+    apply(&args[2..], &mut |path: &[u8]| unsafe { FILES.chmod(path, mode) })
+}
+
+pub fn chown(args: &[&[u8]]) -> i32 {
+    let user: User = match users::by_name(args[1]) {
+        Some(user) => user,
+        None => {
+            unsafe {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            }
+            lib::eprintln!("chown: Unknown user!");
+            unsafe {
+                CONSOLES.printer().reset_colors();
+            }
+            return 1;
+        }
+    };
+    apply(&args[2..], &mut |path: &[u8]| unsafe { FILES.chown(path, user.uid, user.gid) })
+}
+
+fn apply(paths: &[&[u8]], change: &mut dyn FnMut(&[u8]) -> i32) -> i32 {
+    let mut status: i32 = 0;
+    for &path in paths.iter() {
+        let result: i32 = change(path);
+        if result < 0 {
+            error(path, -result);
+            status = 1;
+        }
+    }
+    status
+}
\ No newline at end of file
@@ -4,30 +4,30 @@
 // Author:        Jishen Lin
 // License:       MIT License
 
-use crate::syscalls::print::PRINTER;
+use crate::drivers::console::CONSOLES;
 
 pub fn color() {
     for fg in 0..16 {
         for bg in 0..16 {
             unsafe {
-                PRINTER.set_colors(fg, bg);
+                CONSOLES.printer().set_colors(fg, bg);
             }
             lib::print!(" {:X}{:X} ", fg, bg);
         }
         unsafe {
-            PRINTER.reset_colors();
+            CONSOLES.printer().reset_colors();
         }
         lib::print!("    0x0{:X}", fg);
         unsafe {
-            PRINTER.reset_colors();
+            CONSOLES.printer().reset_colors();
         }
         lib::print!("  ");
         unsafe {
-            PRINTER.set_colors(fg, fg);
+            CONSOLES.printer().set_colors(fg, fg);
         }
         lib::println!("    ");
     }
     unsafe {
-        PRINTER.reset_colors();
+        CONSOLES.printer().reset_colors();
     }
 }
\ No newline at end of file
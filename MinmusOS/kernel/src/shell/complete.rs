@@ -0,0 +1,104 @@
+// Project Name:  MinmusOS
+// File Name:     complete.rs
+// File Function: Tab completion
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::filesystem::fat::{display_name, FatDriver, ATTRIBUTE_DIRECTORY, ENTRY_COUNT, FAT};
+use crate::shell::builtins::BUILTINS;
+use crate::shell::run::APP_SIGNATURE;
+
+const MAX_CANDIDATES: usize = 64;
+pub const NAME_SIZE: usize = 12;
+
+#[derive(Copy, Clone)]
+pub struct Candidates {
+    names: [[u8; NAME_SIZE]; MAX_CANDIDATES],
+    lengths: [usize; MAX_CANDIDATES],
+    count: usize,
+}
+
+impl Candidates {
+    pub const fn new() -> Self {
+        Candidates {
+            names: [[0; NAME_SIZE]; MAX_CANDIDATES],
+            lengths: [0; MAX_CANDIDATES],
+            count: 0,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn get(&self, index: usize) -> &[u8] {
+        &self.names[index][..self.lengths[index]]
+    }
+
+    pub fn common_prefix(&self) -> usize {
+        if self.count == 0 {
+            return 0;
+        }
+        let first: &[u8] = self.get(0);
+        let mut length: usize = first.len();
+        for i in 1..self.count {
+            let name: &[u8] = self.get(i);
+            length = length.min(name.iter().zip(first.iter()).take_while(|(a, b)| a == b).count());
+        }
+        length
+    }
+
+    fn add(&mut self, name: &[u8]) {
+        if self.count == MAX_CANDIDATES || name.len() > NAME_SIZE || (0..self.count).any(|i| self.get(i) == name) {
+            return;
+        }
+        for (i, &c) in name.iter().enumerate() {
+            self.names[self.count][i] = c.to_ascii_lowercase();
+        }
+        self.lengths[self.count] = name.len();
+        self.count += 1;
+    }
+}
+
+pub fn complete(line: &[u8], start: usize, candidates: &mut Candidates) {
+    candidates.count = 0;
+    let word: &[u8] = &line[start..];
+    if line[..start].iter().all(|&c| c == b' ') {
+        for builtin in BUILTINS.iter() {
+            if has_prefix(builtin.name.as_bytes(), word) {
+                candidates.add(builtin.name.as_bytes());
+            }
+        }
+        return;
+    }
+    let command: &[u8] = line.split(|&c| c == b' ').find(|w| !w.is_empty()).unwrap_or(&[]);
+    let apps: bool = command.eq_ignore_ascii_case(b"run");
+    unsafe {
+        let fat: &mut FatDriver = FAT.acquire_mut();
+        for i in 0..ENTRY_COUNT {
+            if fat.entries[i].name[0] == 0 {
+                break;
+            }
+            if !fat.entries[i].is_visible() || fat.entries[i].attributes & ATTRIBUTE_DIRECTORY != 0 {
+                continue;
+            }
+            let mut name: [u8; NAME_SIZE] = [0; NAME_SIZE];
+            let length: usize = display_name(&fat.entries[i].name, &mut name);
+            if !has_prefix(&name[..length], word) {
+                continue;
+            }
+            if apps {
+                let mut signature: [u8; 4] = [0; 4];
+                if fat.read_at(i, 0, &mut signature) != 4 || u32::from_le_bytes(signature) != APP_SIGNATURE {
+                    continue;
+                }
+            }
+            candidates.add(&name[..length]);
+        }
+        FAT.free();
+    }
+}
+
+fn has_prefix(name: &[u8], prefix: &[u8]) -> bool {
+    name.len() >= prefix.len() && name[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
\ No newline at end of file
@@ -0,0 +1,63 @@
+// Project Name:  MinmusOS
+// File Name:     config.rs
+// File Function: The implementation of the commands config and hostname
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::config::{Config, CONFIG, KEYS};
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+use core::fmt;
+use core::str;
+
+pub fn config(args: &[&[u8]]) -> i32 {
+    let config: &mut Config = unsafe { &mut CONFIG };
+    match args.len() {
+        1 => {
+            for key in KEYS.iter() {
+                lib::print!("{}=", key);
+                let _ = config.write_value(key.as_bytes(), &mut lib::print::Printer {});
+                lib::println!();
+            }
+            0
+        }
+        2 => {
+            if config.write_value(args[1], &mut lib::print::Printer {}).is_err() {
+                return failure(format_args!("Unknown key: {}!", str::from_utf8(args[1]).unwrap_or("?")));
+            }
+            lib::println!();
+            0
+        }
+        _ => change(args[1], args[2]),
+    }
+}
+
+pub fn hostname(args: &[&[u8]]) -> i32 {
+    if args.len() == 1 {
+        lib::println!("{}", unsafe { CONFIG.get_hostname() });
+        return 0;
+    }
+    change(b"hostname", args[1])
+}
+
+pub fn change(key: &[u8], value: &[u8]) -> i32 {
+    if !KEYS.iter().any(|name| name.as_bytes() == key) {
+        return failure(format_args!("Cannot set {}: Unknown key!", str::from_utf8(key).unwrap_or("?")));
+    }
+    let result: i32 = unsafe { CONFIG.update(key, value) };
+    if result < 0 {
+        return failure(format_args!("Cannot set {}: {}!", str::from_utf8(key).unwrap_or("?"), lib::errno::description(result)));
+    }
+    0
+}
+
+fn failure(message: fmt::Arguments) -> i32 {
+    unsafe {
+        CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+    }
+    lib::eprintln!("{}", message);
+    unsafe {
+        CONSOLES.printer().reset_colors();
+    }
+    1
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+// Project Name:  MinmusOS
+// File Name:     cp.rs
+// File Function: The implementation of the command cp
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::fat::short_name;
+use crate::filesystem::file::{FILES, O_CREAT, O_TRUNC, O_WRONLY};
+use crate::shell::files::{close, error, open, read};
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+use core::str;
+
+pub fn cp(args: &[&[u8]]) -> i32 {
+    let (source, target): (&[u8], &[u8]) = (args[1], args[2]);
+    if let (Ok(from), Ok(to)) = (short_name(source), short_name(target)) {
+        if from == to {
+            unsafe {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            }
+            lib::eprintln!("{}: Source and target are the same file!", str::from_utf8(target).unwrap_or("?"));
+            unsafe {
+                CONSOLES.printer().reset_colors();
+            }
+            return 1;
+        }
+    }
+    let input: i32 = open(source);
+    if input < 0 {
+        return 1;
+    }
+    let output: i32 = unsafe { FILES.open(target, O_WRONLY | O_CREAT | O_TRUNC) };
+    if output < 0 {
+        error(target, -output);
+        close(input);
+        return 1;
+    }
+    let mut buffer: [u8; 128] = [0; 128];
+    let mut status: i32 = 0;
+    loop {
+        let count: i32 = read(input, &mut buffer);
+        if count <= 0 {
+            if count < 0 {
+                status = 1;
+            }
+            break;
+        }
+        let written: i32 = unsafe { FILES.write(output as usize, &buffer[..count as usize]) };
+        if written < 0 {
+            error(target, -written);
+            status = 1;
+            break;
+        }
+    }
+    close(input);
+    close(output);
+    status
+}
\ No newline at end of file
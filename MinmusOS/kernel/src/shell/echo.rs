@@ -1,24 +1,16 @@
 // Project Name:  MinmusOS
-// File Name:     color.rs
+// File Name:     echo.rs
 // File Function: The implementation of the command echo
 // Author:        Jishen Lin
 // License:       MIT License
 
-pub fn echo(b: &[char]) {
-    let mut last_was_space = true;
-    for &ch in b.iter().skip(5) {
-        if ch == '\0' {
-            break;
-        }
-        if ch == ' ' {
-            if !last_was_space {
-                lib::print!(" ");
-                last_was_space = true;
-            }
-        } else {
-            lib::print!("{}", ch);
-            last_was_space = false;
+pub fn echo(args: &[&[u8]]) -> i32 {
+    for (i, &arg) in args[1..].iter().enumerate() {
+        if i > 0 {
+            lib::io::write(lib::io::STDOUT, b" ");
         }
+        lib::io::write(lib::io::STDOUT, arg);
     }
-    lib::println!();
+    lib::io::write(lib::io::STDOUT, b"\n");
+    0
 }
\ No newline at end of file
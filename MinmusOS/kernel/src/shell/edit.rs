@@ -0,0 +1,442 @@
+// Project Name:  MinmusOS
+// File Name:     edit.rs
+// File Function: Full-screen text editor
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::drivers::input::MODE_RAW;
+use crate::filesystem::descriptor::{Descriptor, STDIN, STDOUT};
+use crate::filesystem::fat::{display_name, short_name};
+use crate::filesystem::file::{Stat, FILES, O_CREAT, O_RDONLY, O_TRUNC, O_WRONLY};
+use crate::multitasking::task::TASK_MANAGER;
+use crate::shell::editor::{read_sequence, Key};
+use crate::shell::files::error;
+use crate::syscalls::print::{Printer, COLOR_BLACK, COLOR_LIGHT_RED, COLOR_WHITE, VGA_HEIGHT, VGA_WIDTH};
+use core::fmt::{self, Write};
+use core::str;
+use lib::errno::{description, ENOENT};
+
+const TEXT_SIZE: usize = 8192;
+const TEXT_ROWS: usize = VGA_HEIGHT as usize - 2;
+const COLUMNS: usize = VGA_WIDTH as usize;
+const QUERY_SIZE: usize = 32;
+const TAB_WIDTH: usize = 4;
+const KEY_CTRL_F: u8 = 0x06;
+const KEY_BACKSPACE: u8 = 0x08;
+const KEY_TAB: u8 = 0x09;
+const KEY_CTRL_K: u8 = 0x0B;
+const KEY_CTRL_S: u8 = 0x13;
+const KEY_CTRL_W: u8 = 0x17;
+const KEY_CTRL_X: u8 = 0x18;
+const KEY_ESCAPE: u8 = 0x1B;
+const KEY_DELETE: u8 = 0x7F;
+
+pub static mut EDITOR: Editor = Editor {
+    text: [0; TEXT_SIZE],
+    length: 0,
+    cursor: 0,
+    top: 0,
+    left: 0,
+    goal: 0,
+    modified: false,
+    busy: false,
+    path: [0; 12],
+    path_length: 0,
+    query: [0; QUERY_SIZE],
+    query_length: 0,
+    message: "",
+    value: None,
+};
+
+pub struct Editor {
+    text: [u8; TEXT_SIZE],
+    length: usize,
+    cursor: usize,
+    top: usize,
+    left: usize,
+    goal: usize,
+    modified: bool,
+    busy: bool,
+    path: [u8; 12],
+    path_length: usize,
+    query: [u8; QUERY_SIZE],
+    query_length: usize,
+    message: &'static str,
+    value: Option<usize>,
+}
+
+struct Row<'a> {
+    printer: &'a mut Printer,
+    x: u16,
+    y: u16,
+}
+
+impl Write for Row<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for &c in s.as_bytes() {
+            if self.x < VGA_WIDTH {
+                self.printer.draw(self.x, self.y, c);
+                self.x += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Editor {
+    fn run(&mut self, path: &[u8]) -> i32 {
+        let name: [u8; 11] = match short_name(path) {
+            Ok(name) => name,
+            Err(code) => {
+                error(path, -code);
+                return 1;
+            }
+        };
+        self.path_length = display_name(&name, &mut self.path);
+        if !self.load() {
+            return 1;
+        }
+        let mode: i32 = unsafe { CONSOLES.current().set_mode(MODE_RAW) };
+        loop {
+            self.scroll();
+            self.render();
+            let c: u8 = match lib::io::getchar() {
+                Some(c) => c,
+                None => continue,
+            };
+            self.message = "";
+            self.value = None;
+            let key: Key = match c {
+                KEY_CTRL_X => {
+                    if self.quit() {
+                        break;
+                    }
+                    continue;
+                }
+                KEY_CTRL_S => {
+                    self.save();
+                    continue;
+                }
+                KEY_CTRL_W | KEY_CTRL_F => {
+                    self.search();
+                    continue;
+                }
+                KEY_TAB => {
+                    for _ in 0..TAB_WIDTH {
+                        self.handle(Key::Character(b' '));
+                    }
+                    continue;
+                }
+                KEY_CTRL_K => Key::KillLine,
+                KEY_ESCAPE => match read_sequence() {
+                    Some(key) => key,
+                    None => continue,
+                },
+                KEY_BACKSPACE | KEY_DELETE => Key::Backspace,
+                b'\n' | b'\r' => Key::Enter,
+                c if c >= b' ' => Key::Character(c),
+                _ => Key::Ignored,
+            };
+            self.handle(key);
+        }
+        unsafe {
+            CONSOLES.current().set_mode(mode as u32);
+            CONSOLES.printer().reset_colors();
+            CONSOLES.printer().clear();
+        }
+        0
+    }
+
+    fn load(&mut self) -> bool {
+        self.length = 0;
+        self.cursor = 0;
+        self.top = 0;
+        self.left = 0;
+        self.goal = 0;
+        self.modified = false;
+        self.message = "";
+        self.value = None;
+        let path: &[u8] = &self.path[..self.path_length];
+        let mut stat: Stat = Stat::new();
+        let result: i32 = unsafe { FILES.stat(path, &mut stat) };
+        if result == -ENOENT {
+            self.message = "New file";
+            return true;
+        }
+        if result == 0 && stat.size as usize > TEXT_SIZE {
+            unsafe {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            }
+            lib::eprintln!("{}: File too large (max {} bytes)!", str::from_utf8(path).unwrap_or("?"), TEXT_SIZE);
+            unsafe {
+                CONSOLES.printer().reset_colors();
+            }
+            return false;
+        }
+        let id: i32 = if result < 0 { result } else { unsafe { FILES.open(path, O_RDONLY) } };
+        if id < 0 {
+            error(path, -id);
+            return false;
+        }
+        loop {
+            let count: i32 = unsafe { FILES.read(id as usize, &mut self.text[self.length..]) };
+            if count <= 0 {
+                break;
+            }
+            self.length += count as usize;
+        }
+        unsafe {
+            FILES.release(id as usize);
+        }
+        self.message = "Read";
+        self.value = Some(self.length);
+        true
+    }
+
+    fn save(&mut self) -> bool {
+        let path: &[u8] = &self.path[..self.path_length];
+        let id: i32 = unsafe { FILES.open(path, O_WRONLY | O_CREAT | O_TRUNC) };
+        if id < 0 {
+            self.message = description(id);
+            return false;
+        }
+        let written: i32 = if self.length > 0 { unsafe { FILES.write(id as usize, &self.text[..self.length]) } } else { 0 };
+        unsafe {
+            FILES.release(id as usize);
+        }
+        if written < 0 || written as usize != self.length {
+            self.message = "Cannot write file";
+            return false;
+        }
+        self.modified = false;
+        self.message = "Wrote";
+        self.value = Some(self.length);
+        true
+    }
+
+    fn quit(&mut self) -> bool {
+        if !self.modified {
+            return true;
+        }
+        self.render();
+        status(format_args!("Save modified buffer? (y)es, (n)o, (c)ancel"));
+        match lib::io::getchar() {
+            Some(b'y') | Some(b'Y') => self.save(),
+            Some(b'n') | Some(b'N') => true,
+            _ => false,
+        }
+    }
+
+    fn search(&mut self) {
+        let mut query: [u8; QUERY_SIZE] = [0; QUERY_SIZE];
+        let mut length: usize = 0;
+        loop {
+            status(format_args!("Search: {}", str::from_utf8(&query[..length]).unwrap_or("")));
+            unsafe {
+                CONSOLES.printer().move_to(8 + length as i32, VGA_HEIGHT as i32 - 1);
+            }
+            match lib::io::getchar() {
+                Some(b'\n') | Some(b'\r') => break,
+                Some(KEY_BACKSPACE) | Some(KEY_DELETE) => length = length.saturating_sub(1),
+                Some(c) if c >= b' ' && length < QUERY_SIZE => {
+                    query[length] = c;
+                    length += 1;
+                }
+                Some(KEY_ESCAPE) | None => {
+                    self.message = "Cancelled";
+                    return;
+                }
+                Some(_) => {}
+            }
+        }
+        if length > 0 {
+            self.query = query;
+            self.query_length = length;
+        }
+        if self.query_length == 0 {
+            self.message = "Cancelled";
+            return;
+        }
+        let query: &[u8] = &self.query[..self.query_length];
+        let start: usize = (self.cursor + 1).min(self.length);
+        let found: Option<usize> = find(&self.text[start..self.length], query).map(|i| start + i).or_else(|| find(&self.text[..self.length], query));
+        match found {
+            Some(index) => {
+                self.cursor = index;
+                self.goal = self.cursor - self.line_start(self.cursor);
+            }
+            None => self.message = "Not found",
+        }
+    }
+
+    fn handle(&mut self, key: Key) {
+        let start: usize = self.line_start(self.cursor);
+        match key {
+            Key::Up => {
+                if start > 0 {
+                    self.cursor = self.at_goal(self.line_start(start - 1));
+                }
+                return;
+            }
+            Key::Down => {
+                let end: usize = self.line_end(self.cursor);
+                if end < self.length {
+                    self.cursor = self.at_goal(end + 1);
+                }
+                return;
+            }
+            Key::PageUp | Key::PageDown => {
+                for _ in 0..TEXT_ROWS {
+                    self.handle(if key == Key::PageUp { Key::Up } else { Key::Down });
+                }
+                return;
+            }
+            Key::Left => self.cursor = self.cursor.saturating_sub(1),
+            Key::Right if self.cursor < self.length => self.cursor += 1,
+            Key::Home => self.cursor = start,
+            Key::End => self.cursor = self.line_end(self.cursor),
+            Key::Character(c) => self.insert(c),
+            Key::Enter => self.insert(b'\n'),
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.remove(self.cursor, 1);
+            }
+            Key::Delete if self.cursor < self.length => self.remove(self.cursor, 1),
+            Key::KillLine => {
+                let end: usize = (self.line_end(start) + 1).min(self.length);
+                self.remove(start, end - start);
+                self.cursor = start;
+            }
+            _ => {}
+        }
+        self.goal = self.cursor - self.line_start(self.cursor);
+    }
+
+    fn insert(&mut self, c: u8) {
+        if self.length == TEXT_SIZE {
+            self.message = "Buffer full";
+            return;
+        }
+        self.text.copy_within(self.cursor..self.length, self.cursor + 1);
+        self.text[self.cursor] = c;
+        self.length += 1;
+        self.cursor += 1;
+        self.modified = true;
+    }
+
+    fn remove(&mut self, at: usize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.text.copy_within(at + count..self.length, at);
+        self.length -= count;
+        self.modified = true;
+    }
+
+    fn line_start(&self, position: usize) -> usize {
+        self.text[..position].iter().rposition(|&c| c == b'\n').map_or(0, |i| i + 1)
+    }
+
+    fn line_end(&self, position: usize) -> usize {
+        self.text[position..self.length].iter().position(|&c| c == b'\n').map_or(self.length, |i| position + i)
+    }
+
+    fn at_goal(&self, start: usize) -> usize {
+        start + self.goal.min(self.line_end(start) - start)
+    }
+
+    fn lines(&self, from: usize, to: usize) -> usize {
+        self.text[from..to].iter().filter(|&&c| c == b'\n').count()
+    }
+
+    fn scroll(&mut self) {
+        let column: usize = self.cursor - self.line_start(self.cursor);
+        if column < self.left {
+            self.left = column;
+        } else if column >= self.left + COLUMNS {
+            self.left = column + 1 - COLUMNS;
+        }
+        if self.cursor < self.top {
+            self.top = self.line_start(self.cursor);
+        }
+        while self.lines(self.top, self.cursor) >= TEXT_ROWS {
+            self.top = self.line_end(self.top) + 1;
+        }
+    }
+
+    fn render(&self) {
+        let path: &str = str::from_utf8(&self.path[..self.path_length]).unwrap_or("?");
+        bar(0, format_args!("  edit    {}{}", path, if self.modified { " (modified)" } else { "" }));
+        let printer: &mut Printer = unsafe { CONSOLES.printer() };
+        printer.reset_colors();
+        let mut line: Option<usize> = Some(self.top);
+        for y in 1..=TEXT_ROWS {
+            let (start, end): (usize, usize) = match line {
+                Some(start) => (start, self.line_end(start)),
+                None => (0, 0),
+            };
+            for x in 0..COLUMNS {
+                let index: usize = start + self.left + x;
+                let c: u8 = if index < end && self.text[index] >= b' ' { self.text[index] } else { b' ' };
+                printer.draw(x as u16, y as u16, c);
+            }
+            line = match line {
+                Some(_) if end < self.length => Some(end + 1),
+                _ => None,
+            };
+        }
+        let row: usize = self.lines(0, self.cursor) + 1;
+        let column: usize = self.cursor - self.line_start(self.cursor);
+        match self.value {
+            Some(value) => status(format_args!("[ {} {} bytes ]", self.message, value)),
+            None if !self.message.is_empty() => status(format_args!("[ {} ]", self.message)),
+            None => status(format_args!("^S Save  ^X Exit  ^W Search  ^K Cut line    Line {}, Col {}", row, column + 1)),
+        }
+        unsafe {
+            CONSOLES.printer().move_to((column - self.left) as i32, (1 + self.lines(self.top, self.cursor)) as i32);
+        }
+    }
+}
+
+pub fn edit(args: &[&[u8]]) -> i32 {
+    unsafe {
+        let terminal: bool = TASK_MANAGER.get_file(STDIN) == Descriptor::Console && TASK_MANAGER.get_file(STDOUT) == Descriptor::Console;
+        if !terminal || TASK_MANAGER.tasks[TASK_MANAGER.get_current_slot() as usize].group >= 0 || EDITOR.busy {
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            if EDITOR.busy {
+                lib::eprintln!("The editor is already open on another console!");
+            } else {
+                lib::eprintln!("The editor must run in the foreground on a console!");
+            }
+            CONSOLES.printer().reset_colors();
+            return 1;
+        }
+        EDITOR.busy = true;
+        let status: i32 = EDITOR.run(args[1]);
+        EDITOR.busy = false;
+        status
+    }
+}
+
+fn status(args: fmt::Arguments) {
+    bar(VGA_HEIGHT - 1, args);
+}
+
+fn bar(y: u16, args: fmt::Arguments) {
+    let printer: &mut Printer = unsafe { CONSOLES.printer() };
+    printer.set_colors(COLOR_BLACK, COLOR_WHITE);
+    let mut row: Row = Row { printer, x: 0, y };
+    let _ = row.write_fmt(args);
+    while row.x < VGA_WIDTH {
+        row.printer.draw(row.x, y, b' ');
+        row.x += 1;
+    }
+    unsafe {
+        CONSOLES.printer().reset_colors();
+    }
+}
+
+fn find(text: &[u8], query: &[u8]) -> Option<usize> {
+    text.windows(query.len()).position(|window| window == query)
+}
\ No newline at end of file
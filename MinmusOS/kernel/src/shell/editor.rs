@@ -0,0 +1,412 @@
+// Project Name:  MinmusOS
+// File Name:     editor.rs
+// File Function: Line editor with history
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::drivers::input::{MODE_COOKED, MODE_RAW};
+use crate::shell::complete::{complete, Candidates, NAME_SIZE};
+use crate::syscalls::print::VGA_WIDTH;
+
+pub const LINE_SIZE: usize = 256;
+const HISTORY_SIZE: usize = 32;
+const KEY_CTRL_D: u8 = 0x04;
+const KEY_BACKSPACE: u8 = 0x08;
+const KEY_TAB: u8 = 0x09;
+const KEY_CTRL_R: u8 = 0x12;
+const KEY_CTRL_U: u8 = 0x15;
+const KEY_CTRL_W: u8 = 0x17;
+const KEY_ESCAPE: u8 = 0x1B;
+const KEY_DELETE: u8 = 0x7F;
+const SEARCH_PROMPT: &[u8] = b"(reverse-i-search)`";
+const SEARCH_SEPARATOR: &[u8] = b"': ";
+const BACKSPACES: [u8; 64] = [KEY_BACKSPACE; 64];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Key {
+    Character(u8),
+    Enter,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    KillWord,
+    KillLine,
+    Search,
+    Complete,
+    EndOfFile,
+    Ignored,
+}
+
+#[derive(Copy, Clone)]
+struct History {
+    entries: [[u8; LINE_SIZE]; HISTORY_SIZE],
+    lengths: [usize; HISTORY_SIZE],
+    head: usize,
+    count: usize,
+}
+
+impl History {
+    const fn new() -> Self {
+        History {
+            entries: [[0; LINE_SIZE]; HISTORY_SIZE],
+            lengths: [0; HISTORY_SIZE],
+            head: 0,
+            count: 0,
+        }
+    }
+
+    fn get(&self, index: usize) -> &[u8] {
+        let slot: usize = (self.head + index) % HISTORY_SIZE;
+        &self.entries[slot][..self.lengths[slot]]
+    }
+
+    fn push(&mut self, line: &[u8]) {
+        if line.iter().all(|&c| c == b' ') || (self.count > 0 && self.get(self.count - 1) == line) {
+            return;
+        }
+        let slot: usize = (self.head + self.count) % HISTORY_SIZE;
+        self.entries[slot][..line.len()].copy_from_slice(line);
+        self.lengths[slot] = line.len();
+        if self.count < HISTORY_SIZE {
+            self.count += 1;
+        } else {
+            self.head = (self.head + 1) % HISTORY_SIZE;
+        }
+    }
+
+    fn search(&self, query: &[u8], before: usize) -> Option<usize> {
+        if query.is_empty() {
+            return None;
+        }
+        (0..before).rev().find(|&i| self.get(i).windows(query.len()).any(|window| window == query))
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct LineEditor {
+    line: [u8; LINE_SIZE],
+    length: usize,
+    cursor: usize,
+    shown: usize,
+    history: History,
+    browse: usize,
+    saved: [u8; LINE_SIZE],
+    saved_length: usize,
+    searching: bool,
+    query: [u8; LINE_SIZE],
+    query_length: usize,
+    found: usize,
+    candidates: Candidates,
+    tabbed: bool,
+}
+
+impl LineEditor {
+    pub const fn new() -> Self {
+        LineEditor {
+            line: [0; LINE_SIZE],
+            length: 0,
+            cursor: 0,
+            shown: 0,
+            history: History::new(),
+            browse: 0,
+            saved: [0; LINE_SIZE],
+            saved_length: 0,
+            searching: false,
+            query: [0; LINE_SIZE],
+            query_length: 0,
+            found: 0,
+            candidates: Candidates::new(),
+            tabbed: false,
+        }
+    }
+
+    pub fn read_line(&mut self, prompt: fn()) -> Option<&[u8]> {
+        prompt();
+        self.length = 0;
+        self.cursor = 0;
+        self.shown = 0;
+        self.searching = false;
+        self.browse = self.history.count;
+        unsafe {
+            CONSOLES.current().set_mode(MODE_RAW);
+        }
+        let accepted: bool = loop {
+            match read_key() {
+                Some(Key::Enter) => {
+                    self.searching = false;
+                    self.cursor = self.length;
+                    self.refresh();
+                    lib::io::write(lib::io::STDOUT, b"\n");
+                    break true;
+                }
+                Some(Key::EndOfFile) if self.length == 0 && !self.searching => {
+                    lib::io::write(lib::io::STDOUT, b"\n");
+                    break false;
+                }
+                Some(Key::Complete) if !self.searching => {
+                    self.complete(prompt);
+                    self.tabbed = true;
+                    self.refresh();
+                }
+                Some(key) => {
+                    self.handle(key);
+                    self.tabbed = false;
+                    self.refresh();
+                }
+                None => {
+                    lib::io::write(lib::io::STDOUT, b"^C\n");
+                    break false;
+                }
+            }
+        };
+        unsafe {
+            CONSOLES.current().set_mode(MODE_COOKED);
+        }
+        if !accepted {
+            return None;
+        }
+        self.history.push(&self.line[..self.length]);
+        Some(&self.line[..self.length])
+    }
+
+    fn handle(&mut self, key: Key) {
+        if self.searching {
+            match key {
+                Key::Character(c) if self.query_length < LINE_SIZE - 1 => {
+                    self.query[self.query_length] = c;
+                    self.query_length += 1;
+                    self.search(self.history.count);
+                }
+                Key::Backspace if self.query_length > 0 => {
+                    self.query_length -= 1;
+                    self.search(self.history.count);
+                }
+                Key::Search => self.search(self.found),
+                Key::Character(_) | Key::Backspace => {}
+                _ => {
+                    self.searching = false;
+                    self.handle(key);
+                }
+            }
+            return;
+        }
+        match key {
+            Key::Character(c) => {
+                self.insert(c);
+            }
+            Key::Backspace if self.cursor > 0 => {
+                self.remove(self.cursor - 1, self.cursor);
+            }
+            Key::Delete | Key::EndOfFile if self.cursor < self.length => {
+                self.remove(self.cursor, self.cursor + 1);
+            }
+            Key::Left if self.cursor > 0 => self.cursor -= 1,
+            Key::Right if self.cursor < self.length => self.cursor += 1,
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = self.length,
+            Key::Up if self.browse > 0 => {
+                if self.browse == self.history.count {
+                    self.saved[..self.length].copy_from_slice(&self.line[..self.length]);
+                    self.saved_length = self.length;
+                }
+                self.browse -= 1;
+                self.load_history(self.browse);
+            }
+            Key::Down if self.browse < self.history.count => {
+                self.browse += 1;
+                if self.browse == self.history.count {
+                    self.line[..self.saved_length].copy_from_slice(&self.saved[..self.saved_length]);
+                    self.length = self.saved_length;
+                    self.cursor = self.length;
+                } else {
+                    self.load_history(self.browse);
+                }
+            }
+            Key::KillWord => {
+                let mut start: usize = self.cursor;
+                while start > 0 && self.line[start - 1] == b' ' {
+                    start -= 1;
+                }
+                while start > 0 && self.line[start - 1] != b' ' {
+                    start -= 1;
+                }
+                self.remove(start, self.cursor);
+            }
+            Key::KillLine => self.remove(0, self.cursor),
+            Key::Search => {
+                self.searching = true;
+                self.query_length = 0;
+                self.found = self.history.count;
+            }
+            _ => {}
+        }
+    }
+
+    fn insert(&mut self, c: u8) -> bool {
+        if self.length >= LINE_SIZE - 1 {
+            return false;
+        }
+        self.line.copy_within(self.cursor..self.length, self.cursor + 1);
+        self.line[self.cursor] = c;
+        self.length += 1;
+        self.cursor += 1;
+        true
+    }
+
+    fn complete(&mut self, prompt: fn()) {
+        let start: usize = self.line[..self.cursor].iter().rposition(|&c| c == b' ').map_or(0, |i| i + 1);
+        complete(&self.line[..self.cursor], start, &mut self.candidates);
+        let count: usize = self.candidates.count();
+        if count == 0 {
+            return;
+        }
+        let typed: usize = self.cursor - start;
+        let common: usize = self.candidates.common_prefix();
+        let mut name: [u8; NAME_SIZE] = [0; NAME_SIZE];
+        name[..common].copy_from_slice(&self.candidates.get(0)[..common]);
+        if common > typed {
+            self.remove(start, self.cursor);
+            for &c in name[..common].iter() {
+                self.insert(c);
+            }
+        }
+        if count == 1 {
+            if self.cursor == self.length || self.line[self.cursor] != b' ' {
+                self.insert(b' ');
+            }
+        } else if common <= typed && self.tabbed {
+            self.list(prompt);
+        }
+    }
+
+    fn list(&mut self, prompt: fn()) {
+        let count: usize = self.candidates.count();
+        let width: usize = (0..count).map(|i| self.candidates.get(i).len()).max().unwrap_or(0) + 2;
+        let columns: usize = (VGA_WIDTH as usize / width).max(1);
+        let rows: usize = count.div_ceil(columns);
+        back(self.shown);
+        lib::io::write(lib::io::STDOUT, &self.line[..self.length]);
+        lib::io::write(lib::io::STDOUT, b"\n");
+        for row in 0..rows {
+            for column in 0..columns {
+                let index: usize = column * rows + row;
+                if index >= count {
+                    break;
+                }
+                let name: &[u8] = self.candidates.get(index);
+                lib::io::write(lib::io::STDOUT, name);
+                if column < columns - 1 && index + rows < count {
+                    for _ in name.len()..width {
+                        lib::io::write(lib::io::STDOUT, b" ");
+                    }
+                }
+            }
+            lib::io::write(lib::io::STDOUT, b"\n");
+        }
+        prompt();
+        self.shown = 0;
+    }
+
+    fn search(&mut self, before: usize) {
+        if let Some(index) = self.history.search(&self.query[..self.query_length], before) {
+            self.found = index;
+            self.load_history(index);
+        }
+    }
+
+    fn load_history(&mut self, index: usize) {
+        let slot: usize = (self.history.head + index) % HISTORY_SIZE;
+        let length: usize = self.history.lengths[slot];
+        self.line[..length].copy_from_slice(&self.history.entries[slot][..length]);
+        self.length = length;
+        self.cursor = length;
+    }
+
+    fn remove(&mut self, start: usize, end: usize) {
+        self.line.copy_within(end..self.length, start);
+        self.length -= end - start;
+        self.cursor = start;
+    }
+
+    fn refresh(&mut self) {
+        back(self.shown);
+        let mut end: usize = self.length;
+        let mut target: usize = self.cursor;
+        if self.searching {
+            lib::io::write(lib::io::STDOUT, SEARCH_PROMPT);
+            lib::io::write(lib::io::STDOUT, &self.query[..self.query_length]);
+            lib::io::write(lib::io::STDOUT, SEARCH_SEPARATOR);
+            end += SEARCH_PROMPT.len() + self.query_length + SEARCH_SEPARATOR.len();
+            target = end;
+        }
+        lib::io::write(lib::io::STDOUT, &self.line[..self.length]);
+        lib::io::write(lib::io::STDOUT, b"\x1B[J");
+        back(end - target);
+        self.shown = target;
+    }
+}
+
+fn back(count: usize) {
+    let mut remaining: usize = count;
+    while remaining > 0 {
+        let chunk: usize = remaining.min(BACKSPACES.len());
+        lib::io::write(lib::io::STDOUT, &BACKSPACES[..chunk]);
+        remaining -= chunk;
+    }
+}
+
+fn read_key() -> Option<Key> {
+    let c: u8 = lib::io::getchar()?;
+    Some(match c {
+        b'\n' | b'\r' => Key::Enter,
+        KEY_BACKSPACE | KEY_DELETE => Key::Backspace,
+        KEY_TAB => Key::Complete,
+        KEY_CTRL_D => Key::EndOfFile,
+        KEY_CTRL_R => Key::Search,
+        KEY_CTRL_U => Key::KillLine,
+        KEY_CTRL_W => Key::KillWord,
+        KEY_ESCAPE => return read_sequence(),
+        c if c >= b' ' => Key::Character(c),
+        _ => Key::Ignored,
+    })
+}
+
+pub fn read_sequence() -> Option<Key> {
+    let c: u8 = lib::io::getchar()?;
+    if c != b'[' && c != b'O' {
+        return Some(Key::Ignored);
+    }
+    let mut parameter: u32 = 0;
+    loop {
+        let c: u8 = lib::io::getchar()?;
+        match c {
+            b'0'..=b'9' => parameter = parameter.saturating_mul(10).saturating_add((c - b'0') as u32),
+            b';' => {}
+            b'A' => return Some(Key::Up),
+            b'B' => return Some(Key::Down),
+            b'C' => return Some(Key::Right),
+            b'D' => return Some(Key::Left),
+            b'H' => return Some(Key::Home),
+            b'F' => return Some(Key::End),
+            b'~' => {
+                return Some(match parameter {
+                    1 | 7 => Key::Home,
+                    3 => Key::Delete,
+                    4 | 8 => Key::End,
+                    5 => Key::PageUp,
+                    6 => Key::PageDown,
+                    _ => Key::Ignored,
+                });
+            }
+            _ => return Some(Key::Ignored),
+        }
+    }
+}
\ No newline at end of file
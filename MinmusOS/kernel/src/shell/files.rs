@@ -0,0 +1,147 @@
+// Project Name:  MinmusOS
+// File Name:     files.rs
+// File Function: Shared helpers of the file commands
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::descriptor::{Descriptor, STDIN};
+use crate::filesystem::file::{FILES, O_RDONLY};
+use crate::multitasking::task::TASK_MANAGER;
+use crate::shell::builtins::{find, usage};
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+use core::str;
+use lib::errno::description;
+
+pub const STANDARD_INPUT: i32 = -1;
+
+pub struct LineReader {
+    source: i32,
+    buffer: [u8; 128],
+    start: usize,
+    end: usize,
+    pub failed: bool,
+}
+
+impl LineReader {
+    pub fn new(source: i32) -> Self {
+        LineReader {
+            source,
+            buffer: [0; 128],
+            start: 0,
+            end: 0,
+            failed: false,
+        }
+    }
+
+    pub fn next(&mut self, line: &mut [u8]) -> Option<usize> {
+        let mut length: usize = 0;
+        let mut any: bool = false;
+        loop {
+            if self.start == self.end {
+                let count: i32 = read(self.source, &mut self.buffer);
+                if count <= 0 {
+                    self.failed = count < 0;
+                    return if any { Some(length) } else { None };
+                }
+                self.start = 0;
+                self.end = count as usize;
+            }
+            any = true;
+            let c: u8 = self.buffer[self.start];
+            self.start += 1;
+            if c == b'\n' {
+                return Some(length);
+            }
+            if length < line.len() {
+                line[length] = c;
+                length += 1;
+            }
+        }
+    }
+}
+
+pub fn error(path: &[u8], error: i32) {
+    unsafe {
+        CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+    }
+    lib::eprintln!("{}: {}!", str::from_utf8(path).unwrap_or("?"), description(-error));
+    unsafe {
+        CONSOLES.printer().reset_colors();
+    }
+}
+
+pub fn open(path: &[u8]) -> i32 {
+    let id: i32 = unsafe { FILES.open(path, O_RDONLY) };
+    if id < 0 {
+        error(path, -id);
+    }
+    id
+}
+
+pub fn read(source: i32, buffer: &mut [u8]) -> i32 {
+    if source == STANDARD_INPUT {
+        lib::io::read(lib::io::STDIN, buffer)
+    } else {
+        unsafe { FILES.read(source as usize, buffer) }
+    }
+}
+
+pub fn close(source: i32) {
+    if source != STANDARD_INPUT {
+        unsafe {
+            FILES.release(source as usize);
+        }
+    }
+}
+
+pub fn each(name: &[u8], paths: &[&[u8]], run: &mut dyn FnMut(i32, &[u8]) -> i32) -> i32 {
+    if paths.is_empty() {
+        if unsafe { TASK_MANAGER.get_file(STDIN) } == Descriptor::Console {
+            return usage(find(name).unwrap());
+        }
+        return run(STANDARD_INPUT, b"");
+    }
+    let mut status: i32 = 0;
+    for &path in paths.iter() {
+        let source: i32 = open(path);
+        if source < 0 {
+            status = 1;
+            continue;
+        }
+        if run(source, path) != 0 {
+            status = 1;
+        }
+        close(source);
+    }
+    status
+}
+
+pub fn header(path: &[u8], first: &mut bool) {
+    if !*first {
+        lib::println!();
+    }
+    *first = false;
+    lib::println!("==> {} <==", str::from_utf8(path).unwrap_or("?"));
+}
+
+pub fn print_attributes(attributes: u32) {
+    for (&bit, &flag) in [0x01, 0x02, 0x04, 0x10, 0x20].iter().zip(b"RHSDA".iter()) {
+        lib::print!("{}", if attributes & bit != 0 { flag as char } else { '-' });
+    }
+}
+
+pub fn print_mode(mode: u32) {
+    for shift in [6, 3, 0].iter() {
+        let bits: u32 = mode >> shift;
+        lib::print!("{}{}{}", if bits & 4 != 0 { 'r' } else { '-' }, if bits & 2 != 0 { 'w' } else { '-' }, if bits & 1 != 0 { 'x' } else { '-' });
+    }
+}
+
+pub fn print_modified(date: u32, time: u32) {
+    if date == 0 {
+        lib::print!("{:<19}", "-");
+        return;
+    }
+    lib::print!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", 1980 + (date >> 9), (date >> 5) & 0x0F, date & 0x1F, time >> 11, (time >> 5) & 0x3F, (time & 0x1F) * 2);
+}
\ No newline at end of file
@@ -0,0 +1,61 @@
+// Project Name:  MinmusOS
+// File Name:     grep.rs
+// File Function: The implementation of the command grep
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::shell::builtins::{find, usage};
+use crate::shell::editor::LINE_SIZE;
+use crate::shell::files::{each, LineReader};
+use crate::shell::regex::matches;
+use core::str;
+
+pub fn grep(args: &[&[u8]]) -> i32 {
+    let (mut fold, mut numbered, mut invert): (bool, bool, bool) = (false, false, false);
+    let mut start: usize = 1;
+    while start < args.len() && args[start].len() > 1 && args[start][0] == b'-' {
+        for &c in args[start][1..].iter() {
+            match c {
+                b'i' => fold = true,
+                b'n' => numbered = true,
+                b'v' => invert = true,
+                _ => return usage(find(b"grep").unwrap()),
+            }
+        }
+        start += 1;
+    }
+    if start >= args.len() {
+        return usage(find(b"grep").unwrap());
+    }
+    let pattern: &[u8] = args[start];
+    let paths: &[&[u8]] = &args[start + 1..];
+    let mut found: bool = false;
+    let status: i32 = each(b"grep", paths, &mut |source: i32, path: &[u8]| -> i32 {
+        let mut reader: LineReader = LineReader::new(source);
+        let mut line: [u8; LINE_SIZE] = [0; LINE_SIZE];
+        let mut number: u32 = 0;
+        while let Some(length) = reader.next(&mut line) {
+            number += 1;
+            if matches(pattern, &line[..length], fold) == invert {
+                continue;
+            }
+            found = true;
+            if paths.len() > 1 {
+                lib::print!("{}:", str::from_utf8(path).unwrap_or("?"));
+            }
+            if numbered {
+                lib::print!("{}:", number);
+            }
+            lib::io::write(lib::io::STDOUT, &line[..length]);
+            lib::io::write(lib::io::STDOUT, b"\n");
+        }
+        reader.failed as i32
+    });
+    if status != 0 {
+        2
+    } else if found {
+        0
+    } else {
+        1
+    }
+}
\ No newline at end of file
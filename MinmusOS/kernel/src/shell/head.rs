@@ -0,0 +1,55 @@
+// Project Name:  MinmusOS
+// File Name:     head.rs
+// File Function: The implementation of the command head
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::shell::builtins::{find, usage};
+use crate::shell::files::{each, header, read};
+use core::str;
+
+pub fn head(args: &[&[u8]]) -> i32 {
+    let (count, paths): (usize, &[&[u8]]) = match lines(args) {
+        Some(parsed) => parsed,
+        None => return usage(find(b"head").unwrap()),
+    };
+    let headers: bool = paths.len() > 1;
+    let mut first: bool = true;
+    each(b"head", paths, &mut |source: i32, path: &[u8]| -> i32 {
+        if headers {
+            header(path, &mut first);
+        }
+        let mut buffer: [u8; 128] = [0; 128];
+        let mut remaining: usize = count;
+        while remaining > 0 {
+            let read: i32 = read(source, &mut buffer);
+            if read <= 0 {
+                return if read < 0 { 1 } else { 0 };
+            }
+            let mut end: usize = 0;
+            while end < read as usize && remaining > 0 {
+                if buffer[end] == b'\n' {
+                    remaining -= 1;
+                }
+                end += 1;
+            }
+            lib::io::write(lib::io::STDOUT, &buffer[..end]);
+        }
+        0
+    })
+}
+
+pub fn lines<'a, 'b>(args: &'a [&'b [u8]]) -> Option<(usize, &'a [&'b [u8]])> {
+    match args.get(1) {
+        Some(&option) if option == b"-n" => Some((number(args.get(2)?)?, &args[3..])),
+        Some(&option) if option.len() > 1 && option[0] == b'-' => {
+            let value: &[u8] = if option[1] == b'n' { &option[2..] } else { &option[1..] };
+            Some((number(value)?, &args[2..]))
+        }
+        _ => Some((10, &args[1..])),
+    }
+}
+
+fn number(text: &[u8]) -> Option<usize> {
+    str::from_utf8(text).ok()?.parse::<usize>().ok()
+}
\ No newline at end of file
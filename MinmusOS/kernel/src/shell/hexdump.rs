@@ -0,0 +1,89 @@
+// Project Name:  MinmusOS
+// File Name:     hexdump.rs
+// File Function: The implementation of the commands hexdump and xxd
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::shell::files::{each, read};
+
+struct Dump {
+    line: [u8; 16],
+    length: usize,
+    offset: u32,
+    xxd: bool,
+}
+
+impl Dump {
+    fn push(&mut self, byte: u8) {
+        self.line[self.length] = byte;
+        self.length += 1;
+        if self.length == self.line.len() {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.length == 0 {
+            return;
+        }
+        if self.xxd {
+            lib::print!("{:08x}:", self.offset);
+        } else {
+            lib::print!("{:08x} ", self.offset);
+        }
+        for i in 0..self.line.len() {
+            if (self.xxd && i % 2 == 0) || (!self.xxd && i % 8 == 0) {
+                lib::print!(" ");
+            }
+            if i < self.length {
+                lib::print!("{:02x}", self.line[i]);
+            } else {
+                lib::print!("  ");
+            }
+            if !self.xxd {
+                lib::print!(" ");
+            }
+        }
+        lib::print!("{}", if self.xxd { "  " } else { " |" });
+        for &c in self.line[..self.length].iter() {
+            lib::print!("{}", if c.is_ascii_graphic() || c == b' ' { c as char } else { '.' });
+        }
+        lib::println!("{}", if self.xxd { "" } else { "|" });
+        self.offset += self.length as u32;
+        self.length = 0;
+    }
+}
+
+pub fn hexdump(args: &[&[u8]]) -> i32 {
+    dump(args, b"hexdump", false)
+}
+
+pub fn xxd(args: &[&[u8]]) -> i32 {
+    dump(args, b"xxd", true)
+}
+
+fn dump(args: &[&[u8]], name: &[u8], xxd: bool) -> i32 {
+    let mut dump: Dump = Dump {
+        line: [0; 16],
+        length: 0,
+        offset: 0,
+        xxd,
+    };
+    let status: i32 = each(name, &args[1..], &mut |source: i32, _path: &[u8]| -> i32 {
+        let mut buffer: [u8; 128] = [0; 128];
+        loop {
+            let read: i32 = read(source, &mut buffer);
+            if read <= 0 {
+                return if read < 0 { 1 } else { 0 };
+            }
+            for &c in buffer[..read as usize].iter() {
+                dump.push(c);
+            }
+        }
+    });
+    dump.flush();
+    if !xxd && dump.offset > 0 {
+        lib::println!("{:08x}", dump.offset);
+    }
+    status
+}
\ No newline at end of file
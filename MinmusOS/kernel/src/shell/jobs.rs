@@ -0,0 +1,232 @@
+// Project Name:  MinmusOS
+// File Name:     jobs.rs
+// File Function: Shell job control
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::ipc::signal::{SIGCONT, SIGKILL, SIGNALS, SIGTSTP};
+use crate::multitasking::task::TASK_MANAGER;
+use crate::shell::parser::{CommandLine, MAX_ARGS};
+use crate::shell::shell::SHELLS;
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+use core::str;
+
+const MAX_JOBS: usize = 8;
+const COMMAND_SIZE: usize = 64;
+
+#[derive(Copy, Clone)]
+struct Job {
+    group: i8,
+    stopped: bool,
+    command: [u8; COMMAND_SIZE],
+    length: usize,
+}
+
+static NULL_JOB: Job = Job {
+    group: -1,
+    stopped: false,
+    command: [0; COMMAND_SIZE],
+    length: 0,
+};
+
+impl Job {
+    fn new(group: i8, line: &CommandLine, first: usize, last: usize) -> Self {
+        let mut job: Job = NULL_JOB;
+        job.group = group;
+        for i in first..=last {
+            if i > first {
+                job.append(b" | ");
+            }
+            let mut argv: [&[u8]; MAX_ARGS] = [&[]; MAX_ARGS];
+            let argc: usize = line.argv(i, &mut argv);
+            for (j, arg) in argv[..argc].iter().enumerate() {
+                if j > 0 {
+                    job.append(b" ");
+                }
+                job.append(arg);
+            }
+        }
+        job
+    }
+
+    fn append(&mut self, text: &[u8]) {
+        for &c in text.iter() {
+            if self.length == COMMAND_SIZE {
+                return;
+            }
+            self.command[self.length] = c;
+            self.length += 1;
+        }
+    }
+
+    fn command(&self) -> &str {
+        str::from_utf8(&self.command[..self.length]).unwrap_or("?")
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Jobs {
+    jobs: [Job; MAX_JOBS],
+    current: usize,
+}
+
+impl Jobs {
+    pub const fn new() -> Self {
+        Jobs {
+            jobs: [NULL_JOB; MAX_JOBS],
+            current: 0,
+        }
+    }
+
+    pub fn start(&mut self, group: i8, pid: i8, background: bool, line: &CommandLine, first: usize, last: usize) -> i32 {
+        let job: Job = Job::new(group, line, first, last);
+        if background {
+            let index: usize = match self.add(job) {
+                Some(index) => index,
+                None => return 1,
+            };
+            lib::println!("[{}] {}", index + 1, group);
+            return 0;
+        }
+        if unsafe { TASK_MANAGER.wait_group(group) } {
+            let index: usize = match self.add(job) {
+                Some(index) => index,
+                None => return 1,
+            };
+            self.stop(index);
+            return 128 + SIGTSTP as i32;
+        }
+        unsafe { TASK_MANAGER.get_status(pid as usize) }
+    }
+
+    pub fn notify(&mut self) {
+        for index in 0..MAX_JOBS {
+            let group: i8 = self.jobs[index].group;
+            if group < 0 {
+                continue;
+            }
+            unsafe {
+                if !TASK_MANAGER.is_group_running(group) {
+                    self.print(index, "Done");
+                    self.jobs[index] = NULL_JOB;
+                } else if TASK_MANAGER.is_group_stopped(group) != self.jobs[index].stopped {
+                    self.jobs[index].stopped = !self.jobs[index].stopped;
+                    if self.jobs[index].stopped {
+                        self.print(index, "Stopped");
+                    }
+                }
+            }
+        }
+    }
+
+    fn add(&mut self, job: Job) -> Option<usize> {
+        let index: usize = match self.jobs.iter().position(|job| job.group < 0) {
+            Some(index) => index,
+            None => {
+                unsafe {
+                    SIGNALS.send_group(job.group, SIGKILL);
+                    CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+                }
+                lib::eprintln!("Too many jobs (max {})!", MAX_JOBS);
+                unsafe {
+                    CONSOLES.printer().reset_colors();
+                }
+                return None;
+            }
+        };
+        self.jobs[index] = job;
+        self.current = index;
+        Some(index)
+    }
+
+    fn select(&self, args: &[&[u8]]) -> Option<usize> {
+        let index: Option<usize> = if args.len() > 1 {
+            let number: &[u8] = args[1].strip_prefix(b"%").unwrap_or(args[1]);
+            match str::from_utf8(number).ok().and_then(|number| number.parse::<usize>().ok()) {
+                Some(number) if number >= 1 && number <= MAX_JOBS && self.jobs[number - 1].group >= 0 => Some(number - 1),
+                _ => None,
+            }
+        } else if self.jobs[self.current].group >= 0 {
+            Some(self.current)
+        } else {
+            self.jobs.iter().rposition(|job| job.group >= 0)
+        };
+        if index.is_none() {
+            unsafe {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            }
+            lib::eprintln!("No such job!");
+            unsafe {
+                CONSOLES.printer().reset_colors();
+            }
+        }
+        index
+    }
+
+    fn stop(&mut self, index: usize) {
+        self.jobs[index].stopped = true;
+        self.current = index;
+        lib::println!();
+        self.print(index, "Stopped");
+    }
+
+    fn print(&self, index: usize, state: &str) {
+        let marker: char = if index == self.current { '+' } else { ' ' };
+        lib::println!("[{}]{} {:<8} {}", index + 1, marker, state, self.jobs[index].command());
+    }
+}
+
+pub fn jobs(_args: &[&[u8]]) -> i32 {
+    unsafe {
+        let jobs: &mut Jobs = &mut SHELLS[CONSOLES.get_current_id()].jobs;
+        jobs.notify();
+        for index in 0..MAX_JOBS {
+            if jobs.jobs[index].group >= 0 {
+                jobs.print(index, if jobs.jobs[index].stopped { "Stopped" } else { "Running" });
+            }
+        }
+    }
+    0
+}
+
+pub fn fg(args: &[&[u8]]) -> i32 {
+    unsafe {
+        let jobs: &mut Jobs = &mut SHELLS[CONSOLES.get_current_id()].jobs;
+        let index: usize = match jobs.select(args) {
+            Some(index) => index,
+            None => return 1,
+        };
+        let group: i8 = jobs.jobs[index].group;
+        lib::println!("{}", jobs.jobs[index].command());
+        jobs.jobs[index].stopped = false;
+        SIGNALS.send_group(group, SIGCONT);
+        if TASK_MANAGER.wait_group(group) {
+            jobs.stop(index);
+        } else {
+            jobs.jobs[index] = NULL_JOB;
+        }
+    }
+    0
+}
+
+pub fn bg(args: &[&[u8]]) -> i32 {
+    unsafe {
+        let jobs: &mut Jobs = &mut SHELLS[CONSOLES.get_current_id()].jobs;
+        let index: usize = match jobs.select(args) {
+            Some(index) => index,
+            None => return 1,
+        };
+        if !jobs.jobs[index].stopped {
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            lib::eprintln!("Job {} is already running!", index + 1);
+            CONSOLES.printer().reset_colors();
+            return 1;
+        }
+        jobs.jobs[index].stopped = false;
+        jobs.current = index;
+        SIGNALS.send_group(jobs.jobs[index].group, SIGCONT);
+        lib::println!("[{}]+ {} &", index + 1, jobs.jobs[index].command());
+    }
+    0
+}
\ No newline at end of file
@@ -1,73 +1,76 @@
 // Project Name:  MinmusOS
-// File Name:     color.rs
+// File Name:     kill.rs
 // File Function: The implementation of the command kill
 // Author:        Jishen Lin
 // License:       MIT License
 
+use crate::drivers::console::CONSOLES;
+use crate::ipc::signal::{signal_name, signal_number, SIGNALS, SIGTERM};
 use crate::multitasking::task::{MAX_TASKS, TASK_MANAGER};
-use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_MAGENTA, COLOR_LIGHT_RED, PRINTER};
+use crate::shell::builtins::{find, usage};
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
 
-pub fn kill(b: &[char]) {
-    let start: usize = b.iter().skip(5).position(|&c| c != ' ' && c != '\0').unwrap_or(b.len());
-    if start + 5 >= b.len() {
-        unsafe {
-            PRINTER.set_colors(COLOR_LIGHT_MAGENTA, COLOR_BLACK);
-        }
-        lib::println!("Usage: kill <pid>");
-        unsafe {
-            PRINTER.reset_colors();
-        }
-        return;
-    }
-    let end: usize = b.iter().skip(start + 5).position(|&c| c == ' ' || c == '\0').map_or(b.len(), |p| p + start + 5);
-    if end <= start + 5 {
-        unsafe {
-            PRINTER.set_colors(COLOR_LIGHT_MAGENTA, COLOR_BLACK);
+pub fn kill(args: &[&[u8]]) -> i32 {
+    let mut signal: u32 = SIGTERM;
+    if args.len() == 3 {
+        if args[1].first() != Some(&b'-') {
+            return usage(find(b"kill").unwrap());
         }
-        lib::println!("Usage: kill <pid>");
-        unsafe {
-            PRINTER.reset_colors();
+        let number: i32 = signal_number(&args[1][1..]);
+        if number < 0 {
+            unsafe {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            }
+            lib::eprintln!("Invalid signal! Use INT, KILL, SEGV, TERM, CHLD, CONT, STOP, TSTP or a number.");
+            unsafe {
+                CONSOLES.printer().reset_colors();
+            }
+            return 1;
         }
-        return;
+        signal = number as u32;
     }
+    let pid: &[u8] = args[args.len() - 1];
     let mut task_id: usize = 0;
-    let mut is_valid_id: bool = true;
-    for i in start + 5..end {
-        if b[i].is_digit(10) {
-            task_id = task_id * 10 + (b[i] as u8 - b'0') as usize;
+    let mut is_valid_id: bool = !pid.is_empty() && pid.len() <= 2;
+    for &c in pid.iter() {
+        if c.is_ascii_digit() {
+            task_id = task_id * 10 + (c - b'0') as usize;
         } else {
             is_valid_id = false;
             break;
         }
     }
-    if is_valid_id {
-        if task_id > 0 && task_id < MAX_TASKS as usize {
-            unsafe {
-                if !TASK_MANAGER.tasks[task_id].running {
-                    PRINTER.set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
-                    lib::println!("Task with PID {} not found!", task_id);
-                    PRINTER.reset_colors();
-                    return;
-                }
-                TASK_MANAGER.remove_task(task_id);
-                lib::println!("Task (PID {}) has been removed.", task_id);
+    if is_valid_id && task_id > 0 && task_id < MAX_TASKS as usize {
+        unsafe {
+            if task_id == TASK_MANAGER.get_current_slot() as usize {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+                lib::eprintln!("Cannot kill the shell itself!");
+                CONSOLES.printer().reset_colors();
+                return 1;
             }
-        } else {
-            unsafe {
-                PRINTER.set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            if !TASK_MANAGER.tasks[task_id].running {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+                lib::eprintln!("Task with PID {} not found!", task_id);
+                CONSOLES.printer().reset_colors();
+                return 1;
             }
-            lib::println!("Please enter a valid PID (1-31).");
-            unsafe {
-                PRINTER.reset_colors();
+            if SIGNALS.kill(task_id, signal) < 0 {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+                lib::eprintln!("Cannot signal task (PID {}): Permission denied!", task_id);
+                CONSOLES.printer().reset_colors();
+                return 1;
             }
+            lib::println!("Signal SIG{} sent to task (PID {}).", signal_name(signal), task_id);
         }
+        0
     } else {
         unsafe {
-            PRINTER.set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
         }
-        lib::println!("Please enter a valid PID (1-31).");
+        lib::eprintln!("Please enter a valid PID (1-31).");
         unsafe {
-            PRINTER.reset_colors();
+            CONSOLES.printer().reset_colors();
         }
+        1
     }
 }
\ No newline at end of file
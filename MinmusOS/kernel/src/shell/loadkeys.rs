@@ -0,0 +1,38 @@
+// Project Name:  MinmusOS
+// File Name:     loadkeys.rs
+// File Function: The implementation of the command loadkeys
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::drivers::layout::{LAYOUT, MAX_NAME_LENGTH};
+use crate::filesystem::config::CONFIG;
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+use core::str;
+
+pub fn loadkeys(args: &[&[u8]]) -> i32 {
+    if args.len() == 1 {
+        unsafe {
+            lib::println!("Current layout: {}", str::from_utf8(LAYOUT.get_name()).unwrap_or("?"));
+        }
+        return 0;
+    }
+    let mut name: [u8; MAX_NAME_LENGTH + 1] = [0; MAX_NAME_LENGTH + 1];
+    let length: usize = args[1].len().min(name.len());
+    for i in 0..length {
+        name[i] = args[1][i].to_ascii_lowercase();
+    }
+    let layout: &str = str::from_utf8(&name[..length]).unwrap_or("?");
+    unsafe {
+        let result: i32 = CONFIG.update(b"keymap", &name[..length]);
+        if result == 0 {
+            lib::println!("Keyboard layout set to {}.", layout);
+            0
+        } else {
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            lib::eprintln!("Cannot load keyboard layout {}: {}", layout, lib::errno::description(result));
+            CONSOLES.printer().reset_colors();
+            1
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,147 @@
+// Project Name:  MinmusOS
+// File Name:     ls.rs
+// File Function: The implementation of the command ls
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::fat::{display_name, Entry, FatDriver, ATTRIBUTE_DIRECTORY, ATTRIBUTE_HIDDEN, ENTRY_COUNT, FAT};
+use crate::multitasking::mutex::Mutex;
+use crate::shell::builtins::{find, usage};
+use crate::shell::files::{print_attributes, print_mode, print_modified};
+use crate::shell::users::{self, User};
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_BLUE};
+use core::cmp::Ordering;
+use core::str;
+
+const COLUMN_WIDTH: usize = 14;
+const COLUMN_COUNT: usize = 5;
+
+static mut INDICES: Mutex<[u16; ENTRY_COUNT]> = Mutex::new([0; ENTRY_COUNT]);
+
+#[derive(Copy, Clone, PartialEq)]
+enum Order {
+    Name,
+    Size,
+    Time,
+}
+
+pub fn ls(args: &[&[u8]]) -> i32 {
+    let (mut long, mut all, mut human, mut reverse): (bool, bool, bool, bool) = (false, false, false, false);
+    let mut order: Order = Order::Name;
+    for &arg in args[1..].iter() {
+        if arg.len() < 2 || arg[0] != b'-' {
+            return usage(find(b"ls").unwrap());
+        }
+        for &c in arg[1..].iter() {
+            match c {
+                b'l' => long = true,
+                b'a' => all = true,
+                b'h' => human = true,
+                b'r' => reverse = true,
+                b'S' => order = Order::Size,
+                b't' => order = Order::Time,
+                _ => return usage(find(b"ls").unwrap()),
+            }
+        }
+    }
+    let indices: &mut [u16; ENTRY_COUNT] = unsafe { INDICES.acquire_mut() };
+    let mut count: usize = 0;
+    unsafe {
+        let fat: &FatDriver = FAT.acquire();
+        for (i, entry) in fat.entries.iter().enumerate() {
+            if entry.name[0] == 0 {
+                break;
+            }
+            if entry.is_visible() && (all || entry.attributes & ATTRIBUTE_HIDDEN == 0) {
+                indices[count] = i as u16;
+                count += 1;
+            }
+        }
+        lib::sort::insertion_sort_by(&mut indices[..count], |&a: &u16, &b: &u16| {
+            let ordering: Ordering = compare(&fat.entries[a as usize], &fat.entries[b as usize], order);
+            (if reverse { ordering.reverse() } else { ordering }) == Ordering::Greater
+        });
+        FAT.free();
+    }
+    let mut owner: Option<User> = None;
+    for (n, &index) in indices[..count].iter().enumerate() {
+        let entry: Entry = unsafe {
+            let entry: Entry = FAT.acquire().entries[index as usize];
+            FAT.free();
+            entry
+        };
+        let mut name: [u8; 12] = [0; 12];
+        let length: usize = display_name(&entry.name, &mut name);
+        if long {
+            print_attributes(entry.attributes as u32);
+            lib::print!(" ");
+            print_mode(entry.mode() as u32);
+            let uid: u16 = entry.owner().0;
+            if owner.map_or(true, |user| user.uid != uid) {
+                owner = users::by_uid(uid);
+            }
+            match owner {
+                Some(user) if user.uid == uid => lib::print!(" {:<8} ", user.name()),
+                _ => lib::print!(" {:<8} ", uid),
+            }
+            print_modified(entry.modified_date as u32, entry.modified_time as u32);
+            lib::print!(" ");
+            print_size(entry.size, human);
+            lib::print!(" ");
+        }
+        print_name(&name[..length], entry.attributes & ATTRIBUTE_DIRECTORY != 0);
+        if long || (n + 1) % COLUMN_COUNT == 0 || n + 1 == count {
+            lib::println!();
+        } else {
+            lib::print!("{:width$}", "", width = COLUMN_WIDTH - length);
+        }
+    }
+    unsafe {
+        INDICES.free();
+    }
+    0
+}
+
+fn compare(a: &Entry, b: &Entry, order: Order) -> Ordering {
+    let ordering: Ordering = match order {
+        Order::Name => Ordering::Equal,
+        Order::Size => {
+            let (first, second): (u32, u32) = (a.size, b.size);
+            second.cmp(&first)
+        }
+        Order::Time => {
+            let first: u32 = (a.modified_date as u32) << 16 | a.modified_time as u32;
+            let second: u32 = (b.modified_date as u32) << 16 | b.modified_time as u32;
+            second.cmp(&first)
+        }
+    };
+    ordering.then_with(|| a.name.cmp(&b.name))
+}
+
+fn print_name(name: &[u8], directory: bool) {
+    if directory {
+        unsafe {
+            CONSOLES.printer().set_colors(COLOR_LIGHT_BLUE, COLOR_BLACK);
+        }
+    }
+    lib::print!("{}", str::from_utf8(name).unwrap_or("?"));
+    if directory {
+        unsafe {
+            CONSOLES.printer().reset_colors();
+        }
+    }
+}
+
+fn print_size(size: u32, human: bool) {
+    if !human || size < 1024 {
+        lib::print!("{:>8}", size);
+        return;
+    }
+    let (value, unit): (u64, char) = if size < 1024 * 1024 { (size as u64 * 10 / 1024, 'K') } else { (size as u64 * 10 / (1024 * 1024), 'M') };
+    if value < 100 {
+        lib::print!("{:>5}.{}{}", value / 10, value % 10, unit);
+    } else {
+        lib::print!("{:>7}{}", value / 10, unit);
+    }
+}
\ No newline at end of file
@@ -5,7 +5,41 @@
 // License:       MIT License
 
 pub mod shell;
+pub mod arithmetic;
+pub mod builtins;
+pub mod cat;
+pub mod chmod;
 pub mod color;
+pub mod complete;
+pub mod config;
+pub mod cp;
 pub mod echo;
+pub mod edit;
+pub mod editor;
+pub mod files;
+pub mod grep;
+pub mod head;
+pub mod hexdump;
+pub mod jobs;
 pub mod kill;
-pub mod cal;
\ No newline at end of file
+pub mod loadkeys;
+pub mod ls;
+pub mod more;
+pub mod mv;
+pub mod cal;
+pub mod parser;
+pub mod pipeline;
+pub mod redirect;
+pub mod regex;
+pub mod rm;
+pub mod run;
+pub mod script;
+pub mod sort;
+pub mod stat;
+pub mod tail;
+pub mod test;
+pub mod touch;
+pub mod uniq;
+pub mod users;
+pub mod variables;
+pub mod wc;
\ No newline at end of file
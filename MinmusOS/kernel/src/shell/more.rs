@@ -0,0 +1,104 @@
+// Project Name:  MinmusOS
+// File Name:     more.rs
+// File Function: The implementation of the pager more
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::drivers::input::MODE_RAW;
+use crate::filesystem::descriptor::{Descriptor, STDOUT};
+use crate::multitasking::task::TASK_MANAGER;
+use crate::shell::files::{each, read};
+use crate::syscalls::print::{COLOR_BLACK, COLOR_WHITE};
+
+const PAGE_ROWS: usize = 24;
+const COLUMNS: usize = 80;
+
+struct Pager {
+    rows: usize,
+    column: usize,
+    paging: bool,
+}
+
+impl Pager {
+    fn write(&mut self, bytes: &[u8]) -> bool {
+        let mut start: usize = 0;
+        for i in 0..bytes.len() {
+            self.column += 1;
+            if bytes[i] == b'\n' || self.column == COLUMNS {
+                self.rows += 1;
+                self.column = 0;
+            }
+            if self.paging && self.rows == PAGE_ROWS {
+                lib::io::write(lib::io::STDOUT, &bytes[start..=i]);
+                start = i + 1;
+                if !self.prompt() {
+                    return false;
+                }
+            }
+        }
+        lib::io::write(lib::io::STDOUT, &bytes[start..]);
+        true
+    }
+
+    fn prompt(&mut self) -> bool {
+        unsafe {
+            CONSOLES.printer().set_colors(COLOR_BLACK, COLOR_WHITE);
+        }
+        lib::print!("--More--");
+        unsafe {
+            CONSOLES.printer().reset_colors();
+        }
+        let mode: i32 = unsafe { CONSOLES.current().set_mode(MODE_RAW) };
+        let mut key: [u8; 1] = [0];
+        let proceed: bool = loop {
+            if unsafe { CONSOLES.current().read(&mut key) } <= 0 {
+                break false;
+            }
+            match key[0] {
+                b' ' => {
+                    self.rows = 0;
+                    break true;
+                }
+                b'\n' | b'\r' => {
+                    self.rows = PAGE_ROWS - 1;
+                    break true;
+                }
+                b'q' | b'Q' => break false,
+                _ => {}
+            }
+        };
+        unsafe {
+            CONSOLES.current().set_mode(mode as u32);
+        }
+        lib::print!("\r\x1b[K");
+        proceed
+    }
+}
+
+pub fn more(args: &[&[u8]]) -> i32 {
+    let mut pager: Pager = Pager {
+        rows: 0,
+        column: 0,
+        paging: unsafe { TASK_MANAGER.get_file(STDOUT) } == Descriptor::Console,
+    };
+    let mut quit: bool = false;
+    let headers: bool = args.len() > 2;
+    each(args[0], &args[1..], &mut |source: i32, path: &[u8]| -> i32 {
+        if quit {
+            return 0;
+        }
+        if headers {
+            quit = !(pager.write(b"==> ") && pager.write(path) && pager.write(b" <==\n"));
+        }
+        let mut buffer: [u8; 128] = [0; 128];
+        while !quit {
+            let count: i32 = read(source, &mut buffer);
+            if count <= 0 {
+                return if count < 0 { 1 } else { 0 };
+            }
+            quit = !pager.write(&buffer[..count as usize]);
+        }
+        0
+    })
+}
\ No newline at end of file
@@ -0,0 +1,18 @@
+// Project Name:  MinmusOS
+// File Name:     mv.rs
+// File Function: The implementation of the command mv
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::filesystem::file::FILES;
+use crate::shell::files::error;
+use lib::errno::ENOENT;
+
+pub fn mv(args: &[&[u8]]) -> i32 {
+    let result: i32 = unsafe { FILES.rename(args[1], args[2]) };
+    if result < 0 {
+        error(if result == -ENOENT { args[1] } else { args[2] }, -result);
+        return 1;
+    }
+    0
+}
\ No newline at end of file
@@ -0,0 +1,372 @@
+// Project Name:  MinmusOS
+// File Name:     parser.rs
+// File Function: Shell command line lexer and parser
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::filesystem::descriptor::{STDERR, STDIN, STDOUT};
+use crate::filesystem::file::{O_APPEND, O_CREAT, O_RDONLY, O_TRUNC, O_WRONLY};
+use crate::shell::editor::LINE_SIZE;
+use crate::shell::script::MAX_UNITS;
+
+pub const MAX_ARGS: usize = 16;
+pub const MAX_REDIRECTIONS: usize = 3;
+const MAX_WORDS: usize = 64;
+const MAX_COMMANDS: usize = 16;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Operator {
+    End,
+    Sequence,
+    Pipe,
+    And,
+    Or,
+    Background,
+}
+
+impl Operator {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Operator::End => "newline",
+            Operator::Sequence => ";",
+            Operator::Pipe => "|",
+            Operator::And => "&&",
+            Operator::Or => "||",
+            Operator::Background => "&",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParseError {
+    UnterminatedQuote(u8),
+    UnexpectedToken(Operator),
+    UnexpectedKeyword(&'static str),
+    UnexpectedEnd,
+    MissingTarget,
+    BadSubstitution,
+    BadExpression,
+    TooManyArguments,
+    TooManyCommands,
+    TooManyStatements,
+    TooLong,
+}
+
+impl ParseError {
+    pub fn print(&self) {
+        match self {
+            ParseError::UnterminatedQuote(quote) => {
+                lib::eprintln!("Syntax error: unterminated {} quote", *quote as char);
+            }
+            ParseError::UnexpectedToken(operator) => {
+                lib::eprintln!("Syntax error near unexpected token `{}'", operator.symbol());
+            }
+            ParseError::UnexpectedKeyword(keyword) => {
+                lib::eprintln!("Syntax error near unexpected token `{}'", keyword);
+            }
+            ParseError::UnexpectedEnd => {
+                lib::eprintln!("Syntax error: unexpected end of file");
+            }
+            ParseError::MissingTarget => {
+                lib::eprintln!("Syntax error: missing file name after redirection");
+            }
+            ParseError::BadSubstitution => {
+                lib::eprintln!("Bad substitution!");
+            }
+            ParseError::BadExpression => {
+                lib::eprintln!("Invalid arithmetic expression!");
+            }
+            ParseError::TooManyArguments => {
+                lib::eprintln!("Too many arguments (max {})!", MAX_ARGS);
+            }
+            ParseError::TooManyCommands => {
+                lib::eprintln!("Too many commands (max {})!", MAX_COMMANDS);
+            }
+            ParseError::TooManyStatements => {
+                lib::eprintln!("Too many statements (max {})!", MAX_UNITS);
+            }
+            ParseError::TooLong => {
+                lib::eprintln!("Command too long!");
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Redirection {
+    start: usize,
+    end: usize,
+    flags: u32,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Command {
+    start: usize,
+    argc: usize,
+    redirections: [Option<Redirection>; MAX_REDIRECTIONS],
+    pub next: Operator,
+}
+
+static NULL_COMMAND: Command = Command {
+    start: 0,
+    argc: 0,
+    redirections: [None; MAX_REDIRECTIONS],
+    next: Operator::End,
+};
+
+impl Command {
+    fn is_empty(&self) -> bool {
+        self.argc == 0 && self.redirections.iter().all(|redirection| redirection.is_none())
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct CommandLine {
+    storage: [u8; LINE_SIZE],
+    used: usize,
+    words: [(usize, usize); MAX_WORDS],
+    word_count: usize,
+    commands: [Command; MAX_COMMANDS],
+    count: usize,
+}
+
+#[derive(Copy, Clone)]
+pub struct Invocation {
+    storage: [u8; LINE_SIZE],
+    used: usize,
+    words: [(usize, usize); MAX_ARGS],
+    argc: usize,
+    redirections: [Option<Redirection>; MAX_REDIRECTIONS],
+}
+
+enum Token {
+    Word,
+    Operator(Operator),
+    Redirection(usize, u32),
+}
+
+impl CommandLine {
+    pub const fn new() -> Self {
+        CommandLine {
+            storage: [0; LINE_SIZE],
+            used: 0,
+            words: [(0, 0); MAX_WORDS],
+            word_count: 0,
+            commands: [NULL_COMMAND; MAX_COMMANDS],
+            count: 0,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn get(&self, index: usize) -> &Command {
+        &self.commands[index]
+    }
+
+    pub fn argv<'a>(&'a self, index: usize, argv: &mut [&'a [u8]; MAX_ARGS]) -> usize {
+        let command: &Command = &self.commands[index];
+        for i in 0..command.argc {
+            let (start, end): (usize, usize) = self.words[command.start + i];
+            argv[i] = &self.storage[start..end];
+        }
+        command.argc
+    }
+
+    pub fn shift(&mut self, index: usize, count: usize) {
+        let command: &mut Command = &mut self.commands[index];
+        let count: usize = count.min(command.argc);
+        command.start += count;
+        command.argc -= count;
+    }
+
+    pub fn invocation(&self, index: usize) -> Invocation {
+        let command: &Command = &self.commands[index];
+        let mut invocation: Invocation = Invocation {
+            storage: [0; LINE_SIZE],
+            used: 0,
+            words: [(0, 0); MAX_ARGS],
+            argc: command.argc,
+            redirections: [None; MAX_REDIRECTIONS],
+        };
+        for (i, &(start, end)) in self.words[command.start..command.start + command.argc].iter().enumerate() {
+            invocation.words[i] = invocation.copy(&self.storage[start..end]);
+        }
+        for (fd, redirection) in command.redirections.iter().enumerate() {
+            if let Some(redirection) = redirection {
+                let (start, end): (usize, usize) = invocation.copy(&self.storage[redirection.start..redirection.end]);
+                invocation.redirections[fd] = Some(Redirection { start, end, flags: redirection.flags });
+            }
+        }
+        invocation
+    }
+
+    pub fn parse(&mut self, line: &[u8]) -> Result<(), ParseError> {
+        self.used = 0;
+        self.word_count = 0;
+        self.count = 0;
+        let mut command: Command = Command {
+            start: 0,
+            ..NULL_COMMAND
+        };
+        let mut position: usize = 0;
+        loop {
+            let token: Option<Token> = self.next_token(line, &mut position)?;
+            match token {
+                Some(Token::Word) => {
+                    if command.argc == MAX_ARGS {
+                        return Err(ParseError::TooManyArguments);
+                    }
+                    command.argc += 1;
+                }
+                Some(Token::Redirection(fd, flags)) => {
+                    if !matches!(self.next_token(line, &mut position)?, Some(Token::Word)) {
+                        return Err(ParseError::MissingTarget);
+                    }
+                    self.word_count -= 1;
+                    let (start, end): (usize, usize) = self.words[self.word_count];
+                    command.redirections[fd] = Some(Redirection { start, end, flags });
+                }
+                Some(Token::Operator(operator)) => {
+                    if command.is_empty() {
+                        return Err(ParseError::UnexpectedToken(operator));
+                    }
+                    command.next = operator;
+                    self.push(command)?;
+                    command = Command {
+                        start: self.word_count,
+                        ..NULL_COMMAND
+                    };
+                }
+                None => break,
+            }
+        }
+        if !command.is_empty() {
+            self.push(command)?;
+        } else if self.count > 0 && !matches!(self.commands[self.count - 1].next, Operator::Sequence | Operator::Background) {
+            return Err(ParseError::UnexpectedToken(Operator::End));
+        }
+        if self.count > 0 && self.commands[self.count - 1].next == Operator::Sequence {
+            self.commands[self.count - 1].next = Operator::End;
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, command: Command) -> Result<(), ParseError> {
+        if self.count == MAX_COMMANDS {
+            return Err(ParseError::TooManyCommands);
+        }
+        self.commands[self.count] = command;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn next_token(&mut self, line: &[u8], position: &mut usize) -> Result<Option<Token>, ParseError> {
+        while *position < line.len() && (line[*position] == b' ' || line[*position] == b'\t') {
+            *position += 1;
+        }
+        if *position >= line.len() {
+            return Ok(None);
+        }
+        let c: u8 = line[*position];
+        let next: u8 = line.get(*position + 1).copied().unwrap_or(0);
+        let operator: Option<(Operator, usize)> = match (c, next) {
+            (b'&', b'&') => Some((Operator::And, 2)),
+            (b'|', b'|') => Some((Operator::Or, 2)),
+            (b'|', _) => Some((Operator::Pipe, 1)),
+            (b';', _) => Some((Operator::Sequence, 1)),
+            (b'&', _) => Some((Operator::Background, 1)),
+            _ => None,
+        };
+        if let Some((operator, length)) = operator {
+            *position += length;
+            return Ok(Some(Token::Operator(operator)));
+        }
+        let third: u8 = line.get(*position + 2).copied().unwrap_or(0);
+        let redirection: Option<(usize, u32, usize)> = match (c, next, third) {
+            (b'<', _, _) => Some((STDIN, O_RDONLY, 1)),
+            (b'>', b'>', _) => Some((STDOUT, O_WRONLY | O_CREAT | O_APPEND, 2)),
+            (b'>', _, _) => Some((STDOUT, O_WRONLY | O_CREAT | O_TRUNC, 1)),
+            (b'2', b'>', b'>') => Some((STDERR, O_WRONLY | O_CREAT | O_APPEND, 3)),
+            (b'2', b'>', _) => Some((STDERR, O_WRONLY | O_CREAT | O_TRUNC, 2)),
+            _ => None,
+        };
+        if let Some((fd, flags, length)) = redirection {
+            *position += length;
+            return Ok(Some(Token::Redirection(fd, flags)));
+        }
+        if self.word_count == MAX_WORDS {
+            return Err(ParseError::TooManyArguments);
+        }
+        let start: usize = self.used;
+        while *position < line.len() {
+            let c: u8 = line[*position];
+            match c {
+                b' ' | b'\t' | b'&' | b'|' | b';' | b'<' | b'>' => break,
+                b'\'' => {
+                    *position += 1;
+                    loop {
+                        match line.get(*position) {
+                            Some(b'\'') => break,
+                            Some(&c) => self.store(c),
+                            None => return Err(ParseError::UnterminatedQuote(b'\'')),
+                        }
+                        *position += 1;
+                    }
+                }
+                b'"' => {
+                    *position += 1;
+                    loop {
+                        match line.get(*position) {
+                            Some(b'"') => break,
+                            Some(b'\\') if matches!(line.get(*position + 1), Some(b'"') | Some(b'\\')) => {
+                                *position += 1;
+                                self.store(line[*position]);
+                            }
+                            Some(&c) => self.store(c),
+                            None => return Err(ParseError::UnterminatedQuote(b'"')),
+                        }
+                        *position += 1;
+                    }
+                }
+                b'\\' if *position + 1 < line.len() => {
+                    *position += 1;
+                    self.store(line[*position]);
+                }
+                _ => self.store(c),
+            }
+            *position += 1;
+        }
+        self.words[self.word_count] = (start, self.used);
+        self.word_count += 1;
+        Ok(Some(Token::Word))
+    }
+
+    fn store(&mut self, c: u8) {
+        if self.used < LINE_SIZE {
+            self.storage[self.used] = c;
+            self.used += 1;
+        }
+    }
+}
+
+impl Invocation {
+    pub fn argv<'a>(&'a self, argv: &mut [&'a [u8]; MAX_ARGS]) -> usize {
+        for (arg, &(start, end)) in argv.iter_mut().zip(self.words[..self.argc].iter()) {
+            *arg = &self.storage[start..end];
+        }
+        self.argc
+    }
+
+    pub fn redirection(&self, fd: usize) -> Option<(&[u8], u32)> {
+        self.redirections[fd].map(|redirection| (&self.storage[redirection.start..redirection.end], redirection.flags))
+    }
+
+    fn copy(&mut self, text: &[u8]) -> (usize, usize) {
+        let start: usize = self.used;
+        self.storage[start..start + text.len()].copy_from_slice(text);
+        self.used += text.len();
+        (start, self.used)
+    }
+}
\ No newline at end of file
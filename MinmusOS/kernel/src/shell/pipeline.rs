@@ -0,0 +1,89 @@
+// Project Name:  MinmusOS
+// File Name:     pipeline.rs
+// File Function: The implementation of shell pipelines
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::descriptor::{Descriptor, STDIN, STDOUT};
+use crate::ipc::pipe::PIPES;
+use crate::multitasking::task::TASK_MANAGER;
+use crate::shell::parser::{CommandLine, Invocation};
+use crate::shell::shell::Shell;
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+use core::arch::asm;
+
+const MAX_STAGES: usize = 4;
+
+pub unsafe fn pipeline(line: &CommandLine, first: usize, last: usize) -> (i8, i8) {
+    let count: usize = last - first + 1;
+    if count > MAX_STAGES {
+        CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+        lib::eprintln!("Too many pipeline stages (max {})!", MAX_STAGES);
+        CONSOLES.printer().reset_colors();
+        return (-1, -1);
+    }
+
+    let mut pipes: [i8; MAX_STAGES] = [-1; MAX_STAGES];
+    for i in 0..count - 1 {
+        pipes[i] = PIPES.create();
+        if pipes[i] < 0 {
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            lib::eprintln!("No free pipes available!");
+            CONSOLES.printer().reset_colors();
+            close_pipes(&pipes);
+            return (-1, -1);
+        }
+    }
+
+    let mut pids: [i8; MAX_STAGES] = [-1; MAX_STAGES];
+    asm!("cli");
+    for i in 0..count {
+        let invocation: Invocation = line.invocation(first + i);
+        pids[i] = TASK_MANAGER.add_task_with(stage as u32, &invocation);
+        if pids[i] < 0 {
+            break;
+        }
+        let pid: usize = pids[i] as usize;
+        if i > 0 {
+            TASK_MANAGER.set_file(pid, STDIN, Descriptor::PipeReader(pipes[i - 1] as usize));
+        }
+        if i < count - 1 {
+            TASK_MANAGER.set_file(pid, STDOUT, Descriptor::PipeWriter(pipes[i] as usize));
+        }
+        TASK_MANAGER.tasks[pid].group = pids[0];
+    }
+    let failed: bool = pids[..count].iter().any(|&pid| pid < 0);
+    if failed {
+        for &pid in pids[..count].iter().filter(|&&pid| pid >= 0) {
+            TASK_MANAGER.remove_task(pid as usize);
+        }
+    }
+    close_pipes(&pipes);
+    asm!("sti");
+
+    if failed {
+        CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+        lib::eprintln!("No free task slots available!");
+        CONSOLES.printer().reset_colors();
+        return (-1, -1);
+    }
+    (pids[0], pids[count - 1])
+}
+
+unsafe fn close_pipes(pipes: &[i8]) {
+    for &id in pipes.iter() {
+        if id >= 0 {
+            PIPES.close_reader(id as usize);
+            PIPES.close_writer(id as usize);
+        }
+    }
+}
+
+extern "C" fn stage(invocation: u32) {
+    unsafe {
+        let status: i32 = Shell::execute(&*(invocation as *const Invocation));
+        TASK_MANAGER.wait_children();
+        TASK_MANAGER.set_status(status);
+    }
+}
\ No newline at end of file
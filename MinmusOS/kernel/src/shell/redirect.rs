@@ -0,0 +1,48 @@
+// Project Name:  MinmusOS
+// File Name:     redirect.rs
+// File Function: Shell I/O redirection
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::descriptor::Descriptor;
+use crate::filesystem::file::FILES;
+use crate::multitasking::task::TASK_MANAGER;
+use crate::shell::parser::{Invocation, MAX_REDIRECTIONS};
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+use core::str;
+use lib::errno::description;
+
+pub unsafe fn apply(invocation: &Invocation, saved: &mut [Option<Descriptor>; MAX_REDIRECTIONS]) -> bool {
+    let task: usize = TASK_MANAGER.get_current_slot() as usize;
+    for fd in 0..MAX_REDIRECTIONS {
+        let (path, flags): (&[u8], u32) = match invocation.redirection(fd) {
+            Some(redirection) => redirection,
+            None => continue,
+        };
+        let id: i32 = FILES.open(path, flags);
+        if id < 0 {
+            restore(saved);
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            lib::eprintln!("{}: {}!", str::from_utf8(path).unwrap_or("?"), description(id));
+            CONSOLES.printer().reset_colors();
+            return false;
+        }
+        let previous: Descriptor = TASK_MANAGER.get_file(fd);
+        previous.duplicate();
+        saved[fd] = Some(previous);
+        TASK_MANAGER.set_file(task, fd, Descriptor::File(id as usize));
+        FILES.release(id as usize);
+    }
+    true
+}
+
+pub unsafe fn restore(saved: &mut [Option<Descriptor>; MAX_REDIRECTIONS]) {
+    let task: usize = TASK_MANAGER.get_current_slot() as usize;
+    for (fd, slot) in saved.iter_mut().enumerate() {
+        if let Some(previous) = slot.take() {
+            TASK_MANAGER.set_file(task, fd, previous);
+            previous.close();
+        }
+    }
+}
\ No newline at end of file
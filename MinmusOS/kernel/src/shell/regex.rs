@@ -0,0 +1,96 @@
+// Project Name:  MinmusOS
+// File Name:     regex.rs
+// File Function: Basic regular expression matching
+// Author:        Jishen Lin
+// License:       MIT License
+
+pub fn matches(pattern: &[u8], text: &[u8], fold: bool) -> bool {
+    if pattern.first() == Some(&b'^') {
+        return here(&pattern[1..], text, fold);
+    }
+    (0..=text.len()).any(|start| here(pattern, &text[start..], fold))
+}
+
+fn here(mut pattern: &[u8], mut text: &[u8], fold: bool) -> bool {
+    loop {
+        if pattern.is_empty() {
+            return true;
+        }
+        if pattern == b"$" {
+            return text.is_empty();
+        }
+        let length: usize = atom(pattern);
+        if pattern.get(length) == Some(&b'*') {
+            return star(&pattern[..length], &pattern[length + 1..], text, fold);
+        }
+        if text.is_empty() || !single(&pattern[..length], text[0], fold) {
+            return false;
+        }
+        pattern = &pattern[length..];
+        text = &text[1..];
+    }
+}
+
+fn star(atom: &[u8], rest: &[u8], text: &[u8], fold: bool) -> bool {
+    let mut i: usize = 0;
+    loop {
+        if here(rest, &text[i..], fold) {
+            return true;
+        }
+        if i < text.len() && single(atom, text[i], fold) {
+            i += 1;
+        } else {
+            return false;
+        }
+    }
+}
+
+fn atom(pattern: &[u8]) -> usize {
+    match pattern[0] {
+        b'\\' if pattern.len() > 1 => 2,
+        b'[' => {
+            let mut i: usize = 1;
+            if pattern.get(i) == Some(&b'^') {
+                i += 1;
+            }
+            if pattern.get(i) == Some(&b']') {
+                i += 1;
+            }
+            while i < pattern.len() && pattern[i] != b']' {
+                i += 1;
+            }
+            if i < pattern.len() { i + 1 } else { 1 }
+        }
+        _ => 1,
+    }
+}
+
+fn single(atom: &[u8], c: u8, fold: bool) -> bool {
+    match atom[0] {
+        b'.' => true,
+        b'[' if atom.len() > 1 => class(&atom[1..atom.len() - 1], c, fold),
+        b'\\' if atom.len() > 1 => same(atom[1], c, fold),
+        first => same(first, c, fold),
+    }
+}
+
+fn class(set: &[u8], c: u8, fold: bool) -> bool {
+    let (negate, set): (bool, &[u8]) = if set.first() == Some(&b'^') { (true, &set[1..]) } else { (false, set) };
+    let mut found: bool = false;
+    let mut i: usize = 0;
+    while i < set.len() {
+        if i + 2 < set.len() && set[i + 1] == b'-' {
+            let inside = |c: u8| set[i] <= c && c <= set[i + 2];
+            found |= inside(c) || (fold && (inside(c.to_ascii_lowercase()) || inside(c.to_ascii_uppercase())));
+            i += 3;
+        } else {
+            found |= same(set[i], c, fold);
+            i += 1;
+        }
+    }
+    found != negate
+}
+
+fn same(a: u8, b: u8, fold: bool) -> bool {
+    if fold { a.eq_ignore_ascii_case(&b) } else { a == b }
+}
\ No newline at end of file
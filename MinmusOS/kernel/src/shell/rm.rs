@@ -0,0 +1,20 @@
+// Project Name:  MinmusOS
+// File Name:     rm.rs
+// File Function: The implementation of the command rm
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::filesystem::file::FILES;
+use crate::shell::files::error;
+
+pub fn rm(args: &[&[u8]]) -> i32 {
+    let mut status: i32 = 0;
+    for &path in args[1..].iter() {
+        let result: i32 = unsafe { FILES.unlink(path) };
+        if result < 0 {
+            error(path, -result);
+            status = 1;
+        }
+    }
+    status
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+// Project Name:  MinmusOS
+// File Name:     run.rs
+// File Function: The implementation of the command run
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::filesystem::fat::{Entry, FatDriver, ACCESS_EXECUTE, ACCESS_READ, FAT};
+use crate::memory::paging::PAGING;
+use crate::memory::paging::TABLES;
+use crate::multitasking::task::TASK_MANAGER;
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+
+pub const APP_SIGNATURE: u32 = 0xB16B00B5;
+const APP_TARGET: u32 = 0x00A00000;
+const APP_SIZE: u32 = 0x00010000;
+
+pub fn run(args: &[&[u8]]) -> i32 {
+    unsafe {
+        let fat: &FatDriver = FAT.acquire();
+        let entry: &Entry = fat.search_file(args[1]);
+        let slot: i8 = TASK_MANAGER.get_free_slot();
+        let (uid, gid): (u16, u16) = TASK_MANAGER.get_credentials();
+        if entry.name[0] != 0 && !entry.allows(uid, gid, ACCESS_READ | ACCESS_EXECUTE) {
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            lib::eprintln!("Permission denied!");
+            CONSOLES.printer().reset_colors();
+        } else if entry.name[0] != 0 && slot < 0 {
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            lib::eprintln!("No free task slots available!");
+            CONSOLES.printer().reset_colors();
+        } else if entry.name[0] != 0 {
+            let target: u32 = APP_TARGET + (slot as u32 * APP_SIZE);
+            TABLES[8].set(target);
+            PAGING.set_table(8, &TABLES[8]);
+            fat.read_file_to_target(&entry, target as *mut u32);
+            if *(target as *mut u32) == APP_SIGNATURE {
+                let pid: i8 = TASK_MANAGER.add_task(target + 4, 0);
+                FAT.free();
+                TASK_MANAGER.wait_job(TASK_MANAGER.tasks[pid as usize].group, pid as usize);
+                return 0;
+            } else {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+                lib::eprintln!("This file is not a valid executable!");
+                CONSOLES.printer().reset_colors();
+            }
+        } else {
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            lib::eprintln!("Application not found!");
+            CONSOLES.printer().reset_colors();
+        }
+        FAT.free();
+    }
+    1
+}
\ No newline at end of file
@@ -0,0 +1,468 @@
+// Project Name:  MinmusOS
+// File Name:     script.rs
+// File Function: Shell script storage and structure
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::filesystem::file::{FILES, O_RDONLY};
+use crate::shell::editor::LINE_SIZE;
+use crate::shell::parser::{Operator, ParseError};
+use crate::shell::variables::{is_name, Variables, NAME_SIZE};
+use lib::errno::E2BIG;
+
+pub const ARENA_SIZE: usize = 2048;
+pub const MAX_UNITS: usize = 128;
+pub const MAX_FUNCTIONS: usize = 8;
+pub const POOL_SIZE: usize = 1024;
+const MAX_NESTING: usize = 16;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Keyword {
+    None,
+    If,
+    Then,
+    Elif,
+    Else,
+    Fi,
+    While,
+    Do,
+    Done,
+    For,
+    Function,
+    Open,
+    Close,
+}
+
+impl Keyword {
+    fn parse(word: &[u8]) -> Keyword {
+        match word {
+            b"if" => Keyword::If,
+            b"then" => Keyword::Then,
+            b"elif" => Keyword::Elif,
+            b"else" => Keyword::Else,
+            b"fi" => Keyword::Fi,
+            b"while" => Keyword::While,
+            b"do" => Keyword::Do,
+            b"done" => Keyword::Done,
+            b"for" => Keyword::For,
+            b"{" => Keyword::Open,
+            b"}" => Keyword::Close,
+            _ if word.ends_with(b"()") && is_name(&word[..word.len() - 2]) => Keyword::Function,
+            _ => Keyword::None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Keyword::None => "",
+            Keyword::If => "if",
+            Keyword::Then => "then",
+            Keyword::Elif => "elif",
+            Keyword::Else => "else",
+            Keyword::Fi => "fi",
+            Keyword::While => "while",
+            Keyword::Do => "do",
+            Keyword::Done => "done",
+            Keyword::For => "for",
+            Keyword::Function => "()",
+            Keyword::Open => "{",
+            Keyword::Close => "}",
+        }
+    }
+
+    fn is_opener(&self) -> bool {
+        matches!(self, Keyword::If | Keyword::While | Keyword::For | Keyword::Open)
+    }
+
+    fn is_closer(&self) -> bool {
+        matches!(self, Keyword::Fi | Keyword::Done | Keyword::Close)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Connector {
+    Sequence,
+    And,
+    Or,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Flow {
+    Normal,
+    Break,
+    Continue,
+    Return,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Unit {
+    start: u16,
+    end: u16,
+    pub keyword: Keyword,
+    pub connector: Connector,
+}
+
+static NULL_UNIT: Unit = Unit {
+    start: 0,
+    end: 0,
+    keyword: Keyword::None,
+    connector: Connector::Sequence,
+};
+
+#[derive(Copy, Clone)]
+struct Function {
+    name: [u8; NAME_SIZE],
+    name_length: usize,
+    start: usize,
+    end: usize,
+}
+
+static NULL_FUNCTION: Function = Function {
+    name: [0; NAME_SIZE],
+    name_length: 0,
+    start: 0,
+    end: 0,
+};
+
+#[derive(Copy, Clone)]
+pub struct Script {
+    arena: [u8; ARENA_SIZE],
+    used: usize,
+    units: [Unit; MAX_UNITS],
+    count: usize,
+    functions: [Function; MAX_FUNCTIONS],
+    pool: [u8; POOL_SIZE],
+    pool_used: usize,
+    expansion: [u8; LINE_SIZE],
+    pub flow: Flow,
+    pub loops: usize,
+    pub calls: usize,
+}
+
+impl Script {
+    pub const fn new() -> Self {
+        Script {
+            arena: [0; ARENA_SIZE],
+            used: 0,
+            units: [NULL_UNIT; MAX_UNITS],
+            count: 0,
+            functions: [NULL_FUNCTION; MAX_FUNCTIONS],
+            pool: [0; POOL_SIZE],
+            pool_used: 0,
+            expansion: [0; LINE_SIZE],
+            flow: Flow::Normal,
+            loops: 0,
+            calls: 0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.used = 0;
+        self.count = 0;
+        self.flow = Flow::Normal;
+        self.loops = 0;
+        self.calls = 0;
+    }
+
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn release(&mut self, used: usize, count: usize) {
+        self.used = used;
+        self.count = count;
+    }
+
+    pub fn unit(&self, index: usize) -> Unit {
+        self.units[index]
+    }
+
+    pub fn append(&mut self, line: &[u8]) -> bool {
+        let separator: usize = if self.used > 0 { 1 } else { 0 };
+        if self.used + separator + line.len() > ARENA_SIZE {
+            return false;
+        }
+        if separator > 0 {
+            self.arena[self.used] = b'\n';
+        }
+        self.arena[self.used + separator..self.used + separator + line.len()].copy_from_slice(line);
+        self.used += separator + line.len();
+        true
+    }
+
+    pub fn load(&mut self, path: &[u8]) -> Result<(usize, usize), i32> {
+        let id: i32 = unsafe { FILES.open(path, O_RDONLY) };
+        if id < 0 {
+            return Err(id);
+        }
+        let start: usize = self.used;
+        let mut end: usize = start;
+        unsafe {
+            loop {
+                let read: i32 = FILES.read(id as usize, &mut self.arena[end..]);
+                if read <= 0 {
+                    break;
+                }
+                end += read as usize;
+            }
+            let mut extra: [u8; 1] = [0; 1];
+            let full: bool = end == ARENA_SIZE && FILES.read(id as usize, &mut extra) > 0;
+            FILES.release(id as usize);
+            if full {
+                return Err(-E2BIG);
+            }
+        }
+        self.used = end;
+        Ok((start, end))
+    }
+
+    pub fn expand(&mut self, unit: &Unit, variables: &Variables) -> Result<&[u8], ParseError> {
+        let length: usize = variables.expand(&self.arena[unit.start as usize..unit.end as usize], &mut self.expansion)?;
+        Ok(&self.expansion[..length])
+    }
+
+    pub fn split(&mut self, start: usize, end: usize) -> Result<usize, ParseError> {
+        let first: usize = self.count;
+        let mut position: usize = start;
+        let mut unit: usize = start;
+        let mut quote: u8 = 0;
+        let mut word_start: bool = true;
+        while position < end {
+            let c: u8 = self.arena[position];
+            let next: u8 = if position + 1 < end { self.arena[position + 1] } else { 0 };
+            if quote != 0 {
+                if c == quote {
+                    quote = 0;
+                } else if c == b'\\' && quote == b'"' {
+                    position += 1;
+                }
+                position += 1;
+                continue;
+            }
+            match c {
+                b'\'' | b'"' => quote = c,
+                b'\\' if next == b'\n' => {
+                    self.arena[position] = b' ';
+                    self.arena[position + 1] = b' ';
+                }
+                b'\\' => position += 1,
+                b'#' if word_start => {
+                    while position < end && self.arena[position] != b'\n' {
+                        self.arena[position] = b' ';
+                        position += 1;
+                    }
+                    continue;
+                }
+                b'\n' | b';' => {
+                    self.emit(unit, position, Connector::Sequence)?;
+                    unit = position + 1;
+                }
+                b'&' if next == b'&' => {
+                    self.emit(unit, position, Connector::And)?;
+                    position += 1;
+                    unit = position + 1;
+                }
+                b'|' if next == b'|' => {
+                    self.emit(unit, position, Connector::Or)?;
+                    position += 1;
+                    unit = position + 1;
+                }
+                b'&' => {
+                    self.emit(unit, position + 1, Connector::Sequence)?;
+                    unit = position + 1;
+                }
+                _ => {}
+            }
+            word_start = matches!(c, b' ' | b'\t' | b'\n' | b';' | b'&' | b'|');
+            position += 1;
+        }
+        if quote != 0 {
+            return Err(ParseError::UnterminatedQuote(quote));
+        }
+        self.emit(unit, end, Connector::Sequence)?;
+        self.check(first)?;
+        Ok(first)
+    }
+
+    fn emit(&mut self, start: usize, end: usize, connector: Connector) -> Result<(), ParseError> {
+        let (mut start, end): (usize, usize) = self.trim(start, end);
+        if start == end {
+            return match connector {
+                Connector::Sequence => Ok(()),
+                Connector::And => Err(ParseError::UnexpectedToken(Operator::And)),
+                Connector::Or => Err(ParseError::UnexpectedToken(Operator::Or)),
+            };
+        }
+        loop {
+            let word: usize = self.arena[start..end].iter().position(|&c| c == b' ' || c == b'\t').map_or(end, |length| start + length);
+            let keyword: Keyword = Keyword::parse(&self.arena[start..word]);
+            let rest: usize = self.trim(word, end).0;
+            match keyword {
+                Keyword::None => return self.push(start, end, Keyword::None, connector),
+                Keyword::For => return self.push(rest, end, Keyword::For, connector),
+                Keyword::Function => self.push(start, word - 2, Keyword::Function, Connector::Sequence)?,
+                _ if keyword.is_closer() => {
+                    if rest != end {
+                        return Err(ParseError::UnexpectedKeyword(keyword.name()));
+                    }
+                    return self.push(start, word, keyword, connector);
+                }
+                _ => self.push(start, word, keyword, Connector::Sequence)?,
+            }
+            if rest == end {
+                if connector != Connector::Sequence {
+                    return Err(ParseError::UnexpectedKeyword(keyword.name()));
+                }
+                return Ok(());
+            }
+            start = rest;
+        }
+    }
+
+    fn push(&mut self, start: usize, end: usize, keyword: Keyword, connector: Connector) -> Result<(), ParseError> {
+        if self.count == MAX_UNITS {
+            return Err(ParseError::TooManyStatements);
+        }
+        self.units[self.count] = Unit {
+            start: start as u16,
+            end: end as u16,
+            keyword,
+            connector,
+        };
+        self.count += 1;
+        Ok(())
+    }
+
+    fn trim(&self, mut start: usize, mut end: usize) -> (usize, usize) {
+        while start < end && self.arena[start].is_ascii_whitespace() {
+            start += 1;
+        }
+        while end > start && self.arena[end - 1].is_ascii_whitespace() {
+            end -= 1;
+        }
+        (start, end)
+    }
+
+    fn check(&self, first: usize) -> Result<(), ParseError> {
+        let mut stack: [Keyword; MAX_NESTING] = [Keyword::None; MAX_NESTING];
+        let mut depth: usize = 0;
+        for i in first..self.count {
+            let keyword: Keyword = self.units[i].keyword;
+            let top: Keyword = if depth > 0 { stack[depth - 1] } else { Keyword::None };
+            let valid: bool = match keyword {
+                Keyword::None => true,
+                Keyword::Function => i + 1 == self.count || self.units[i + 1].keyword == Keyword::Open,
+                Keyword::Then => top == Keyword::If,
+                Keyword::Elif | Keyword::Else => top == Keyword::Then,
+                Keyword::Fi => top == Keyword::Then || top == Keyword::Else,
+                Keyword::Do => top == Keyword::While || top == Keyword::For,
+                Keyword::Done => top == Keyword::Do,
+                Keyword::Close => top == Keyword::Open,
+                _ => true,
+            };
+            if !valid {
+                return Err(ParseError::UnexpectedKeyword(keyword.name()));
+            }
+            if keyword.is_opener() {
+                if depth == MAX_NESTING {
+                    return Err(ParseError::TooManyStatements);
+                }
+                stack[depth] = keyword;
+                depth += 1;
+            } else if keyword.is_closer() {
+                depth -= 1;
+            } else if keyword != Keyword::None && keyword != Keyword::Function {
+                stack[depth - 1] = if keyword == Keyword::Elif { Keyword::If } else { keyword };
+            }
+        }
+        let last: Option<&Unit> = self.units[first..self.count].last();
+        if depth > 0 || last.map_or(false, |unit| unit.keyword == Keyword::Function || unit.connector != Connector::Sequence) {
+            return Err(ParseError::UnexpectedEnd);
+        }
+        Ok(())
+    }
+
+    pub fn matching(&self, index: usize) -> usize {
+        let mut depth: usize = 0;
+        for (i, unit) in self.units.iter().enumerate().take(self.count).skip(index + 1) {
+            let keyword: Keyword = unit.keyword;
+            if keyword.is_opener() {
+                depth += 1;
+            } else if keyword.is_closer() {
+                if depth == 0 {
+                    return i;
+                }
+                depth -= 1;
+            }
+        }
+        self.count
+    }
+
+    pub fn next_clause(&self, from: usize, to: usize) -> usize {
+        let mut depth: usize = 0;
+        for (i, unit) in self.units.iter().enumerate().take(to).skip(from) {
+            let keyword: Keyword = unit.keyword;
+            if keyword.is_opener() {
+                depth += 1;
+            } else if keyword.is_closer() {
+                depth -= 1;
+            } else if depth == 0 && matches!(keyword, Keyword::Then | Keyword::Elif | Keyword::Else | Keyword::Do) {
+                return i;
+            }
+        }
+        to
+    }
+
+    pub fn define(&mut self, name: usize, open: usize, close: usize) -> bool {
+        let (start, end): (usize, usize) = (self.units[open].end as usize, self.units[close].start as usize);
+        let (name_start, name_end): (usize, usize) = (self.units[name].start as usize, self.units[name].end as usize);
+        if let Some(index) = self.find_function(&self.arena[name_start..name_end]) {
+            let function: Function = self.functions[index];
+            let size: usize = function.end - function.start;
+            self.pool.copy_within(function.end..self.pool_used, function.start);
+            self.pool_used -= size;
+            for other in self.functions.iter_mut() {
+                if other.name_length > 0 && other.start >= function.end {
+                    other.start -= size;
+                    other.end -= size;
+                }
+            }
+            self.functions[index] = NULL_FUNCTION;
+        }
+        let index: usize = match self.functions.iter().position(|function| function.name_length == 0) {
+            Some(index) => index,
+            None => return false,
+        };
+        if self.pool_used + end - start > POOL_SIZE {
+            return false;
+        }
+        self.pool[self.pool_used..self.pool_used + end - start].copy_from_slice(&self.arena[start..end]);
+        let function: &mut Function = &mut self.functions[index];
+        function.name[..name_end - name_start].copy_from_slice(&self.arena[name_start..name_end]);
+        function.name_length = name_end - name_start;
+        function.start = self.pool_used;
+        function.end = self.pool_used + end - start;
+        self.pool_used = function.end;
+        true
+    }
+
+    pub fn find_function(&self, name: &[u8]) -> Option<usize> {
+        self.functions.iter().position(|function| function.name_length > 0 && &function.name[..function.name_length] == name)
+    }
+
+    pub fn load_function(&mut self, index: usize) -> Option<(usize, usize)> {
+        let function: Function = self.functions[index];
+        let size: usize = function.end - function.start;
+        if self.used + size > ARENA_SIZE {
+            return None;
+        }
+        let start: usize = self.used;
+        self.arena[start..start + size].copy_from_slice(&self.pool[function.start..function.end]);
+        self.used += size;
+        Some((start, self.used))
+    }
+}
\ No newline at end of file
@@ -4,275 +4,436 @@
 // Author:        Jishen Lin
 // License:       MIT License
 
-use crate::filesystem::fat::{Entry, FatDriver, FAT};
-use crate::memory::paging::PAGING;
-use crate::memory::paging::TABLES;
-use crate::multitasking::task::TASK_MANAGER;
-use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_CYAN, COLOR_LIGHT_GREEN, COLOR_LIGHT_MAGENTA, COLOR_LIGHT_RED, COLOR_LIGHT_WHITE, PRINTER};
-use crate::timer::time::Time;
+use crate::drivers::console::{CONSOLES, MAX_CONSOLES};
+use crate::drivers::input::MODE_COOKED;
+use crate::filesystem::config::CONFIG;
+use crate::filesystem::descriptor::Descriptor;
+use crate::shell::builtins;
+use crate::shell::editor::{LineEditor, LINE_SIZE};
+use crate::shell::jobs::Jobs;
+use crate::shell::parser::{CommandLine, Invocation, Operator, ParseError, MAX_ARGS, MAX_REDIRECTIONS};
+use crate::shell::script::{Connector, Flow, Keyword, Script, Unit, MAX_FUNCTIONS, POOL_SIZE};
+use crate::shell::users::{self, User};
+use crate::shell::variables::{is_name, split_assignment, Parameters, Variables, NAME_SIZE};
+use crate::shell::{pipeline, redirect};
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
 
-const APP_TARGET: u32 = 0x00A00000;
-const APP_SIZE: u32 = 0x00010000;
-const APP_SIGNATURE: u32 = 0xB16B00B5;
-const HELP: &'static str = "Available commands:
-cal            - Shows current month's calendar
-cat <filename> - Shows content of a file
-clear          - Clears terminal screen
-color          - Shows VGA text mode color
-date           - Shows current datetime
-echo <text>    - Outputs text
-exit           - Exits current session
-help           - Shows available commands
-hostname       - Shows hostname
-kill <pid>     - Terminates specified process
-ls             - Lists root directory entries
-ps             - Lists running tasks
-pwd            - Shows current directory
-reboot         - Reboot system
-run <appname>  - Runs an application
-shutdown       - Shutdowns system
-ticks          - Shows current CPU ticks
-timestamp      - Shows current timestamp
-uname          - Shows system information
-whoami         - Shows current user";
+pub static mut SHELLS: [Shell; MAX_CONSOLES] = [NULL_SHELL; MAX_CONSOLES];
 
-pub static mut SHELL: Shell = Shell {
-    buffer: [0 as char; 256],
-    arg: [0 as char; 11],
-    cursor: 0,
-};
+const PROMPT_SIZE: usize = 160;
+const MAX_CALLS: usize = 4;
 
+#[derive(Copy, Clone)]
 pub struct Shell {
-    buffer: [char; 256],
-    arg: [char; 11],
-    cursor: usize,
+    pub line: CommandLine,
+    editor: LineEditor,
+    pub jobs: Jobs,
+    pub variables: Variables,
+    pub script: Script,
+    pub user: User,
+}
+
+static NULL_SHELL: Shell = Shell {
+    line: CommandLine::new(),
+    editor: LineEditor::new(),
+    jobs: Jobs::new(),
+    variables: Variables::new(),
+    script: Script::new(),
+    user: User::new(),
+};
+
+pub extern "C" fn shell(console: u32) {
+    unsafe {
+        let shell: &mut Shell = &mut SHELLS[console as usize];
+        shell.init();
+        loop {
+            shell.jobs.notify();
+            if let Some(first) = shell.read() {
+                shell.variables.status = shell.run(first, shell.script.count());
+            }
+        }
+    }
 }
 
 impl Shell {
     pub fn init(&mut self) {
-        self.buffer = ['\0'; 256];
-        self.cursor = 0;
         unsafe {
-            PRINTER.set_colors(COLOR_LIGHT_GREEN, COLOR_BLACK);
-            lib::print!("root@MinmusOS");
-            PRINTER.set_colors(COLOR_LIGHT_WHITE, COLOR_BLACK);
-            lib::print!(":");
-            PRINTER.set_colors(COLOR_LIGHT_CYAN, COLOR_BLACK);
-            lib::print!("/");
-            PRINTER.set_colors(COLOR_LIGHT_WHITE, COLOR_BLACK);
-            lib::print!("$ ");
-            PRINTER.reset_colors();
+            CONSOLES.current().set_mode(MODE_COOKED);
         }
-    }
-
-    pub fn add(&mut self, c: char) {
-        self.buffer[self.cursor] = c;
-        self.cursor += 1;
-        lib::print!("{}", c);
-    }
-
-    pub fn backspace(&mut self) {
-        if self.cursor > 0 {
-            unsafe {
-                PRINTER.delete();
+        users::login();
+        let mut startup: [u8; LINE_SIZE] = [0; LINE_SIZE];
+        let scripts: &[u8] = unsafe { CONFIG.get_startup() };
+        startup[..scripts.len()].copy_from_slice(scripts);
+        for script in startup[..scripts.len()].split(|&c| c == b' ' || c == b',') {
+            if !script.is_empty() {
+                let _ = self.source(&[script]);
             }
-            self.cursor -= 1;
-            self.buffer[self.cursor] = 0 as char;
         }
     }
 
-    pub fn enter(&mut self) {
+    fn prompt() {
+        let user: &User = users::current();
+        let template: &[u8] = unsafe { CONFIG.get_prompt() };
+        let mut buffer: [u8; PROMPT_SIZE] = [0; PROMPT_SIZE];
+        let mut length: usize = 0;
+        let mut push = |text: &[u8]| {
+            let count: usize = text.len().min(PROMPT_SIZE - length);
+            buffer[length..length + count].copy_from_slice(&text[..count]);
+            length += count;
+        };
+        let mut escaped: bool = false;
+        for &c in template.iter() {
+            if !escaped && c == b'\\' {
+                escaped = true;
+                continue;
+            }
+            match c {
+                b'u' if escaped => push(user.name().as_bytes()),
+                b'h' if escaped => push(unsafe { CONFIG.get_hostname() }.as_bytes()),
+                b'w' if escaped => push(b"/"),
+                b'$' if escaped => push(if user.uid == 0 { b"#" } else { b"$" }),
+                b'e' if escaped => push(b"\x1b"),
+                b'n' if escaped => push(b"\n"),
+                _ => push(&[c]),
+            }
+            escaped = false;
+        }
+        lib::io::write(lib::io::STDOUT, &buffer[..length]);
         unsafe {
-            PRINTER.new_line();
+            CONSOLES.printer().reset_colors();
         }
-        self.interpret();
-        self.init();
     }
 
-    #[allow(unused_unsafe)]
-    fn interpret(&mut self) {
-        match self.buffer {
-            _b if self.is_command("cal") => unsafe {
-                crate::shell::cal::cal();
-            },
-            b if self.is_command("cat") => unsafe {
-                self.cat(&b);
-            },
-            _b if self.is_command("clear") => unsafe {
-                PRINTER.clear();
-            },
-            _b if self.is_command("color") => unsafe {
-                crate::shell::color::color();
-            },
-            _b if self.is_command("date") => unsafe {
-                let time = Time::init();
-                lib::println!("{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC", time.get_year(), time.get_month(), time.get_day(), time.get_hour(), time.get_minute(), time.get_second());
-            },
-            b if self.is_command("echo") => unsafe {
-                crate::shell::echo::echo(&b);
-            },
-            _b if self.is_command("exit") => unsafe {
-                core::arch::asm!("mov dx, 0x604", "mov ax, 0x2000", "out dx, ax", options(nostack, nomem));
-            },
-            _b if self.is_command("help") => unsafe {
-                lib::println!("{}", HELP);
-            },
-            _b if self.is_command("hostname") => unsafe {
-                lib::println!("MinmusOS");
-            },
-            b if self.is_command("kill") => unsafe {
-                crate::shell::kill::kill(&b);
-            },
-            _b if self.is_command("ls") => unsafe {
-                FAT.acquire().list_entries();
-                FAT.free();
-            },
-            _b if self.is_command("ps") => unsafe {
-                TASK_MANAGER.list_tasks();
-            },
-            _b if self.is_command("pwd") => unsafe {
-                lib::println!("/");
-            },
-            _b if self.is_command("reboot") => unsafe {
-                core::arch::asm!("mov al, 0xFE", "out 0x64, al", options(nostack, nomem));
-            },
-            b if self.is_command("run") => unsafe {
-                self.run(&b);
-            },
-            _b if self.is_command("shutdown") => unsafe {
-                core::arch::asm!("mov dx, 0x604", "mov ax, 0x2000", "out dx, ax", options(nostack, nomem));
-            },
-            _b if self.is_command("ticks") => unsafe {
-                lib::println!("{}", Time::init().get_ticks());
-            },
-            _b if self.is_command("timestamp") => unsafe {
-                lib::println!("{}", Time::init().get_timestamp());
-            },
-            _b if self.is_command("uname") => unsafe {
-                lib::println!("MinmusOS v1.0 IA-32 x86");
-            },
-            _b if self.is_command("whoami") => unsafe {
-                lib::println!("root");
-            },
-            b if b[0] == '\0' || b[0] == '\n' => {}
-            _ => {
-                unsafe {
-                    PRINTER.set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+    fn continuation() {
+        lib::print!("> ");
+    }
+
+    fn read(&mut self) -> Option<usize> {
+        self.script.reset();
+        let mut prompt: fn() = Self::prompt;
+        loop {
+            let line: &[u8] = self.editor.read_line(prompt)?;
+            if !self.script.append(line) {
+                error(ParseError::TooLong);
+                return None;
+            }
+            match self.script.split(0, self.script.used()) {
+                Ok(first) => return Some(first),
+                Err(ParseError::UnexpectedEnd) => {
+                    self.script.release(self.script.used(), 0);
+                    prompt = Self::continuation;
                 }
-                lib::println!("Command not found!");
-                unsafe {
-                    PRINTER.reset_colors();
+                Err(other) => {
+                    error(other);
+                    return None;
                 }
             }
         }
     }
 
-    unsafe fn cat(&mut self, b: &[char]) {
-        let start: usize = b.iter().skip(4).position(|&c| c != ' ' && c != '\0').unwrap_or(b.len());
-        if start + 4 >= b.len() {
-            PRINTER.set_colors(COLOR_LIGHT_MAGENTA, COLOR_BLACK);
-            lib::println!("Usage: cat <filename>");
-            PRINTER.reset_colors();
-            return;
+    pub fn source(&mut self, args: &[&[u8]]) -> Result<i32, i32> {
+        let (used, count): (usize, usize) = (self.script.used(), self.script.count());
+        let (start, end): (usize, usize) = self.script.load(args[0])?;
+        let parameters: Parameters = self.variables.parameters;
+        self.variables.parameters.set(args);
+        let status: i32 = self.run_text(start, end);
+        self.variables.parameters = parameters;
+        self.script.release(used, count);
+        Ok(status)
+    }
+
+    fn run_text(&mut self, start: usize, end: usize) -> i32 {
+        if self.script.calls == MAX_CALLS {
+            unsafe {
+                CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+            }
+            lib::eprintln!("Maximum nesting depth exceeded (max {})!", MAX_CALLS);
+            unsafe {
+                CONSOLES.printer().reset_colors();
+            }
+            return 1;
         }
-        let end: usize = b.iter().skip(start + 4).position(|&c| c == ' ' || c == '\0').map_or(b.len(), |p| p + start + 4);
-        if end <= start + 4 {
-            PRINTER.set_colors(COLOR_LIGHT_MAGENTA, COLOR_BLACK);
-            lib::println!("Usage: cat <filename>");
-            PRINTER.reset_colors();
-            return;
+        let first: usize = match self.script.split(start, end) {
+            Ok(first) => first,
+            Err(other) => {
+                error(other);
+                return 2;
+            }
+        };
+        let loops: usize = self.script.loops;
+        self.script.calls += 1;
+        self.script.loops = 0;
+        let status: i32 = self.run(first, self.script.count());
+        self.script.calls -= 1;
+        self.script.loops = loops;
+        if self.script.flow == Flow::Return {
+            self.script.flow = Flow::Normal;
         }
-        let mut j: usize = 0;
-        for i in start + 4..end {
-            self.arg[j] = b[i];
-            j += 1;
+        status
+    }
+
+    fn run(&mut self, first: usize, last: usize) -> i32 {
+        let mut status: i32 = 0;
+        let mut skip: bool = false;
+        let mut i: usize = first;
+        while i < last && self.script.flow == Flow::Normal {
+            let unit: Unit = self.script.unit(i);
+            let end: usize = match unit.keyword {
+                Keyword::Function => self.script.matching(i + 1),
+                keyword if keyword != Keyword::None => self.script.matching(i),
+                _ => i,
+            };
+            if !skip {
+                status = match unit.keyword {
+                    Keyword::If => self.run_if(i, end),
+                    Keyword::While => self.run_while(i, end),
+                    Keyword::For => self.run_for(i, end),
+                    Keyword::Open => self.run(i + 1, end),
+                    Keyword::Function => self.define(i, end),
+                    _ => self.run_command(&unit),
+                };
+                self.variables.status = status;
+            }
+            skip = match self.script.unit(end).connector {
+                Connector::And => status != 0,
+                Connector::Or => status == 0,
+                Connector::Sequence => false,
+            };
+            i = end + 1;
         }
-        for i in j..self.arg.len() {
-            self.arg[i] = '\0';
+        status
+    }
+
+    fn run_if(&mut self, first: usize, last: usize) -> i32 {
+        let mut condition: usize = first;
+        loop {
+            let then: usize = self.script.next_clause(condition + 1, last);
+            let next: usize = self.script.next_clause(then + 1, last);
+            let status: i32 = self.run(condition + 1, then);
+            if self.script.flow != Flow::Normal {
+                return status;
+            }
+            if status == 0 {
+                return self.run(then + 1, next);
+            }
+            match self.script.unit(next).keyword {
+                Keyword::Elif => condition = next,
+                Keyword::Else => return self.run(next + 1, last),
+                _ => return 0,
+            }
         }
-        let fat: &FatDriver = FAT.acquire();
-        let entry: &Entry = fat.search_file(&self.arg);
-        if entry.name[0] != 0 {
-            fat.read_file_to_buffer(entry);
-            for c in fat.buffer {
-                if c != 0 {
-                    lib::print!("{}", c as char);
-                }
+    }
+
+    fn run_while(&mut self, first: usize, last: usize) -> i32 {
+        let body: usize = self.script.next_clause(first + 1, last);
+        let interrupts: u32 = unsafe { CONSOLES.current().interrupts };
+        let mut status: i32 = 0;
+        self.script.loops += 1;
+        while self.run(first + 1, body) == 0 && self.script.flow == Flow::Normal {
+            status = self.run(body + 1, last);
+            if !self.next_iteration(interrupts) {
+                break;
             }
-            lib::println!();
-        } else {
-            PRINTER.set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
-            lib::println!("File not found!");
-            PRINTER.reset_colors();
         }
-        FAT.free();
+        self.script.loops -= 1;
+        status
     }
 
-    unsafe fn run(&mut self, b: &[char]) {
-        let start: usize = b.iter().skip(4).position(|&c| c != ' ' && c != '\0').unwrap_or(b.len());
-        if start + 4 >= b.len() {
-            unsafe {
-                PRINTER.set_colors(COLOR_LIGHT_MAGENTA, COLOR_BLACK);
+    fn run_for(&mut self, first: usize, last: usize) -> i32 {
+        let unit: Unit = self.script.unit(first);
+        let body: usize = self.script.next_clause(first + 1, last);
+        let mut words: [u8; LINE_SIZE] = [0; LINE_SIZE];
+        let mut ends: [usize; MAX_ARGS] = [0; MAX_ARGS];
+        let mut name: [u8; NAME_SIZE] = [0; NAME_SIZE];
+        let mut count: usize = 0;
+        if let Err(other) = self.parse(&unit) {
+            error(other);
+            return 2;
+        }
+        let mut argv: [&[u8]; MAX_ARGS] = [&[]; MAX_ARGS];
+        let argc: usize = self.line.argv(0, &mut argv);
+        if argc == 0 || !is_name(argv[0]) || (argc > 1 && argv[1] != b"in") {
+            error(ParseError::UnexpectedKeyword("for"));
+            return 2;
+        }
+        let name_length: usize = argv[0].len();
+        name[..name_length].copy_from_slice(argv[0]);
+        let mut used: usize = 0;
+        let mut add = |word: &[u8]| {
+            words[used..used + word.len()].copy_from_slice(word);
+            used += word.len();
+            ends[count] = used;
+            count += 1;
+        };
+        if argc == 1 {
+            for i in 1..=self.variables.parameters.count().min(MAX_ARGS) {
+                add(self.variables.parameters.get(i));
             }
-            lib::println!("Usage: run <appname>");
-            unsafe {
-                PRINTER.reset_colors();
+        } else {
+            for &arg in argv[2..argc].iter() {
+                add(arg);
             }
-            return;
         }
-        let end: usize = b.iter().skip(start + 4).position(|&c| c == ' ' || c == '\0').map_or(b.len(), |p| p + start + 4);
-        if end <= start + 4 {
-            unsafe {
-                PRINTER.set_colors(COLOR_LIGHT_MAGENTA, COLOR_BLACK);
+        let interrupts: u32 = unsafe { CONSOLES.current().interrupts };
+        let mut status: i32 = 0;
+        self.script.loops += 1;
+        for i in 0..count {
+            let start: usize = if i == 0 { 0 } else { ends[i - 1] };
+            self.variables.set(&name[..name_length], &words[start..ends[i]]);
+            status = self.run(body + 1, last);
+            if !self.next_iteration(interrupts) {
+                break;
             }
-            lib::println!("Usage: run <appname>");
-            unsafe {
-                PRINTER.reset_colors();
+        }
+        self.script.loops -= 1;
+        status
+    }
+
+    fn next_iteration(&mut self, interrupts: u32) -> bool {
+        if unsafe { CONSOLES.current().interrupts } != interrupts {
+            return false;
+        }
+        match self.script.flow {
+            Flow::Break => {
+                self.script.flow = Flow::Normal;
+                false
+            }
+            Flow::Continue => {
+                self.script.flow = Flow::Normal;
+                true
             }
-            return;
+            Flow::Return => false,
+            Flow::Normal => true,
         }
-        let mut j: usize = 0;
-        for i in start + 4..end {
-            self.arg[j] = b[i];
-            j += 1;
+    }
+
+    fn define(&mut self, first: usize, last: usize) -> i32 {
+        if self.script.define(first, first + 1, last) {
+            return 0;
         }
-        for i in j..self.arg.len() {
-            self.arg[i] = '\0';
+        unsafe {
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+        }
+        lib::eprintln!("Cannot define function (max {} functions, {} bytes)!", MAX_FUNCTIONS, POOL_SIZE);
+        unsafe {
+            CONSOLES.printer().reset_colors();
+        }
+        1
+    }
+
+    fn parse(&mut self, unit: &Unit) -> Result<(), ParseError> {
+        let text: &[u8] = self.script.expand(unit, &self.variables)?;
+        self.line.parse(text)
+    }
+
+    fn run_command(&mut self, unit: &Unit) -> i32 {
+        if let Err(other) = self.parse(unit) {
+            error(other);
+            return 2;
         }
-        let fat: &FatDriver = FAT.acquire();
-        let entry: &Entry = fat.search_file(&self.arg);
-        if entry.name[0] != 0 {
-            let target: u32 = APP_TARGET + (TASK_MANAGER.get_free_slot() as u32 * APP_SIZE);
-            TABLES[8].set(target);
-            PAGING.set_table(8, &TABLES[8]);
-            fat.read_file_to_target(&entry, target as *mut u32);
-            if *(target as *mut u32) == APP_SIGNATURE {
-                TASK_MANAGER.add_task(target + 4);
-            } else {
+        if self.line.count() == 0 {
+            return 0;
+        }
+        let mut argv: [&[u8]; MAX_ARGS] = [&[]; MAX_ARGS];
+        let argc: usize = self.line.argv(0, &mut argv);
+        let mut assignments: usize = 0;
+        while assignments < argc {
+            let (name, value): (&[u8], &[u8]) = match split_assignment(argv[assignments]) {
+                Some(assignment) => assignment,
+                None => break,
+            };
+            if !self.variables.set(name, value) {
                 unsafe {
-                    PRINTER.set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+                    CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
                 }
-                lib::println!("This file is not a valid executable!");
+                lib::eprintln!("Cannot set {}!", core::str::from_utf8(name).unwrap_or("?"));
                 unsafe {
-                    PRINTER.reset_colors();
+                    CONSOLES.printer().reset_colors();
                 }
+                return 1;
             }
-        } else {
-            PRINTER.set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
-            lib::println!("Application not found!");
-            PRINTER.reset_colors();
+            assignments += 1;
         }
-        FAT.free();
+        self.line.shift(0, assignments);
+        self.interpret()
     }
 
-    fn is_command(&self, command: &str) -> bool {
-        let command_len: usize = command.len();
-        for (i, command_char) in command.chars().enumerate() {
-            let buffer_char: char = self.buffer[i].to_ascii_lowercase();
-            if buffer_char == '\0' || buffer_char != command_char.to_ascii_lowercase() {
-                return false;
+    fn interpret(&mut self) -> i32 {
+        let last: usize = self.line.count() - 1;
+        let background: bool = self.line.get(last).next == Operator::Background;
+        if background || last > 0 || self.is_job(0) {
+            return self.launch(0, last, background);
+        }
+        let mut argv: [&[u8]; MAX_ARGS] = [&[]; MAX_ARGS];
+        let argc: usize = self.line.argv(0, &mut argv);
+        if argc > 0 {
+            if let Some(function) = self.script.find_function(argv[0]) {
+                return self.call(function);
             }
         }
-        self.buffer[command_len].to_ascii_lowercase() == ' ' || self.buffer[command_len] == '\0'
+        Self::execute(&self.line.invocation(0))
+    }
+
+    fn call(&mut self, function: usize) -> i32 {
+        let mut saved: [Option<Descriptor>; MAX_REDIRECTIONS] = [None; MAX_REDIRECTIONS];
+        if !unsafe { redirect::apply(&self.line.invocation(0), &mut saved) } {
+            return 1;
+        }
+        let parameters: Parameters = self.variables.parameters;
+        let mut argv: [&[u8]; MAX_ARGS] = [&[]; MAX_ARGS];
+        let argc: usize = self.line.argv(0, &mut argv);
+        self.variables.parameters.set(&argv[..argc]);
+        let (used, count): (usize, usize) = (self.script.used(), self.script.count());
+        let status: i32 = match self.script.load_function(function) {
+            Some((start, end)) => self.run_text(start, end),
+            None => {
+                error(ParseError::TooLong);
+                1
+            }
+        };
+        self.script.release(used, count);
+        self.variables.parameters = parameters;
+        unsafe {
+            redirect::restore(&mut saved);
+        }
+        status
+    }
+
+    fn is_job(&self, index: usize) -> bool {
+        let mut argv: [&[u8]; MAX_ARGS] = [&[]; MAX_ARGS];
+        let argc: usize = self.line.argv(index, &mut argv);
+        argc > 0 && builtins::find(argv[0]).map_or(false, |builtin| builtin.job)
+    }
+
+    fn launch(&mut self, first: usize, last: usize, background: bool) -> i32 {
+        let (group, pid): (i8, i8) = unsafe { pipeline::pipeline(&self.line, first, last) };
+        if group < 0 {
+            return 1;
+        }
+        self.jobs.start(group, pid, background, &self.line, first, last)
+    }
+
+    pub fn execute(invocation: &Invocation) -> i32 {
+        let mut argv: [&[u8]; MAX_ARGS] = [&[]; MAX_ARGS];
+        let argc: usize = invocation.argv(&mut argv);
+        let mut saved: [Option<Descriptor>; MAX_REDIRECTIONS] = [None; MAX_REDIRECTIONS];
+        unsafe {
+            if !redirect::apply(invocation, &mut saved) {
+                return 1;
+            }
+            let status: i32 = if argc > 0 { builtins::execute(&argv[..argc]) } else { 0 };
+            redirect::restore(&mut saved);
+            status
+        }
+    }
+}
+
+fn error(error: ParseError) {
+    unsafe {
+        CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+    }
+    error.print();
+    unsafe {
+        CONSOLES.printer().reset_colors();
     }
 }
\ No newline at end of file
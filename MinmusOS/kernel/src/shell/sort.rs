@@ -0,0 +1,131 @@
+// Project Name:  MinmusOS
+// File Name:     sort.rs
+// File Function: The implementation of the command sort
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::multitasking::mutex::Mutex;
+use crate::shell::builtins::{find, usage};
+use crate::shell::files::{each, LineReader};
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+use core::cmp::Ordering;
+
+const SORT_SIZE: usize = 2048;
+const MAX_LINES: usize = 128;
+
+struct Options {
+    numeric: bool,
+    reverse: bool,
+    unique: bool,
+    fold: bool,
+}
+
+struct Buffer {
+    text: [u8; SORT_SIZE],
+    lines: [(u16, u16); MAX_LINES],
+}
+
+static mut BUFFER: Mutex<Buffer> = Mutex::new(Buffer {
+    text: [0; SORT_SIZE],
+    lines: [(0, 0); MAX_LINES],
+});
+
+pub fn sort(args: &[&[u8]]) -> i32 {
+    let mut options: Options = Options {
+        numeric: false,
+        reverse: false,
+        unique: false,
+        fold: false,
+    };
+    let mut start: usize = 1;
+    while start < args.len() && args[start].len() > 1 && args[start][0] == b'-' {
+        for &c in args[start][1..].iter() {
+            match c {
+                b'n' => options.numeric = true,
+                b'r' => options.reverse = true,
+                b'u' => options.unique = true,
+                b'f' => options.fold = true,
+                _ => return usage(find(b"sort").unwrap()),
+            }
+        }
+        start += 1;
+    }
+    unsafe {
+        let buffer: &mut Buffer = BUFFER.acquire_mut();
+        let status: i32 = sort_lines(&args[start..], &options, buffer);
+        BUFFER.free();
+        status
+    }
+}
+
+fn sort_lines(paths: &[&[u8]], options: &Options, buffer: &mut Buffer) -> i32 {
+    let Buffer { text, lines } = buffer;
+    let mut used: usize = 0;
+    let mut count: usize = 0;
+    let mut overflow: bool = false;
+    let status: i32 = each(b"sort", paths, &mut |source: i32, _path: &[u8]| -> i32 {
+        let mut reader: LineReader = LineReader::new(source);
+        while let Some(length) = reader.next(&mut text[used..]) {
+            if count == MAX_LINES || used + length == SORT_SIZE {
+                overflow = true;
+                return 1;
+            }
+            lines[count] = (used as u16, (used + length) as u16);
+            used += length;
+            count += 1;
+        }
+        reader.failed as i32
+    });
+    if overflow {
+        unsafe {
+            CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+        }
+        lib::eprintln!("sort: Input too large (max {} bytes, {} lines)!", SORT_SIZE, MAX_LINES);
+        unsafe {
+            CONSOLES.printer().reset_colors();
+        }
+        return 1;
+    }
+    if status != 0 {
+        return status;
+    }
+    let line = |&(start, end): &(u16, u16)| -> &[u8] { &text[start as usize..end as usize] };
+    lib::sort::insertion_sort_by(&mut lines[..count], |a: &(u16, u16), b: &(u16, u16)| compare(line(a), line(b), options) == Ordering::Greater);
+    for i in 0..count {
+        if options.unique && i > 0 && compare(line(&lines[i - 1]), line(&lines[i]), options) == Ordering::Equal {
+            continue;
+        }
+        lib::io::write(lib::io::STDOUT, line(&lines[i]));
+        lib::io::write(lib::io::STDOUT, b"\n");
+    }
+    0
+}
+
+fn compare(a: &[u8], b: &[u8], options: &Options) -> Ordering {
+    let mut ordering: Ordering = Ordering::Equal;
+    if options.numeric {
+        ordering = number(a).cmp(&number(b));
+    }
+    if ordering == Ordering::Equal && !(options.numeric && options.unique) {
+        ordering = if options.fold {
+            a.iter().map(|c| c.to_ascii_lowercase()).cmp(b.iter().map(|c| c.to_ascii_lowercase()))
+        } else {
+            a.cmp(b)
+        };
+    }
+    if options.reverse { ordering.reverse() } else { ordering }
+}
+
+fn number(text: &[u8]) -> i64 {
+    let text: &[u8] = match text.iter().position(|c| !c.is_ascii_whitespace()) {
+        Some(start) => &text[start..],
+        None => return 0,
+    };
+    let (negative, digits): (bool, &[u8]) = if text.first() == Some(&b'-') { (true, &text[1..]) } else { (false, text) };
+    let mut value: i64 = 0;
+    for &c in digits.iter().take_while(|c| c.is_ascii_digit()) {
+        value = value.saturating_mul(10).saturating_add((c - b'0') as i64);
+    }
+    if negative { -value } else { value }
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+// Project Name:  MinmusOS
+// File Name:     stat.rs
+// File Function: The implementation of the command stat
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::filesystem::fat::{display_name, short_name, ATTRIBUTE_DIRECTORY};
+use crate::filesystem::file::{Stat, FILES};
+use crate::shell::files::{error, print_attributes, print_mode, print_modified};
+use core::str;
+
+pub fn stat(args: &[&[u8]]) -> i32 {
+    let mut status: i32 = 0;
+    for &path in args[1..].iter() {
+        let mut info: Stat = Stat::new();
+        let result: i32 = unsafe { FILES.stat(path, &mut info) };
+        if result < 0 {
+            error(path, -result);
+            status = 1;
+            continue;
+        }
+        let mut name: [u8; 12] = [0; 12];
+        let length: usize = match short_name(path) {
+            Ok(short) => display_name(&short, &mut name),
+            Err(_) => 0,
+        };
+        lib::println!("  File: {}", str::from_utf8(&name[..length]).unwrap_or("?"));
+        lib::println!("  Size: {:<10} Cluster: {}", info.size, info.cluster);
+        lib::print!("  Type: {:<10} Attributes: ", if info.attributes & ATTRIBUTE_DIRECTORY as u32 != 0 { "directory" } else { "file" });
+        print_attributes(info.attributes);
+        lib::println!(" (0x{:02x})", info.attributes);
+        lib::print!("Access: ({:04o}/", info.mode);
+        print_mode(info.mode);
+        lib::println!(")  Uid: {:<5} Gid: {}", info.uid, info.gid);
+        lib::print!("Modify: ");
+        print_modified(info.modified_date, info.modified_time);
+        lib::println!();
+    }
+    status
+}
\ No newline at end of file
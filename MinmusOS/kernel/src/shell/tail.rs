@@ -0,0 +1,102 @@
+// Project Name:  MinmusOS
+// File Name:     tail.rs
+// File Function: The implementation of the command tail
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::filesystem::file::{FILES, SEEK_SET};
+use crate::shell::builtins::{find, usage};
+use crate::shell::files::{each, header, read, STANDARD_INPUT};
+use crate::shell::head::lines;
+
+const WINDOW_SIZE: usize = 1024;
+
+pub fn tail(args: &[&[u8]]) -> i32 {
+    let (count, paths): (usize, &[&[u8]]) = match lines(args) {
+        Some(parsed) => parsed,
+        None => return usage(find(b"tail").unwrap()),
+    };
+    let headers: bool = paths.len() > 1;
+    let mut first: bool = true;
+    each(b"tail", paths, &mut |source: i32, path: &[u8]| -> i32 {
+        if headers {
+            header(path, &mut first);
+        }
+        if source == STANDARD_INPUT { tail_stream(count) } else { tail_file(source, count) }
+    })
+}
+
+fn tail_file(source: i32, count: usize) -> i32 {
+    let mut buffer: [u8; 128] = [0; 128];
+    let mut total: usize = 0;
+    let mut last: u8 = b'\n';
+    loop {
+        let read: i32 = read(source, &mut buffer);
+        if read < 0 {
+            return 1;
+        }
+        if read == 0 {
+            break;
+        }
+        total += buffer[..read as usize].iter().filter(|&&c| c == b'\n').count();
+        last = buffer[read as usize - 1];
+    }
+    if last != b'\n' {
+        total += 1;
+    }
+    let mut skip: usize = total.saturating_sub(count);
+    unsafe {
+        FILES.seek(source as usize, 0, SEEK_SET);
+    }
+    loop {
+        let read: i32 = read(source, &mut buffer);
+        if read <= 0 {
+            break;
+        }
+        let mut start: usize = 0;
+        while skip > 0 && start < read as usize {
+            if buffer[start] == b'\n' {
+                skip -= 1;
+            }
+            start += 1;
+        }
+        lib::io::write(lib::io::STDOUT, &buffer[start..read as usize]);
+    }
+    0
+}
+
+fn tail_stream(count: usize) -> i32 {
+    let mut window: [u8; WINDOW_SIZE] = [0; WINDOW_SIZE];
+    let mut length: usize = 0;
+    loop {
+        if length == WINDOW_SIZE {
+            window.copy_within(WINDOW_SIZE / 2.., 0);
+            length = WINDOW_SIZE / 2;
+        }
+        let read: i32 = read(STANDARD_INPUT, &mut window[length..]);
+        if read < 0 {
+            return 1;
+        }
+        if read == 0 {
+            break;
+        }
+        length += read as usize;
+    }
+    if count == 0 {
+        return 0;
+    }
+    let end: usize = if length > 0 && window[length - 1] == b'\n' { length - 1 } else { length };
+    let mut start: usize = 0;
+    let mut seen: usize = 0;
+    for i in (0..end).rev() {
+        if window[i] == b'\n' {
+            seen += 1;
+            if seen == count {
+                start = i + 1;
+                break;
+            }
+        }
+    }
+    lib::io::write(lib::io::STDOUT, &window[start..length]);
+    0
+}
\ No newline at end of file
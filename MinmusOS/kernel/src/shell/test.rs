@@ -0,0 +1,77 @@
+// Project Name:  MinmusOS
+// File Name:     test.rs
+// File Function: The implementation of the command test
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::filesystem::fat::ATTRIBUTE_DIRECTORY;
+use crate::filesystem::file::{Stat, FILES};
+use crate::shell::builtins::{find, usage};
+use core::str;
+
+pub fn test(args: &[&[u8]]) -> i32 {
+    let mut expression: &[&[u8]] = &args[1..];
+    if args[0] == b"[" {
+        match expression.split_last() {
+            Some((&b"]", rest)) => expression = rest,
+            _ => return usage(find(b"[").unwrap()),
+        }
+    }
+    match evaluate(expression) {
+        Some(true) => 0,
+        Some(false) => 1,
+        None => usage(find(args[0]).unwrap()),
+    }
+}
+
+fn evaluate(expression: &[&[u8]]) -> Option<bool> {
+    match expression.len() {
+        0 => Some(false),
+        1 => Some(!expression[0].is_empty()),
+        2 => match expression[0] {
+            b"!" => evaluate(&expression[1..]).map(|result| !result),
+            b"-n" => Some(!expression[1].is_empty()),
+            b"-z" => Some(expression[1].is_empty()),
+            b"-e" | b"-f" | b"-d" => {
+                let mut stat: Stat = Stat::new();
+                if unsafe { FILES.stat(expression[1], &mut stat) } < 0 {
+                    return Some(false);
+                }
+                let directory: bool = stat.attributes & ATTRIBUTE_DIRECTORY as u32 != 0;
+                Some(match expression[0] {
+                    b"-f" => !directory,
+                    b"-d" => directory,
+                    _ => true,
+                })
+            }
+            _ => None,
+        },
+        3 => {
+            let (left, operator, right): (&[u8], &[u8], &[u8]) = (expression[0], expression[1], expression[2]);
+            match operator {
+                b"=" | b"==" => Some(left == right),
+                b"!=" => Some(left != right),
+                _ if expression[0] == b"!" => evaluate(&expression[1..]).map(|result| !result),
+                _ => {
+                    let left: i32 = number(left)?;
+                    let right: i32 = number(right)?;
+                    match operator {
+                        b"-eq" => Some(left == right),
+                        b"-ne" => Some(left != right),
+                        b"-lt" => Some(left < right),
+                        b"-le" => Some(left <= right),
+                        b"-gt" => Some(left > right),
+                        b"-ge" => Some(left >= right),
+                        _ => None,
+                    }
+                }
+            }
+        }
+        4 if expression[0] == b"!" => evaluate(&expression[1..]).map(|result| !result),
+        _ => None,
+    }
+}
+
+fn number(text: &[u8]) -> Option<i32> {
+    str::from_utf8(text).ok()?.parse::<i32>().ok()
+}
\ No newline at end of file
@@ -0,0 +1,20 @@
+// Project Name:  MinmusOS
+// File Name:     touch.rs
+// File Function: The implementation of the command touch
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::filesystem::file::FILES;
+use crate::shell::files::error;
+
+pub fn touch(args: &[&[u8]]) -> i32 {
+    let mut status: i32 = 0;
+    for &path in args[1..].iter() {
+        let result: i32 = unsafe { FILES.touch(path) };
+        if result < 0 {
+            error(path, -result);
+            status = 1;
+        }
+    }
+    status
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+// Project Name:  MinmusOS
+// File Name:     uniq.rs
+// File Function: The implementation of the command uniq
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::shell::builtins::{find, usage};
+use crate::shell::editor::LINE_SIZE;
+use crate::shell::files::{each, LineReader};
+
+struct Options {
+    counted: bool,
+    repeated: bool,
+    single: bool,
+    fold: bool,
+}
+
+pub fn uniq(args: &[&[u8]]) -> i32 {
+    let mut options: Options = Options {
+        counted: false,
+        repeated: false,
+        single: false,
+        fold: false,
+    };
+    let mut start: usize = 1;
+    while start < args.len() && args[start].len() > 1 && args[start][0] == b'-' {
+        for &c in args[start][1..].iter() {
+            match c {
+                b'c' => options.counted = true,
+                b'd' => options.repeated = true,
+                b'u' => options.single = true,
+                b'i' => options.fold = true,
+                _ => return usage(find(b"uniq").unwrap()),
+            }
+        }
+        start += 1;
+    }
+    let mut lines: [[u8; LINE_SIZE]; 2] = [[0; LINE_SIZE]; 2];
+    let mut lengths: [usize; 2] = [0; 2];
+    let mut current: usize = 0;
+    let mut repeats: u32 = 0;
+    let status: i32 = each(b"uniq", &args[start..], &mut |source: i32, _path: &[u8]| -> i32 {
+        let mut reader: LineReader = LineReader::new(source);
+        while let Some(length) = reader.next(&mut lines[current]) {
+            lengths[current] = length;
+            let previous: usize = 1 - current;
+            if repeats > 0 {
+                let (a, b): (&[u8], &[u8]) = (&lines[previous][..lengths[previous]], &lines[current][..length]);
+                if if options.fold { a.eq_ignore_ascii_case(b) } else { a == b } {
+                    repeats += 1;
+                    continue;
+                }
+                report(&lines[previous][..lengths[previous]], repeats, &options);
+            }
+            repeats = 1;
+            current = previous;
+        }
+        reader.failed as i32
+    });
+    if repeats > 0 {
+        report(&lines[1 - current][..lengths[1 - current]], repeats, &options);
+    }
+    status
+}
+
+fn report(line: &[u8], repeats: u32, options: &Options) {
+    if (options.repeated && repeats == 1) || (options.single && repeats > 1) {
+        return;
+    }
+    if options.counted {
+        lib::print!("{:>7} ", repeats);
+    }
+    lib::io::write(lib::io::STDOUT, line);
+    lib::io::write(lib::io::STDOUT, b"\n");
+}
\ No newline at end of file
@@ -0,0 +1,464 @@
+// Project Name:  MinmusOS
+// File Name:     users.rs
+// File Function: User accounts, login and the commands su, useradd and whoami
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::drivers::console::CONSOLES;
+use crate::drivers::input::MODE_RAW;
+use crate::filesystem::fat::MAX_OWNER;
+use crate::filesystem::config::CONFIG;
+use crate::filesystem::file::{FILES, O_APPEND, O_CREAT, O_RDONLY, O_TRUNC, O_WRONLY};
+use crate::multitasking::task::TASK_MANAGER;
+use crate::shell::editor::LINE_SIZE;
+use crate::shell::files::{error, LineReader};
+use crate::shell::shell::SHELLS;
+use crate::shell::variables::is_name;
+use crate::syscalls::print::{COLOR_BLACK, COLOR_LIGHT_RED};
+use core::fmt::{self, Write};
+use core::str;
+use lib::errno::ENOSPC;
+
+pub const PASSWD_FILE: &[u8] = b"passwd";
+pub const SHADOW_FILE: &[u8] = b"shadow";
+pub const USER_SIZE: usize = 16;
+const PASSWORD_SIZE: usize = 32;
+const HASH_ROUNDS: usize = 1024;
+const SHADOW_MODE: u16 = 0o600;
+const SHADOW_SIZE: usize = 512;
+const DEFAULT_SHADOW: &[u8] = b"root:E62DB1E0BA898325:1\nguest::0\n";
+const FIRST_UID: u16 = 100;
+const MAX_UID: u16 = MAX_OWNER;
+const USERS_GID: u16 = 100;
+
+#[derive(Copy, Clone)]
+pub struct User {
+    name: [u8; USER_SIZE],
+    length: usize,
+    pub uid: u16,
+    pub gid: u16,
+}
+
+#[derive(Copy, Clone)]
+struct Secret {
+    hash: Option<u64>,
+    expired: bool,
+}
+
+impl User {
+    pub const fn new() -> Self {
+        User {
+            name: [b'r', b'o', b'o', b't', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            length: 4,
+            uid: 0,
+            gid: 0,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        str::from_utf8(&self.name[..self.length]).unwrap_or("?")
+    }
+
+    fn parse(line: &[u8]) -> Option<User> {
+        let mut fields = line.split(|&c| c == b':');
+        let name: &[u8] = fields.next()?;
+        fields.next()?;
+        let uid: u16 = str::from_utf8(fields.next()?).ok()?.trim().parse::<u16>().ok()?;
+        let gid: u16 = str::from_utf8(fields.next()?).ok()?.trim().parse::<u16>().ok()?;
+        if name.is_empty() || name.len() > USER_SIZE {
+            return None;
+        }
+        let mut user: User = User {
+            name: [0; USER_SIZE],
+            length: name.len(),
+            uid,
+            gid,
+        };
+        user.name[..name.len()].copy_from_slice(name);
+        Some(user)
+    }
+}
+
+impl Secret {
+    fn check(&self, name: &[u8], password: &[u8]) -> bool {
+        match self.hash {
+            Some(value) => hash(name, password) == value,
+            None => true,
+        }
+    }
+
+    fn parse(line: &[u8], name: &[u8]) -> Option<Secret> {
+        let mut fields = line.split(|&c| c == b':');
+        if fields.next()? != name {
+            return None;
+        }
+        let hash: &[u8] = fields.next()?;
+        let hash: Option<u64> = if hash.is_empty() { None } else { Some(u64::from_str_radix(str::from_utf8(hash).ok()?, 16).ok()?) };
+        Some(Secret { hash, expired: fields.next() == Some(b"1") })
+    }
+}
+
+struct Entry {
+    buffer: [u8; 64],
+    length: usize,
+}
+
+impl Write for Entry {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let bytes: &[u8] = s.as_bytes();
+        if self.length + bytes.len() > self.buffer.len() {
+            return Err(fmt::Error);
+        }
+        self.buffer[self.length..self.length + bytes.len()].copy_from_slice(bytes);
+        self.length += bytes.len();
+        Ok(())
+    }
+}
+
+pub fn hash(name: &[u8], password: &[u8]) -> u64 {
+    let mut value: u64 = 0xCBF29CE484222325;
+    for _ in 0..HASH_ROUNDS {
+        for &c in name.iter().chain(b":".iter()).chain(password.iter()) {
+            value ^= c as u64;
+            value = value.wrapping_mul(0x100000001B3);
+        }
+    }
+    value
+}
+
+pub fn scan(predicate: &mut dyn FnMut(&User) -> bool) -> Option<User> {
+    let id: i32 = unsafe { FILES.open(PASSWD_FILE, O_RDONLY) };
+    if id < 0 {
+        return None;
+    }
+    let mut reader: LineReader = LineReader::new(id);
+    let mut line: [u8; LINE_SIZE] = [0; LINE_SIZE];
+    let mut found: Option<User> = None;
+    while let Some(length) = reader.next(&mut line) {
+        if let Some(user) = User::parse(&line[..length]) {
+            if predicate(&user) {
+                found = Some(user);
+                break;
+            }
+        }
+    }
+    unsafe {
+        FILES.release(id as usize);
+    }
+    found
+}
+
+pub fn by_name(name: &[u8]) -> Option<User> {
+    scan(&mut |user: &User| user.name().as_bytes() == name)
+}
+
+pub fn by_uid(uid: u16) -> Option<User> {
+    scan(&mut |user: &User| user.uid == uid)
+}
+
+pub fn current() -> &'static User {
+    unsafe { &SHELLS[CONSOLES.get_current_id()].user }
+}
+
+fn switch(user: User) {
+    unsafe {
+        TASK_MANAGER.set_credentials(user.uid, user.gid);
+        SHELLS[CONSOLES.get_current_id()].user = user;
+    }
+}
+
+fn open_shadow(flags: u32) -> i32 {
+    unsafe {
+        let (uid, gid): (u16, u16) = TASK_MANAGER.get_credentials();
+        TASK_MANAGER.set_credentials(0, 0);
+        let id: i32 = FILES.open_mode(SHADOW_FILE, flags, SHADOW_MODE);
+        TASK_MANAGER.set_credentials(uid, gid);
+        id
+    }
+}
+
+fn init_shadow() {
+    let mut id: i32 = open_shadow(O_RDONLY);
+    if id < 0 {
+        id = open_shadow(O_WRONLY | O_CREAT | O_TRUNC);
+        if id >= 0 {
+            unsafe {
+                FILES.write(id as usize, DEFAULT_SHADOW);
+            }
+        }
+    }
+    if id >= 0 {
+        unsafe {
+            FILES.release(id as usize);
+        }
+    }
+}
+
+fn secret(name: &[u8]) -> Option<Secret> {
+    let id: i32 = open_shadow(O_RDONLY);
+    if id < 0 {
+        return None;
+    }
+    let mut reader: LineReader = LineReader::new(id);
+    let mut line: [u8; LINE_SIZE] = [0; LINE_SIZE];
+    let mut found: Option<Secret> = None;
+    while let Some(length) = reader.next(&mut line) {
+        found = Secret::parse(&line[..length], name);
+        if found.is_some() {
+            break;
+        }
+    }
+    unsafe {
+        FILES.release(id as usize);
+    }
+    found
+}
+
+fn set_secret(name: &[u8], hash: Option<u64>) -> i32 {
+    let mut buffer: [u8; SHADOW_SIZE] = [0; SHADOW_SIZE];
+    let mut length: usize = 0;
+    let id: i32 = open_shadow(O_RDONLY | O_CREAT);
+    if id < 0 {
+        return id;
+    }
+    while length < SHADOW_SIZE {
+        let read: i32 = unsafe { FILES.read(id as usize, &mut buffer[length..]) };
+        if read <= 0 {
+            break;
+        }
+        length += read as usize;
+    }
+    unsafe {
+        FILES.release(id as usize);
+    }
+    let mut entry: Entry = Entry { buffer: [0; 64], length: 0 };
+    let _ = write!(entry, "{}:", str::from_utf8(name).unwrap_or("?"));
+    if let Some(hash) = hash {
+        let _ = write!(entry, "{:016X}", hash);
+    }
+    let _ = writeln!(entry, ":0");
+    if length == SHADOW_SIZE {
+        return -ENOSPC;
+    }
+    let mut kept: usize = 0;
+    let mut start: usize = 0;
+    while start < length {
+        let end: usize = buffer[start..length].iter().position(|&c| c == b'\n').map_or(length, |position| start + position);
+        if end > start && buffer[start..end].split(|&c| c == b':').next() != Some(name) {
+            buffer.copy_within(start..end, kept);
+            kept += end - start;
+            buffer[kept] = b'\n';
+            kept += 1;
+        }
+        start = end + 1;
+    }
+    if kept + entry.length > SHADOW_SIZE {
+        return -ENOSPC;
+    }
+    let id: i32 = open_shadow(O_WRONLY | O_TRUNC);
+    if id < 0 {
+        return id;
+    }
+    let mut written: i32 = unsafe { FILES.write(id as usize, &buffer[..kept]) };
+    if written >= 0 {
+        written = unsafe { FILES.write(id as usize, &entry.buffer[..entry.length]) };
+    }
+    unsafe {
+        FILES.release(id as usize);
+    }
+    if written < 0 { written } else { 0 }
+}
+
+fn failure(message: &str) -> i32 {
+    unsafe {
+        CONSOLES.printer().set_colors(COLOR_LIGHT_RED, COLOR_BLACK);
+    }
+    lib::eprintln!("{}", message);
+    unsafe {
+        CONSOLES.printer().reset_colors();
+    }
+    1
+}
+
+fn read_password(prompt: &str, buffer: &mut [u8; PASSWORD_SIZE]) -> Option<usize> {
+    lib::print!("{}", prompt);
+    let mode: i32 = unsafe { CONSOLES.current().set_mode(MODE_RAW) };
+    let mut key: [u8; 1] = [0];
+    let mut length: usize = 0;
+    let accepted: bool = loop {
+        if unsafe { CONSOLES.current().read(&mut key) } <= 0 {
+            break false;
+        }
+        match key[0] {
+            b'\n' | b'\r' => break true,
+            0x03 => break false,
+            0x08 | 0x7F => length = length.saturating_sub(1),
+            c if c >= b' ' && length < PASSWORD_SIZE => {
+                buffer[length] = c;
+                length += 1;
+            }
+            _ => {}
+        }
+    };
+    unsafe {
+        CONSOLES.current().set_mode(mode as u32);
+    }
+    lib::println!();
+    if accepted { Some(length) } else { None }
+}
+
+fn new_password(name: &[u8]) -> Option<Option<u64>> {
+    let mut password: [u8; PASSWORD_SIZE] = [0; PASSWORD_SIZE];
+    let mut again: [u8; PASSWORD_SIZE] = [0; PASSWORD_SIZE];
+    let length: usize = read_password("New password: ", &mut password)?;
+    match read_password("Retype new password: ", &mut again) {
+        Some(retyped) if password[..length] == again[..retyped] => {}
+        Some(_) => {
+            failure("Passwords do not match!");
+            return None;
+        }
+        None => return None,
+    }
+    Some(if length > 0 { Some(hash(name, &password[..length])) } else { None })
+}
+
+pub fn login() {
+    if scan(&mut |_user: &User| true).is_none() {
+        lib::println!("No user accounts found, logged in as root.");
+        switch(User::new());
+        return;
+    }
+    init_shadow();
+    loop {
+        let mut buffer: [u8; LINE_SIZE] = [0; LINE_SIZE];
+        lib::print!("{} login: ", unsafe { CONFIG.get_hostname() });
+        let name: &[u8] = match lib::io::read_line(&mut buffer) {
+            Some(name) => name.trim().as_bytes(),
+            None => {
+                lib::println!();
+                continue;
+            }
+        };
+        if name.is_empty() {
+            continue;
+        }
+        let user: Option<User> = by_name(name);
+        let secret: Option<Secret> = user.and_then(|_| secret(name));
+        let mut password: [u8; PASSWORD_SIZE] = [0; PASSWORD_SIZE];
+        let mut length: usize = 0;
+        if secret.is_none_or(|secret| secret.hash.is_some()) {
+            length = match read_password("Password: ", &mut password) {
+                Some(length) => length,
+                None => continue,
+            };
+        }
+        match (user, secret) {
+            (Some(user), Some(secret)) if secret.check(name, &password[..length]) => {
+                if secret.expired && !expire(name) {
+                    continue;
+                }
+                switch(user);
+                return;
+            }
+            _ => {
+                failure("Login incorrect!");
+            }
+        }
+    }
+}
+
+fn expire(name: &[u8]) -> bool {
+    lib::println!("You are required to change your password.");
+    let hash: u64 = match new_password(name) {
+        Some(Some(hash)) => hash,
+        Some(None) => {
+            failure("The password cannot be empty!");
+            return false;
+        }
+        None => return false,
+    };
+    let result: i32 = set_secret(name, Some(hash));
+    if result < 0 {
+        error(SHADOW_FILE, -result);
+        return false;
+    }
+    true
+}
+
+pub fn su(args: &[&[u8]]) -> i32 {
+    let name: &[u8] = args.get(1).copied().unwrap_or(b"root");
+    let user: User = match by_name(name) {
+        Some(user) => user,
+        None => return failure("su: Unknown user!"),
+    };
+    if current().uid != 0 {
+        let secret: Secret = match secret(name) {
+            Some(secret) => secret,
+            None => return failure("su: Authentication failure!"),
+        };
+        if secret.hash.is_some() {
+            let mut password: [u8; PASSWORD_SIZE] = [0; PASSWORD_SIZE];
+            let length: usize = match read_password("Password: ", &mut password) {
+                Some(length) => length,
+                None => return 1,
+            };
+            if !secret.check(name, &password[..length]) {
+                return failure("su: Authentication failure!");
+            }
+        }
+    }
+    switch(user);
+    0
+}
+
+pub fn useradd(args: &[&[u8]]) -> i32 {
+    let name: &[u8] = args[1];
+    if current().uid != 0 {
+        return failure("useradd: Permission denied!");
+    }
+    if !is_name(name) {
+        return failure("useradd: Invalid user name!");
+    }
+    if by_name(name).is_some() {
+        return failure("useradd: User already exists!");
+    }
+    let mut uid: u16 = FIRST_UID;
+    scan(&mut |user: &User| {
+        uid = uid.max(user.uid.saturating_add(1));
+        false
+    });
+    if uid > MAX_UID {
+        return failure("useradd: Too many users!");
+    }
+    let hash: Option<u64> = match new_password(name) {
+        Some(hash) => hash,
+        None => return 1,
+    };
+    let result: i32 = set_secret(name, hash);
+    if result < 0 {
+        error(SHADOW_FILE, -result);
+        return 1;
+    }
+    let mut entry: Entry = Entry { buffer: [0; 64], length: 0 };
+    let _ = writeln!(entry, "{}:x:{}:{}", str::from_utf8(name).unwrap_or("?"), uid, USERS_GID);
+    let id: i32 = unsafe { FILES.open(PASSWD_FILE, O_WRONLY | O_CREAT | O_APPEND) };
+    if id < 0 {
+        error(PASSWD_FILE, -id);
+        return 1;
+    }
+    let written: i32 = unsafe { FILES.write(id as usize, &entry.buffer[..entry.length]) };
+    unsafe {
+        FILES.release(id as usize);
+    }
+    if written < 0 {
+        error(PASSWD_FILE, -written);
+        return 1;
+    }
+    lib::println!("User {} added with uid {}.", str::from_utf8(name).unwrap_or("?"), uid);
+    0
+}
+
+pub fn whoami(_args: &[&[u8]]) -> i32 {
+    lib::println!("{}", current().name());
+    0
+}
\ No newline at end of file
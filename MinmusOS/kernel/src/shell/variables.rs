@@ -0,0 +1,347 @@
+// Project Name:  MinmusOS
+// File Name:     variables.rs
+// File Function: Shell variables and parameter expansion
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::multitasking::task::TASK_MANAGER;
+use crate::shell::arithmetic;
+use crate::shell::parser::ParseError;
+use core::fmt;
+use core::fmt::Write;
+use core::str;
+
+pub const NAME_SIZE: usize = 16;
+const VALUE_SIZE: usize = 64;
+const MAX_VARIABLES: usize = 16;
+const PARAMETERS_SIZE: usize = 128;
+const MAX_PARAMETERS: usize = 10;
+
+#[derive(Copy, Clone)]
+struct Variable {
+    name: [u8; NAME_SIZE],
+    name_length: usize,
+    value: [u8; VALUE_SIZE],
+    value_length: usize,
+    exported: bool,
+}
+
+static NULL_VARIABLE: Variable = Variable {
+    name: [0; NAME_SIZE],
+    name_length: 0,
+    value: [0; VALUE_SIZE],
+    value_length: 0,
+    exported: false,
+};
+
+impl Variable {
+    fn name(&self) -> &[u8] {
+        &self.name[..self.name_length]
+    }
+
+    fn value(&self) -> &[u8] {
+        &self.value[..self.value_length]
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Parameters {
+    storage: [u8; PARAMETERS_SIZE],
+    ends: [usize; MAX_PARAMETERS],
+    count: usize,
+}
+
+impl Parameters {
+    pub const fn new() -> Self {
+        Parameters {
+            storage: [0; PARAMETERS_SIZE],
+            ends: [0; MAX_PARAMETERS],
+            count: 0,
+        }
+    }
+
+    pub fn set(&mut self, argv: &[&[u8]]) {
+        let mut used: usize = 0;
+        self.ends = [0; MAX_PARAMETERS];
+        self.count = 0;
+        for (i, arg) in argv.iter().take(MAX_PARAMETERS).enumerate() {
+            let length: usize = arg.len().min(PARAMETERS_SIZE - used);
+            self.storage[used..used + length].copy_from_slice(&arg[..length]);
+            used += length;
+            self.ends[i] = used;
+            self.count = i;
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn get(&self, index: usize) -> &[u8] {
+        if index > self.count {
+            return &[];
+        }
+        let start: usize = if index == 0 { 0 } else { self.ends[index - 1] };
+        &self.storage[start..self.ends[index]]
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Variables {
+    variables: [Variable; MAX_VARIABLES],
+    pub parameters: Parameters,
+    pub status: i32,
+}
+
+struct Output<'a> {
+    buffer: &'a mut [u8],
+    length: usize,
+    quoted: bool,
+    overflow: bool,
+}
+
+impl Output<'_> {
+    fn push(&mut self, c: u8) {
+        if self.length == self.buffer.len() {
+            self.overflow = true;
+            return;
+        }
+        self.buffer[self.length] = c;
+        self.length += 1;
+    }
+
+    fn push_value(&mut self, value: &[u8]) {
+        for &c in value.iter() {
+            let special: bool = if self.quoted {
+                matches!(c, b'"' | b'\\')
+            } else {
+                matches!(c, b'\\' | b'\'' | b'"' | b'&' | b'|' | b';' | b'<' | b'>')
+            };
+            if special {
+                self.push(b'\\');
+            }
+            self.push(c);
+        }
+    }
+}
+
+impl Write for Output<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_value(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl Variables {
+    pub const fn new() -> Self {
+        Variables {
+            variables: [NULL_VARIABLE; MAX_VARIABLES],
+            parameters: Parameters::new(),
+            status: 0,
+        }
+    }
+
+    pub fn get(&self, name: &[u8]) -> Option<&[u8]> {
+        self.find(name).map(|index| self.variables[index].value())
+    }
+
+    pub fn set(&mut self, name: &[u8], value: &[u8]) -> bool {
+        if !is_name(name) || value.len() > VALUE_SIZE {
+            return false;
+        }
+        let index: usize = match self.find(name) {
+            Some(index) => index,
+            None => match self.variables.iter().position(|variable| variable.name_length == 0) {
+                Some(index) => {
+                    self.variables[index] = NULL_VARIABLE;
+                    self.variables[index].name[..name.len()].copy_from_slice(name);
+                    self.variables[index].name_length = name.len();
+                    index
+                }
+                None => return false,
+            },
+        };
+        let variable: &mut Variable = &mut self.variables[index];
+        variable.value[..value.len()].copy_from_slice(value);
+        variable.value_length = value.len();
+        true
+    }
+
+    pub fn unset(&mut self, name: &[u8]) {
+        if let Some(index) = self.find(name) {
+            self.variables[index] = NULL_VARIABLE;
+        }
+    }
+
+    pub fn export(&mut self, name: &[u8]) -> bool {
+        if self.find(name).is_none() && !self.set(name, &[]) {
+            return false;
+        }
+        if let Some(index) = self.find(name) {
+            self.variables[index].exported = true;
+        }
+        true
+    }
+
+    pub fn list(&self, exported: bool) {
+        for variable in self.variables.iter() {
+            if variable.name_length == 0 || (exported && !variable.exported) {
+                continue;
+            }
+            let name: &str = str::from_utf8(variable.name()).unwrap_or("?");
+            let value: &str = str::from_utf8(variable.value()).unwrap_or("?");
+            if exported {
+                lib::println!("export {}={}", name, value);
+            } else {
+                lib::println!("{}={}", name, value);
+            }
+        }
+    }
+
+    pub fn number(&self, name: &[u8]) -> i32 {
+        let value: &[u8] = match name.first() {
+            Some(c) if c.is_ascii_digit() => self.parameters.get((c - b'0') as usize),
+            _ => self.get(name).unwrap_or(&[]),
+        };
+        str::from_utf8(value).ok().and_then(|value| value.trim().parse::<i32>().ok()).unwrap_or(0)
+    }
+
+    pub fn expand(&self, text: &[u8], buffer: &mut [u8]) -> Result<usize, ParseError> {
+        let mut output: Output = Output {
+            buffer,
+            length: 0,
+            quoted: false,
+            overflow: false,
+        };
+        let mut position: usize = 0;
+        while position < text.len() {
+            let c: u8 = text[position];
+            let next: u8 = text.get(position + 1).copied().unwrap_or(0);
+            match c {
+                b'\'' if !output.quoted => {
+                    let end: usize = text[position + 1..].iter().position(|&c| c == b'\'').map_or(text.len(), |end| position + end + 2);
+                    for &c in text[position..end].iter() {
+                        output.push(c);
+                    }
+                    position = end;
+                    continue;
+                }
+                b'"' => output.quoted = !output.quoted,
+                b'\\' if output.quoted && next == b'$' => {
+                    output.push(b'$');
+                    position += 2;
+                    continue;
+                }
+                b'\\' if next != 0 => {
+                    output.push(c);
+                    output.push(next);
+                    position += 2;
+                    continue;
+                }
+                b'$' => {
+                    position = self.substitute(text, position + 1, &mut output)?;
+                    continue;
+                }
+                _ => {}
+            }
+            output.push(c);
+            position += 1;
+        }
+        if output.overflow {
+            return Err(ParseError::TooLong);
+        }
+        Ok(output.length)
+    }
+
+    fn substitute(&self, text: &[u8], start: usize, output: &mut Output) -> Result<usize, ParseError> {
+        let c: u8 = text.get(start).copied().unwrap_or(0);
+        if c == b'(' && text.get(start + 1) == Some(&b'(') {
+            let mut depth: usize = 0;
+            let mut end: usize = start + 2;
+            while end + 1 < text.len() && !(depth == 0 && text[end] == b')' && text[end + 1] == b')') {
+                match text[end] {
+                    b'(' => depth += 1,
+                    b')' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                end += 1;
+            }
+            if end + 1 >= text.len() {
+                return Err(ParseError::BadExpression);
+            }
+            let value: i32 = arithmetic::evaluate(&text[start + 2..end], self).ok_or(ParseError::BadExpression)?;
+            let _ = write!(output, "{}", value);
+            return Ok(end + 2);
+        }
+        if c == b'{' {
+            let end: usize = match text[start..].iter().position(|&c| c == b'}') {
+                Some(end) => start + end,
+                None => return Err(ParseError::BadSubstitution),
+            };
+            if !self.lookup(&text[start + 1..end], output) {
+                return Err(ParseError::BadSubstitution);
+            }
+            return Ok(end + 1);
+        }
+        let mut end: usize = start;
+        if c.is_ascii_digit() || matches!(c, b'?' | b'#' | b'@' | b'*' | b'$') {
+            end += 1;
+        } else {
+            while end < text.len() && (text[end].is_ascii_alphanumeric() || text[end] == b'_') {
+                end += 1;
+            }
+        }
+        if end == start {
+            output.push(b'$');
+            return Ok(start);
+        }
+        self.lookup(&text[start..end], output);
+        Ok(end)
+    }
+
+    fn lookup(&self, name: &[u8], output: &mut Output) -> bool {
+        match name {
+            b"?" => {
+                let _ = write!(output, "{}", self.status);
+            }
+            b"#" => {
+                let _ = write!(output, "{}", self.parameters.count());
+            }
+            b"$" => {
+                let _ = write!(output, "{}", unsafe { TASK_MANAGER.get_current_slot() });
+            }
+            b"@" | b"*" => {
+                for i in 1..=self.parameters.count() {
+                    if i > 1 {
+                        output.push(b' ');
+                    }
+                    output.push_value(self.parameters.get(i));
+                }
+            }
+            [c] if c.is_ascii_digit() => output.push_value(self.parameters.get((c - b'0') as usize)),
+            _ if is_name(name) => output.push_value(self.get(name).unwrap_or(&[])),
+            _ => return false,
+        }
+        true
+    }
+
+    fn find(&self, name: &[u8]) -> Option<usize> {
+        self.variables.iter().position(|variable| variable.name_length > 0 && variable.name() == name)
+    }
+}
+
+pub fn is_name(name: &[u8]) -> bool {
+    match name.first() {
+        Some(c) if c.is_ascii_alphabetic() || *c == b'_' => {}
+        _ => return false,
+    }
+    name.len() <= NAME_SIZE && name.iter().all(|&c| c.is_ascii_alphanumeric() || c == b'_')
+}
+
+pub fn split_assignment(word: &[u8]) -> Option<(&[u8], &[u8])> {
+    let equals: usize = word.iter().position(|&c| c == b'=')?;
+    if !is_name(&word[..equals]) {
+        return None;
+    }
+    Some((&word[..equals], &word[equals + 1..]))
+}
\ No newline at end of file
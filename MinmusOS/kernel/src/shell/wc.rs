@@ -0,0 +1,76 @@
+// Project Name:  MinmusOS
+// File Name:     wc.rs
+// File Function: The implementation of the command wc
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::shell::builtins::{find, usage};
+use crate::shell::files::{each, read};
+use core::str;
+
+const LINES: u8 = 0x01;
+const WORDS: u8 = 0x02;
+const BYTES: u8 = 0x04;
+
+pub fn wc(args: &[&[u8]]) -> i32 {
+    let mut selected: u8 = 0;
+    let mut start: usize = 1;
+    while start < args.len() && args[start].len() > 1 && args[start][0] == b'-' {
+        for &c in args[start][1..].iter() {
+            selected |= match c {
+                b'l' => LINES,
+                b'w' => WORDS,
+                b'c' => BYTES,
+                _ => return usage(find(b"wc").unwrap()),
+            };
+        }
+        start += 1;
+    }
+    if selected == 0 {
+        selected = LINES | WORDS | BYTES;
+    }
+    let mut total: [u32; 3] = [0; 3];
+    let status: i32 = each(b"wc", &args[start..], &mut |source: i32, path: &[u8]| -> i32 {
+        let mut buffer: [u8; 128] = [0; 128];
+        let mut counts: [u32; 3] = [0; 3];
+        let mut word: bool = false;
+        loop {
+            let read: i32 = read(source, &mut buffer);
+            if read < 0 {
+                return 1;
+            }
+            if read == 0 {
+                break;
+            }
+            for &c in buffer[..read as usize].iter() {
+                let space: bool = c.is_ascii_whitespace();
+                if c == b'\n' {
+                    counts[0] += 1;
+                }
+                if !space && !word {
+                    counts[1] += 1;
+                }
+                counts[2] += 1;
+                word = !space;
+            }
+        }
+        for i in 0..3 {
+            total[i] += counts[i];
+        }
+        report(&counts, selected, path);
+        0
+    });
+    if args.len() - start > 1 {
+        report(&total, selected, b"total");
+    }
+    status
+}
+
+fn report(counts: &[u32; 3], selected: u8, name: &[u8]) {
+    for (i, count) in counts.iter().enumerate() {
+        if selected & (1 << i) != 0 {
+            lib::print!("{:>7} ", count);
+        }
+    }
+    lib::println!("{}", str::from_utf8(name).unwrap_or("?"));
+}
\ No newline at end of file
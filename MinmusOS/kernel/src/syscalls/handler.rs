@@ -4,24 +4,62 @@
 // Author:        Jishen Lin
 // License:       MIT License
 
+use crate::drivers::console::CONSOLES;
+use crate::drivers::event::MouseEvent;
 use crate::drivers::pic::PICS;
-use crate::multitasking::task::TASK_MANAGER;
-use crate::syscalls::print;
+use crate::drivers::tty::TtyAttributes;
+use crate::filesystem::descriptor::{Descriptor, STDOUT};
+use crate::filesystem::fat::MAX_OWNER;
+use crate::filesystem::file::{DirectoryEntry, Stat, FILES};
+use crate::ipc::message::{Message, MAILBOXES};
+use crate::ipc::pipe::PIPES;
+use crate::ipc::signal::{SIGNALS, SIG_IGN};
+use crate::memory::shared::{SharedMemory, SHARED};
+use crate::multitasking::task::{exit, is_kernel_address, TASK_MANAGER};
 use core::arch::asm;
 use core::slice;
-use core::str;
+use lib::errno::{EAGAIN, EFAULT, EINVAL, EMFILE, ENFILE, ENOSYS, EPERM};
+use lib::keyboard::KeyEvent;
+use lib::syscall::{SYS_CALL, SYS_CHMOD, SYS_CHOWN, SYS_CLOSE, SYS_EXIT, SYS_GETGID, SYS_GETUID, SYS_INPUT_MODE, SYS_KEY_EVENT, SYS_KILL, SYS_LSEEK, SYS_MOUSE_EVENT, SYS_OPEN, SYS_PIPE, SYS_PRINT, SYS_READ, SYS_READDIR, SYS_RECEIVE, SYS_REPLY, SYS_SEND, SYS_SETUID, SYS_SHM_MAP, SYS_SHM_UNMAP, SYS_SIGNAL, SYS_STAT, SYS_THREAD_JOIN, SYS_THREAD_SPAWN, SYS_TRY_RECEIVE, SYS_TRY_SEND, SYS_TTY_GET, SYS_TTY_SET, SYS_UNLINK, SYS_WRITE};
 
 pub const SYSCALL_INT: u8 = 0x80;
+const APP_START: u32 = 0x02000000;
+const APP_SIZE: u32 = 0x00010000;
+
+#[repr(C)]
+pub struct SyscallFrame {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+    edx: u32,
+    esi: u32,
+    edi: u32,
+    ebp: u32,
+    eip: u32,
+}
 
 #[naked]
 pub extern "C" fn syscall() {
     unsafe {
         asm!(
-        "push eax",
-        "push ebx",
+        "push dword ptr [esp]",
+        "push ebp",
+        "push edi",
+        "push esi",
+        "push edx",
         "push ecx",
+        "push ebx",
+        "push eax",
+        "push esp",
         "call syscall_handler",
-        "add esp, 12",
+        "add esp, 8",
+        "pop ebx",
+        "pop ecx",
+        "pop edx",
+        "pop esi",
+        "pop edi",
+        "pop ebp",
+        "add esp, 4",
         "iretd",
         options(noreturn),
         );
@@ -29,21 +67,215 @@ pub extern "C" fn syscall() {
 }
 
 #[no_mangle]
-pub extern "C" fn syscall_handler(ecx: u32, ebx: u32, eax: u32) {
+pub extern "C" fn syscall_handler(frame: &SyscallFrame) -> u32 {
+    let result: i32;
     unsafe {
-        match eax {
-            0 => {
-                let s = {
-                    let slice = slice::from_raw_parts(ebx as *const u8, ecx as usize);
-                    str::from_utf8(slice)
-                };
-                print::PRINTER.prints(s.unwrap());
+        result = dispatch(frame);
+        PICS.end_interrupt(SYSCALL_INT);
+    }
+    result as u32
+}
+
+unsafe fn dispatch(frame: &SyscallFrame) -> i32 {
+    match frame.eax {
+        SYS_PRINT => match frame.buffer(frame.ebx, frame.ecx) {
+            Some(buffer) => TASK_MANAGER.get_file(STDOUT).write(buffer),
+            None => -EFAULT,
+        },
+        SYS_EXIT => {
+            exit();
+            0
+        }
+        SYS_READ => match frame.buffer_mut(frame.ecx, frame.edx) {
+            Some(buffer) => TASK_MANAGER.get_file(frame.ebx as usize).read(buffer),
+            None => -EFAULT,
+        },
+        SYS_WRITE => match frame.buffer(frame.ecx, frame.edx) {
+            Some(buffer) => TASK_MANAGER.get_file(frame.ebx as usize).write(buffer),
+            None => -EFAULT,
+        },
+        SYS_PIPE => match frame.object::<[u32; 2]>(frame.ebx) {
+            Some(fds) => pipe(fds),
+            None => -EFAULT,
+        },
+        SYS_CLOSE => TASK_MANAGER.close_file(frame.ebx as usize),
+        SYS_SEND | SYS_TRY_SEND => match frame.object::<Message>(frame.ecx) {
+            Some(message) => MAILBOXES.send(frame.ebx as usize, message, frame.eax == SYS_SEND),
+            None => -EFAULT,
+        },
+        SYS_RECEIVE | SYS_TRY_RECEIVE => match frame.object::<Message>(frame.ebx) {
+            Some(message) => MAILBOXES.receive(message, frame.eax == SYS_RECEIVE),
+            None => -EFAULT,
+        },
+        SYS_CALL => match frame.object::<Message>(frame.ecx) {
+            Some(message) => MAILBOXES.call(frame.ebx as usize, message),
+            None => -EFAULT,
+        },
+        SYS_REPLY => match frame.object::<Message>(frame.ecx) {
+            Some(message) => MAILBOXES.reply(frame.ebx as usize, message),
+            None => -EFAULT,
+        },
+        SYS_SIGNAL => {
+            if frame.ecx > SIG_IGN && !frame.is_valid(frame.ecx, 1) {
+                return -EFAULT;
             }
-            1 => {
-                TASK_MANAGER.remove_current_task();
+            SIGNALS.set_handler(TASK_MANAGER.get_current_slot() as usize, frame.ebx, frame.ecx)
+        }
+        SYS_KILL => SIGNALS.kill(frame.ebx as usize, frame.ecx),
+        SYS_SHM_MAP => match frame.object::<SharedMemory>(frame.ebx) {
+            Some(request) => SHARED.map(TASK_MANAGER.get_current_process() as usize, request),
+            None => -EFAULT,
+        },
+        SYS_SHM_UNMAP => SHARED.unmap(TASK_MANAGER.get_current_process() as usize, frame.ebx),
+        SYS_THREAD_SPAWN => {
+            if !frame.is_valid(frame.ebx, 1) {
+                return -EFAULT;
             }
-            _ => {}
+            let id: i8 = TASK_MANAGER.add_thread(frame.ebx, frame.ecx);
+            if id < 0 { -EAGAIN } else { id as i32 }
         }
-        PICS.end_interrupt(SYSCALL_INT);
+        SYS_THREAD_JOIN => TASK_MANAGER.join_thread(frame.ebx as usize),
+        SYS_OPEN => match frame.buffer(frame.ebx, frame.ecx) {
+            Some(path) => open(path, frame.edx),
+            None => -EFAULT,
+        },
+        SYS_LSEEK => TASK_MANAGER.get_file(frame.ebx as usize).seek(frame.ecx as i32, frame.edx),
+        SYS_STAT => match (frame.buffer(frame.ebx, frame.ecx), frame.object::<Stat>(frame.edx)) {
+            (Some(path), Some(stat)) => FILES.stat(path, stat),
+            _ => -EFAULT,
+        },
+        SYS_READDIR => match frame.object::<DirectoryEntry>(frame.ecx) {
+            Some(entry) => FILES.read_directory(frame.ebx as usize, entry),
+            None => -EFAULT,
+        },
+        SYS_UNLINK => match frame.buffer(frame.ebx, frame.ecx) {
+            Some(path) => FILES.unlink(path),
+            None => -EFAULT,
+        },
+        SYS_INPUT_MODE => CONSOLES.current().set_mode(frame.ebx),
+        SYS_TTY_GET => match frame.object::<TtyAttributes>(frame.ebx) {
+            Some(attributes) => {
+                *attributes = CONSOLES.current().tty.get_attributes();
+                0
+            }
+            None => -EFAULT,
+        },
+        SYS_TTY_SET => match frame.object::<TtyAttributes>(frame.ebx) {
+            Some(attributes) => {
+                CONSOLES.current().tty.set_attributes(attributes);
+                0
+            }
+            None => -EFAULT,
+        },
+        SYS_KEY_EVENT => match frame.object::<KeyEvent>(frame.ebx) {
+            Some(event) => CONSOLES.current().events.read(event, frame.ecx != 0),
+            None => -EFAULT,
+        },
+        SYS_MOUSE_EVENT => match frame.object::<MouseEvent>(frame.ebx) {
+            Some(event) => CONSOLES.current().mouse.read(event, frame.ecx != 0),
+            None => -EFAULT,
+        },
+        SYS_CHMOD => match frame.buffer(frame.ebx, frame.ecx) {
+            Some(path) => FILES.chmod(path, frame.edx as u16),
+            None => -EFAULT,
+        },
+        SYS_CHOWN => match frame.buffer(frame.ebx, frame.ecx) {
+            Some(_) if frame.edx > MAX_OWNER as u32 || frame.esi > MAX_OWNER as u32 => -EINVAL,
+            Some(path) => FILES.chown(path, frame.edx as u16, frame.esi as u16),
+            None => -EFAULT,
+        },
+        SYS_GETUID => TASK_MANAGER.get_credentials().0 as i32,
+        SYS_GETGID => TASK_MANAGER.get_credentials().1 as i32,
+        SYS_SETUID => {
+            if TASK_MANAGER.get_credentials().0 != 0 {
+                return -EPERM;
+            }
+            if frame.ebx > MAX_OWNER as u32 || frame.ecx > MAX_OWNER as u32 {
+                return -EINVAL;
+            }
+            TASK_MANAGER.set_credentials(frame.ebx as u16, frame.ecx as u16);
+            0
+        }
+        _ => -ENOSYS,
+    }
+}
+
+impl SyscallFrame {
+    fn is_valid(&self, address: u32, length: u32) -> bool {
+        if is_kernel_address(self.eip) {
+            return true;
+        }
+        let end: u32 = match address.checked_add(length) {
+            Some(end) => end,
+            None => return false,
+        };
+        if address >= APP_START && end <= APP_START + APP_SIZE {
+            return true;
+        }
+        unsafe {
+            let process: i8 = TASK_MANAGER.get_current_process();
+            TASK_MANAGER.is_process_stack(address, end) || (process >= 0 && SHARED.is_mapped(process as usize, address, end))
+        }
+    }
+
+    unsafe fn buffer(&self, address: u32, length: u32) -> Option<&'static [u8]> {
+        if length == 0 {
+            return Some(&[]);
+        }
+        if address == 0 || !self.is_valid(address, length) {
+            return None;
+        }
+        Some(slice::from_raw_parts(address as *const u8, length as usize))
+    }
+
+    unsafe fn buffer_mut(&self, address: u32, length: u32) -> Option<&'static mut [u8]> {
+        if length == 0 {
+            return Some(&mut []);
+        }
+        if address == 0 || !self.is_valid(address, length) {
+            return None;
+        }
+        Some(slice::from_raw_parts_mut(address as *mut u8, length as usize))
+    }
+
+    unsafe fn object<T>(&self, address: u32) -> Option<&'static mut T> {
+        if address == 0 || address as usize % align_of::<T>() != 0 || !self.is_valid(address, size_of::<T>() as u32) {
+            return None;
+        }
+        Some(&mut *(address as *mut T))
+    }
+}
+
+unsafe fn open(path: &[u8], flags: u32) -> i32 {
+    let id: i32 = FILES.open(path, flags);
+    if id < 0 {
+        return id;
+    }
+    let fd: i32 = TASK_MANAGER.open_file(Descriptor::File(id as usize));
+    if fd < 0 {
+        FILES.release(id as usize);
+    }
+    fd
+}
+
+unsafe fn pipe(fds: &mut [u32; 2]) -> i32 {
+    let id: i8 = PIPES.create();
+    if id < 0 {
+        return -ENFILE;
+    }
+    let reader: i32 = TASK_MANAGER.open_file(Descriptor::PipeReader(id as usize));
+    if reader < 0 {
+        PIPES.close_reader(id as usize);
+        PIPES.close_writer(id as usize);
+        return -EMFILE;
+    }
+    let writer: i32 = TASK_MANAGER.open_file(Descriptor::PipeWriter(id as usize));
+    if writer < 0 {
+        TASK_MANAGER.close_file(reader as usize);
+        PIPES.close_writer(id as usize);
+        return -EMFILE;
     }
+    fds[0] = reader as u32;
+    fds[1] = writer as u32;
+    0
 }
\ No newline at end of file
@@ -6,9 +6,10 @@
 
 use core::arch::asm;
 
-const VGA_WIDTH: u16 = 80;
-const VGA_HEIGHT: u16 = 25;
+pub const VGA_WIDTH: u16 = 80;
+pub const VGA_HEIGHT: u16 = 25;
 const VGA_START: u32 = 0x000B8000;
+const SCROLLBACK_LINES: usize = 256;
 #[allow(dead_code)]
 pub const COLOR_BLACK: u8 = 0x0;
 #[allow(dead_code)]
@@ -42,73 +43,195 @@ pub const COLOR_LIGHT_YELLOW: u8 = 0xE;
 #[allow(dead_code)]
 pub const COLOR_LIGHT_WHITE: u8 = 0xF;
 
-pub static mut PRINTER: Printer = Printer {
-    x: 0,
-    y: 0,
-    fg_color: COLOR_WHITE,
-    bg_color: COLOR_BLACK,
-};
-
+#[derive(Copy, Clone)]
 pub struct Printer {
+    cells: [u16; (VGA_WIDTH * VGA_HEIGHT) as usize],
+    history: [[u16; VGA_WIDTH as usize]; SCROLLBACK_LINES],
+    history_head: usize,
+    history_count: usize,
+    view: usize,
     x: u16,
     y: u16,
     fg_color: u8,
     bg_color: u8,
+    default_colors: (u8, u8),
+    visible: bool,
 }
 
 impl Printer {
+    pub const fn new() -> Self {
+        Printer {
+            cells: [(COLOR_WHITE as u16) << 8 | b' ' as u16; (VGA_WIDTH * VGA_HEIGHT) as usize],
+            history: [[0; VGA_WIDTH as usize]; SCROLLBACK_LINES],
+            history_head: 0,
+            history_count: 0,
+            view: 0,
+            x: 0,
+            y: 0,
+            fg_color: COLOR_WHITE,
+            bg_color: COLOR_BLACK,
+            default_colors: (COLOR_WHITE, COLOR_BLACK),
+            visible: false,
+        }
+    }
+
     pub fn printc(&mut self, c: char) {
         if c == '\n' {
             self.new_line();
             return;
         }
-        let target: *mut u8 = (VGA_START + ((self.y * VGA_WIDTH + self.x) * 2) as u32) as *mut u8;
-        unsafe {
-            if self.y >= VGA_HEIGHT - 1 && self.x >= VGA_WIDTH - 1 {
-                *target = c as u8;
-                *target.byte_add(1) = self.bg_color << 4 | self.fg_color;
-                self.scroll();
+        self.put(self.y * VGA_WIDTH + self.x, c as u8);
+        if self.y >= VGA_HEIGHT - 1 && self.x >= VGA_WIDTH - 1 {
+            self.scroll();
+            self.x = 0;
+        } else {
+            self.x += 1;
+            if self.x >= VGA_WIDTH {
                 self.x = 0;
-            } else {
-                *target = c as u8;
-                *target.byte_add(1) = self.bg_color << 4 | self.fg_color;
-                self.x += 1;
-                if self.x >= VGA_WIDTH {
-                    self.x = 0;
-                    self.y += 1;
-                }
+                self.y += 1;
             }
         }
         self.set_cursor_position();
     }
 
     pub fn prints(&mut self, s: &str) {
-        let cursor: (u16, u16) = self.get_cursor_position();
-        self.x = cursor.0;
-        self.y = cursor.1;
         for c in s.chars() {
             self.printc(c);
         }
         self.set_cursor_position();
     }
 
-    pub fn delete(&mut self) {
+    pub fn carriage_return(&mut self) {
+        self.x = 0;
+        self.set_cursor_position();
+    }
+
+    pub fn tab(&mut self) {
+        let next: u16 = ((self.x / 8 + 1) * 8).min(VGA_WIDTH - 1);
+        while self.x < next {
+            self.printc(' ');
+        }
+    }
+
+    pub fn back(&mut self) {
         if self.x > 0 {
             self.x -= 1;
         } else if self.y > 0 {
             self.y -= 1;
             self.x = VGA_WIDTH - 1;
-        } else {
-            return;
         }
-        let target: *mut u8 = (VGA_START + ((self.y * VGA_WIDTH + self.x) * 2) as u32) as *mut u8;
-        unsafe {
-            *target = b' ' as u8;
-            *target.byte_add(1) = self.bg_color << 4 | self.fg_color;
+        self.set_cursor_position();
+    }
+
+    pub fn move_to(&mut self, x: i32, y: i32) {
+        self.x = x.clamp(0, VGA_WIDTH as i32 - 1) as u16;
+        self.y = y.clamp(0, VGA_HEIGHT as i32 - 1) as u16;
+        self.set_cursor_position();
+    }
+
+    pub fn move_by(&mut self, dx: i32, dy: i32) {
+        self.move_to(self.x as i32 + dx, self.y as i32 + dy);
+    }
+
+    pub fn erase_line(&mut self, mode: u32) {
+        let (start, end): (u16, u16) = match mode {
+            0 => (self.x, VGA_WIDTH),
+            1 => (0, self.x + 1),
+            _ => (0, VGA_WIDTH),
+        };
+        self.erase(self.y * VGA_WIDTH + start, self.y * VGA_WIDTH + end);
+    }
+
+    pub fn erase_screen(&mut self, mode: u32) {
+        let index: u16 = self.y * VGA_WIDTH + self.x;
+        match mode {
+            0 => self.erase(index, VGA_WIDTH * VGA_HEIGHT),
+            1 => self.erase(0, index + 1),
+            _ => self.erase(0, VGA_WIDTH * VGA_HEIGHT),
         }
+    }
+
+    pub fn draw(&mut self, x: u16, y: u16, c: u8) {
+        self.put(y * VGA_WIDTH + x, c);
+    }
+
+    fn erase(&mut self, start: u16, end: u16) {
+        for i in start..end {
+            self.put(i, b' ');
+        }
+    }
+
+    fn put(&mut self, index: u16, c: u8) {
+        let cell: u16 = ((self.bg_color << 4 | self.fg_color) as u16) << 8 | c as u16;
+        self.cells[index as usize] = cell;
+        if self.visible && self.view == 0 {
+            unsafe {
+                *((VGA_START as *mut u16).add(index as usize)) = cell;
+            }
+        }
+    }
+
+    pub fn capture(&mut self) {
+        for (i, cell) in self.cells.iter_mut().enumerate() {
+            *cell = unsafe { *((VGA_START as *const u16).add(i)) };
+        }
+        let cursor: (u16, u16) = self.get_cursor_position();
+        self.x = cursor.0;
+        self.y = cursor.1;
+        self.visible = true;
+    }
+
+    pub fn show(&mut self) {
+        self.visible = true;
+        self.redraw();
         self.set_cursor_position();
     }
 
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn scroll_view(&mut self, lines: i32) {
+        let view: usize = (self.view as i32 + lines).clamp(0, self.history_count as i32) as usize;
+        if view != self.view {
+            self.view = view;
+            self.redraw();
+            self.set_cursor_position();
+        }
+    }
+
+    pub fn reset_view(&mut self) {
+        self.scroll_view(-(self.view as i32));
+    }
+
+    fn redraw(&self) {
+        if !self.visible {
+            return;
+        }
+        let width: usize = VGA_WIDTH as usize;
+        for row in 0..VGA_HEIGHT as usize {
+            let line: usize = self.history_count - self.view + row;
+            for column in 0..width {
+                let cell: u16 = if line < self.history_count {
+                    self.history[(self.history_head + line) % SCROLLBACK_LINES][column]
+                } else {
+                    self.cells[(line - self.history_count) * width + column]
+                };
+                unsafe {
+                    *((VGA_START as *mut u16).add(row * width + column)) = cell;
+                }
+            }
+        }
+    }
+
+    pub fn get_cursor(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+
+    pub fn get_colors(&self) -> (u8, u8) {
+        (self.fg_color, self.bg_color)
+    }
+
     pub fn get_cursor_position(&self) -> (u16, u16) {
         let mut index: u16 = 0;
         unsafe {
@@ -125,7 +248,10 @@ impl Printer {
     }
 
     pub fn set_cursor_position(&self) {
-        let index: u16 = self.y * VGA_WIDTH + self.x;
+        if !self.visible {
+            return;
+        }
+        let index: u16 = if self.view == 0 { self.y * VGA_WIDTH + self.x } else { VGA_WIDTH * VGA_HEIGHT };
         unsafe {
             asm!("out dx, al", in("dx") 0x3D4u16, in("al") 0x0Fu8);
             asm!("out dx, al", in("dx") 0x3D5u16, in("al") (index & 0xFF) as u8);
@@ -135,16 +261,22 @@ impl Printer {
     }
 
     pub fn scroll(&mut self) {
-        for i in 0..VGA_HEIGHT {
-            for j in (VGA_WIDTH * i)..((VGA_WIDTH * i) + VGA_WIDTH) {
-                let new: *mut u8 = (VGA_START + (j * 2) as u32) as *mut u8;
-                let old: *const u8 = (VGA_START + ((j + VGA_WIDTH) * 2) as u32) as *const u8;
-                unsafe {
-                    *new = *old;
-                    *new.byte_add(1) = *old.byte_add(1);
-                }
-            }
+        let size: usize = (VGA_WIDTH * VGA_HEIGHT) as usize;
+        let tail: usize = (self.history_head + self.history_count) % SCROLLBACK_LINES;
+        self.history[tail].copy_from_slice(&self.cells[..VGA_WIDTH as usize]);
+        if self.history_count < SCROLLBACK_LINES {
+            self.history_count += 1;
+        } else {
+            self.history_head = (self.history_head + 1) % SCROLLBACK_LINES;
+        }
+        if self.view > 0 {
+            self.view = (self.view + 1).min(self.history_count);
+        }
+        self.cells.copy_within(VGA_WIDTH as usize..size, 0);
+        for i in size - VGA_WIDTH as usize..size {
+            self.cells[i] = ((self.bg_color << 4 | self.fg_color) as u16) << 8 | b' ' as u16;
         }
+        self.redraw();
     }
 
     pub fn set_colors(&mut self, fg_color: u8, bg_color: u8) {
@@ -152,8 +284,17 @@ impl Printer {
         self.bg_color = bg_color;
     }
 
+    pub fn get_default_colors(&self) -> (u8, u8) {
+        self.default_colors
+    }
+
+    pub fn set_default_colors(&mut self, fg_color: u8, bg_color: u8) {
+        self.default_colors = (fg_color, bg_color);
+        self.reset_colors();
+    }
+
     pub fn reset_colors(&mut self) {
-        self.set_colors(COLOR_WHITE, COLOR_BLACK)
+        self.set_colors(self.default_colors.0, self.default_colors.1)
     }
 
     pub fn new_line(&mut self) {
@@ -169,13 +310,7 @@ impl Printer {
     pub fn clear(&mut self) {
         self.x = 0;
         self.y = 0;
-        for i in 0..(VGA_WIDTH * VGA_HEIGHT) {
-            let target: *mut u8 = (VGA_START + (i * 2) as u32) as *mut u8;
-            unsafe {
-                *target = b' ' as u8;
-                *target.byte_add(1) = self.bg_color << 4 | self.fg_color;
-            }
-        }
+        self.erase(0, VGA_WIDTH * VGA_HEIGHT);
         self.set_cursor_position();
     }
 }
\ No newline at end of file
@@ -4,6 +4,7 @@
 // Author:        Jishen Lin
 // License:       MIT License
 
+use crate::filesystem::config::CONFIG;
 use core::arch::asm;
 
 #[derive(Copy, Clone, Debug)]
@@ -35,6 +36,43 @@ impl Time {
         }
     }
 
+    pub fn local() -> Self {
+        Self::init().offset(unsafe { CONFIG.get_timezone() })
+    }
+
+    pub fn offset(&self, minutes: i32) -> Self {
+        let seconds: u64 = (self.timestamp as i64 + minutes as i64 * 60).max(0) as u64;
+        let mut days: u64 = seconds / 86400;
+        let mut year: u16 = 1970;
+        loop {
+            let length: u64 = if Self::is_leap_year(year) { 366 } else { 365 };
+            if days < length {
+                break;
+            }
+            days -= length;
+            year += 1;
+        }
+        let mut month: u8 = 1;
+        loop {
+            let length: u64 = Self::month_days(year, month) as u64;
+            if days < length {
+                break;
+            }
+            days -= length;
+            month += 1;
+        }
+        Time {
+            year,
+            month,
+            day: days as u8 + 1,
+            hour: (seconds % 86400 / 3600) as u8,
+            minute: (seconds % 3600 / 60) as u8,
+            second: (seconds % 60) as u8,
+            timestamp: self.timestamp,
+            ticks: self.ticks,
+        }
+    }
+
     pub fn get_year(&self) -> u16 {
         self.year
     }
@@ -127,13 +165,8 @@ impl Time {
         for y in 1970..year {
             days_since_epoch += if Self::is_leap_year(y) { 366 } else { 365 };
         }
-        let month_days: [i32; 13] = if Self::is_leap_year(year) {
-            [0, 31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-        } else {
-            [0, 31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-        };
         for m in 1..month {
-            days_since_epoch += month_days[m as usize] as u64;
+            days_since_epoch += Self::month_days(year, m) as u64;
         }
         days_since_epoch += day as u64 - 1;
         let hours_since_epoch: u64 = days_since_epoch * 24 + hour as u64;
@@ -160,6 +193,15 @@ impl Time {
         (bcd & 0x0F) + ((bcd >> 4) * 10)
     }
 
+    fn month_days(year: u16, month: u8) -> u8 {
+        match month {
+            2 if Self::is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
     fn is_leap_year(year: u16) -> bool {
         (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
     }
@@ -0,0 +1,60 @@
+// Project Name:  MinmusOS
+// File Name:     errno.rs
+// File Function: System call error numbers
+// Author:        Jishen Lin
+// License:       MIT License
+
+pub const EPERM: i32 = 1;
+pub const ENOENT: i32 = 2;
+pub const ESRCH: i32 = 3;
+pub const EINTR: i32 = 4;
+pub const EIO: i32 = 5;
+pub const E2BIG: i32 = 7;
+pub const EBADF: i32 = 9;
+pub const EAGAIN: i32 = 11;
+pub const ENOMEM: i32 = 12;
+pub const EACCES: i32 = 13;
+pub const EFAULT: i32 = 14;
+pub const EBUSY: i32 = 16;
+pub const EEXIST: i32 = 17;
+pub const ENOTDIR: i32 = 20;
+pub const EISDIR: i32 = 21;
+pub const EINVAL: i32 = 22;
+pub const ENFILE: i32 = 23;
+pub const EMFILE: i32 = 24;
+pub const ENOSPC: i32 = 28;
+pub const ESPIPE: i32 = 29;
+pub const EPIPE: i32 = 32;
+pub const ENAMETOOLONG: i32 = 36;
+pub const ENOSYS: i32 = 38;
+pub const ENOTEMPTY: i32 = 39;
+
+pub fn description(error: i32) -> &'static str {
+    match -error {
+        EPERM => "Operation not permitted",
+        ENOENT => "No such file or directory",
+        ESRCH => "No such task",
+        EINTR => "Interrupted system call",
+        EIO => "Input/output error",
+        E2BIG => "Argument too large",
+        EBADF => "Bad file descriptor",
+        EAGAIN => "Resource temporarily unavailable",
+        ENOMEM => "Out of memory",
+        EACCES => "Permission denied",
+        EFAULT => "Bad address",
+        EBUSY => "Device or resource busy",
+        EEXIST => "File exists",
+        ENOTDIR => "Not a directory",
+        EISDIR => "Is a directory",
+        EINVAL => "Invalid argument",
+        ENFILE => "Too many open files in system",
+        EMFILE => "Too many open files",
+        ENOSPC => "No space left on device",
+        ESPIPE => "Illegal seek",
+        EPIPE => "Broken pipe",
+        ENAMETOOLONG => "File name too long",
+        ENOSYS => "Function not implemented",
+        ENOTEMPTY => "Directory not empty",
+        _ => "Unknown error",
+    }
+}
\ No newline at end of file
@@ -0,0 +1,144 @@
+// Project Name:  MinmusOS
+// File Name:     fs.rs
+// File Function: Filesystem utils
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::syscall::{syscall1, syscall2, syscall3, syscall4, SYS_CHMOD, SYS_CHOWN, SYS_CLOSE, SYS_LSEEK, SYS_OPEN, SYS_READ, SYS_READDIR, SYS_STAT, SYS_UNLINK, SYS_WRITE};
+
+pub const O_RDONLY: u32 = 0x0000;
+pub const O_WRONLY: u32 = 0x0001;
+pub const O_RDWR: u32 = 0x0002;
+pub const O_CREAT: u32 = 0x0040;
+pub const O_TRUNC: u32 = 0x0200;
+pub const O_APPEND: u32 = 0x0400;
+pub const SEEK_SET: u32 = 0;
+pub const SEEK_CUR: u32 = 1;
+pub const SEEK_END: u32 = 2;
+pub const ATTRIBUTE_DIRECTORY: u32 = 0x10;
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct Stat {
+    pub size: u32,
+    pub attributes: u32,
+    pub cluster: u32,
+    pub modified_date: u32,
+    pub modified_time: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub mode: u32,
+}
+
+impl Stat {
+    pub const fn new() -> Self {
+        Stat {
+            size: 0,
+            attributes: 0,
+            cluster: 0,
+            modified_date: 0,
+            modified_time: 0,
+            uid: 0,
+            gid: 0,
+            mode: 0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct DirectoryEntry {
+    pub name: [u8; 12],
+    pub length: u32,
+    pub size: u32,
+    pub attributes: u32,
+}
+
+impl DirectoryEntry {
+    pub const fn new() -> Self {
+        DirectoryEntry {
+            name: [0; 12],
+            length: 0,
+            size: 0,
+            attributes: 0,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        core::str::from_utf8(&self.name[..(self.length as usize).min(12)]).unwrap_or("?")
+    }
+}
+
+pub struct File {
+    fd: u32,
+}
+
+impl File {
+    pub fn open(path: &str) -> Result<File, i32> {
+        Self::open_with(path, O_RDONLY)
+    }
+
+    pub fn create(path: &str) -> Result<File, i32> {
+        Self::open_with(path, O_WRONLY | O_CREAT | O_TRUNC)
+    }
+
+    pub fn open_with(path: &str, flags: u32) -> Result<File, i32> {
+        let fd: i32 = open(path, flags);
+        if fd < 0 {
+            return Err(fd);
+        }
+        Ok(File { fd: fd as u32 })
+    }
+
+    pub fn fd(&self) -> u32 {
+        self.fd
+    }
+
+    pub fn read(&self, buffer: &mut [u8]) -> i32 {
+        unsafe { syscall3(SYS_READ, self.fd, buffer.as_mut_ptr() as u32, buffer.len() as u32) }
+    }
+
+    pub fn write(&self, buffer: &[u8]) -> i32 {
+        unsafe { syscall3(SYS_WRITE, self.fd, buffer.as_ptr() as u32, buffer.len() as u32) }
+    }
+
+    pub fn seek(&self, offset: i32, whence: u32) -> i32 {
+        lseek(self.fd, offset, whence)
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        unsafe {
+            syscall1(SYS_CLOSE, self.fd);
+        }
+    }
+}
+
+pub fn open(path: &str, flags: u32) -> i32 {
+    unsafe { syscall3(SYS_OPEN, path.as_ptr() as u32, path.len() as u32, flags) }
+}
+
+pub fn lseek(fd: u32, offset: i32, whence: u32) -> i32 {
+    unsafe { syscall3(SYS_LSEEK, fd, offset as u32, whence) }
+}
+
+pub fn stat(path: &str, stat: &mut Stat) -> i32 {
+    unsafe { syscall3(SYS_STAT, path.as_ptr() as u32, path.len() as u32, stat as *mut Stat as u32) }
+}
+
+pub fn read_directory(cursor: u32, entry: &mut DirectoryEntry) -> i32 {
+    unsafe { syscall2(SYS_READDIR, cursor, entry as *mut DirectoryEntry as u32) }
+}
+
+pub fn unlink(path: &str) -> i32 {
+    unsafe { syscall2(SYS_UNLINK, path.as_ptr() as u32, path.len() as u32) }
+}
+
+pub fn chmod(path: &str, mode: u32) -> i32 {
+    unsafe { syscall3(SYS_CHMOD, path.as_ptr() as u32, path.len() as u32, mode) }
+}
+
+pub fn chown(path: &str, uid: u32, gid: u32) -> i32 {
+    unsafe { syscall4(SYS_CHOWN, path.as_ptr() as u32, path.len() as u32, uid, gid) }
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+// Project Name:  MinmusOS
+// File Name:     io.rs
+// File Function: File descriptor utils
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::syscall::{syscall1, syscall3, SYS_CLOSE, SYS_INPUT_MODE, SYS_PIPE, SYS_READ, SYS_WRITE};
+
+pub const STDIN: u32 = 0;
+pub const STDOUT: u32 = 1;
+pub const STDERR: u32 = 2;
+pub const MODE_COOKED: u32 = 0;
+pub const MODE_RAW: u32 = 1;
+
+pub fn read(fd: u32, buffer: &mut [u8]) -> i32 {
+    unsafe { syscall3(SYS_READ, fd, buffer.as_mut_ptr() as u32, buffer.len() as u32) }
+}
+
+pub fn write(fd: u32, buffer: &[u8]) -> i32 {
+    unsafe { syscall3(SYS_WRITE, fd, buffer.as_ptr() as u32, buffer.len() as u32) }
+}
+
+pub fn pipe(fds: &mut [u32; 2]) -> i32 {
+    unsafe { syscall1(SYS_PIPE, fds.as_mut_ptr() as u32) }
+}
+
+pub fn close(fd: u32) -> i32 {
+    unsafe { syscall1(SYS_CLOSE, fd) }
+}
+
+pub fn set_input_mode(mode: u32) -> i32 {
+    unsafe { syscall1(SYS_INPUT_MODE, mode) }
+}
+
+pub fn getchar() -> Option<u8> {
+    let mut c: [u8; 1] = [0];
+    if read(STDIN, &mut c) <= 0 {
+        return None;
+    }
+    Some(c[0])
+}
+
+pub fn read_line(buffer: &mut [u8]) -> Option<&str> {
+    let mut length: usize = 0;
+    loop {
+        match getchar() {
+            Some(b'\n') => break,
+            Some(c) => {
+                if length < buffer.len() {
+                    buffer[length] = c;
+                    length += 1;
+                }
+            }
+            None if length == 0 => return None,
+            None => break,
+        }
+    }
+    core::str::from_utf8(&buffer[..length]).ok()
+}
\ No newline at end of file
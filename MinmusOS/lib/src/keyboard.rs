@@ -0,0 +1,90 @@
+// Project Name:  MinmusOS
+// File Name:     keyboard.rs
+// File Function: Keyboard event utils
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::syscall::{syscall2, SYS_KEY_EVENT};
+
+pub const EVENT_PRESSED: u8 = 0x01;
+pub const EVENT_REPEAT: u8 = 0x02;
+pub const MOD_SHIFT: u8 = 0x01;
+pub const MOD_CTRL: u8 = 0x02;
+pub const MOD_ALT: u8 = 0x04;
+pub const MOD_ALT_GR: u8 = 0x08;
+pub const MOD_CAPS_LOCK: u8 = 0x10;
+pub const MOD_NUM_LOCK: u8 = 0x20;
+pub const MOD_SCROLL_LOCK: u8 = 0x40;
+pub const KEY_ESCAPE: u8 = 0x01;
+pub const KEY_BACKSPACE: u8 = 0x0E;
+pub const KEY_TAB: u8 = 0x0F;
+pub const KEY_ENTER: u8 = 0x1C;
+pub const KEY_LEFT_CTRL: u8 = 0x1D;
+pub const KEY_LEFT_SHIFT: u8 = 0x2A;
+pub const KEY_RIGHT_SHIFT: u8 = 0x36;
+pub const KEY_LEFT_ALT: u8 = 0x38;
+pub const KEY_SPACE: u8 = 0x39;
+pub const KEY_CAPS_LOCK: u8 = 0x3A;
+pub const KEY_F1: u8 = 0x3B;
+pub const KEY_F2: u8 = 0x3C;
+pub const KEY_F3: u8 = 0x3D;
+pub const KEY_F4: u8 = 0x3E;
+pub const KEY_F5: u8 = 0x3F;
+pub const KEY_F6: u8 = 0x40;
+pub const KEY_F7: u8 = 0x41;
+pub const KEY_F8: u8 = 0x42;
+pub const KEY_F9: u8 = 0x43;
+pub const KEY_F10: u8 = 0x44;
+pub const KEY_NUM_LOCK: u8 = 0x45;
+pub const KEY_SCROLL_LOCK: u8 = 0x46;
+pub const KEY_F11: u8 = 0x57;
+pub const KEY_F12: u8 = 0x58;
+pub const KEY_KEYPAD_ENTER: u8 = 0x9C;
+pub const KEY_RIGHT_CTRL: u8 = 0x9D;
+pub const KEY_RIGHT_ALT: u8 = 0xB8;
+pub const KEY_HOME: u8 = 0xC7;
+pub const KEY_UP: u8 = 0xC8;
+pub const KEY_PAGE_UP: u8 = 0xC9;
+pub const KEY_LEFT: u8 = 0xCB;
+pub const KEY_RIGHT: u8 = 0xCD;
+pub const KEY_END: u8 = 0xCF;
+pub const KEY_DOWN: u8 = 0xD0;
+pub const KEY_PAGE_DOWN: u8 = 0xD1;
+pub const KEY_INSERT: u8 = 0xD2;
+pub const KEY_DELETE: u8 = 0xD3;
+
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(C)]
+pub struct KeyEvent {
+    pub key: u8,
+    pub character: u8,
+    pub modifiers: u8,
+    pub flags: u8,
+}
+
+impl KeyEvent {
+    pub fn is_pressed(&self) -> bool {
+        self.flags & EVENT_PRESSED != 0
+    }
+
+    pub fn is_repeat(&self) -> bool {
+        self.flags & EVENT_REPEAT != 0
+    }
+}
+
+pub fn read_event() -> Option<KeyEvent> {
+    next_event(true)
+}
+
+pub fn poll_event() -> Option<KeyEvent> {
+    next_event(false)
+}
+
+fn next_event(wait: bool) -> Option<KeyEvent> {
+    let mut event: KeyEvent = KeyEvent::default();
+    let result: i32 = unsafe { syscall2(SYS_KEY_EVENT, &mut event as *mut KeyEvent as u32, wait as u32) };
+    if result < 0 {
+        return None;
+    }
+    Some(event)
+}
\ No newline at end of file
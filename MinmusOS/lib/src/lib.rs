@@ -6,9 +6,21 @@
 
 #![no_std]
 
+pub mod errno;
+pub mod fs;
+pub mod io;
+pub mod keyboard;
 pub mod math;
+pub mod message;
+pub mod mouse;
 pub mod mutex;
 pub mod print;
+pub mod process;
 pub mod rand;
+pub mod shm;
+pub mod signal;
 pub mod sort;
-pub mod string;
\ No newline at end of file
+pub mod string;
+pub mod syscall;
+pub mod thread;
+pub mod tty;
\ No newline at end of file
@@ -0,0 +1,63 @@
+// Project Name:  MinmusOS
+// File Name:     message.rs
+// File Function: Message-passing utils
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::syscall::{syscall2, SYS_CALL, SYS_RECEIVE, SYS_REPLY, SYS_SEND, SYS_TRY_RECEIVE, SYS_TRY_SEND};
+
+pub const MAX_MESSAGE_SIZE: usize = 64;
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct Message {
+    pub sender: u32,
+    pub length: u32,
+    pub data: [u8; MAX_MESSAGE_SIZE],
+}
+
+impl Message {
+    pub const fn new() -> Self {
+        Message {
+            sender: 0,
+            length: 0,
+            data: [0; MAX_MESSAGE_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut message: Message = Message::new();
+        let length: usize = bytes.len().min(MAX_MESSAGE_SIZE);
+        message.data[..length].copy_from_slice(&bytes[..length]);
+        message.length = length as u32;
+        message
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data[..(self.length as usize).min(MAX_MESSAGE_SIZE)]
+    }
+}
+
+pub fn send(pid: u32, message: &Message) -> i32 {
+    unsafe { syscall2(SYS_SEND, pid, message as *const Message as u32) }
+}
+
+pub fn try_send(pid: u32, message: &Message) -> i32 {
+    unsafe { syscall2(SYS_TRY_SEND, pid, message as *const Message as u32) }
+}
+
+pub fn receive(message: &mut Message) -> i32 {
+    unsafe { syscall2(SYS_RECEIVE, message as *mut Message as u32, 0) }
+}
+
+pub fn try_receive(message: &mut Message) -> i32 {
+    unsafe { syscall2(SYS_TRY_RECEIVE, message as *mut Message as u32, 0) }
+}
+
+pub fn call(pid: u32, message: &mut Message) -> i32 {
+    unsafe { syscall2(SYS_CALL, pid, message as *mut Message as u32) }
+}
+
+pub fn reply(pid: u32, message: &Message) -> i32 {
+    unsafe { syscall2(SYS_REPLY, pid, message as *const Message as u32) }
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+// Project Name:  MinmusOS
+// File Name:     mouse.rs
+// File Function: Mouse event utils
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::syscall::{syscall2, SYS_MOUSE_EVENT};
+
+pub const BUTTON_LEFT: u8 = 0x01;
+pub const BUTTON_RIGHT: u8 = 0x02;
+pub const BUTTON_MIDDLE: u8 = 0x04;
+
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(C)]
+pub struct MouseEvent {
+    pub x: u16,
+    pub y: u16,
+    pub dx: i16,
+    pub dy: i16,
+    pub wheel: i8,
+    pub buttons: u8,
+}
+
+impl MouseEvent {
+    pub fn is_pressed(&self, button: u8) -> bool {
+        self.buttons & button != 0
+    }
+}
+
+pub fn read_event() -> Option<MouseEvent> {
+    next_event(true)
+}
+
+pub fn poll_event() -> Option<MouseEvent> {
+    next_event(false)
+}
+
+fn next_event(wait: bool) -> Option<MouseEvent> {
+    let mut event: MouseEvent = MouseEvent::default();
+    let result: i32 = unsafe { syscall2(SYS_MOUSE_EVENT, &mut event as *mut MouseEvent as u32, wait as u32) };
+    if result < 0 {
+        return None;
+    }
+    Some(event)
+}
\ No newline at end of file
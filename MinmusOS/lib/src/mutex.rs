@@ -20,17 +20,19 @@ impl<T> Mutex<T> {
     }
 
     pub fn acquire_mut(&mut self) -> &mut T {
-        while !self.free.load(Ordering::SeqCst) {}
-        self.free.store(false, Ordering::SeqCst);
+        self.lock();
         &mut self.target
     }
 
     pub fn acquire(&mut self) -> &T {
-        while !self.free.load(Ordering::SeqCst) {}
-        self.free.store(false, Ordering::SeqCst);
+        self.lock();
         &self.target
     }
 
+    fn lock(&self) {
+        while self.free.compare_exchange(true, false, Ordering::SeqCst, Ordering::SeqCst).is_err() {}
+    }
+
     pub fn free(&self) {
         self.free.store(true, Ordering::SeqCst);
     }
@@ -4,12 +4,17 @@
 // Author:        Jishen Lin
 // License:       MIT License
 
+use crate::io::{write, STDERR};
+use crate::syscall::{syscall2, SYS_PRINT};
 use core::fmt;
 
 pub static mut PRINTER: Printer = Printer {};
+pub static mut ERROR_PRINTER: ErrorPrinter = ErrorPrinter {};
 
 pub struct Printer {}
 
+pub struct ErrorPrinter {}
+
 impl fmt::Write for Printer {
     fn write_str(&mut self, s: &str) -> fmt::Result {
         self.prints(s);
@@ -20,24 +25,24 @@ impl fmt::Write for Printer {
 impl Printer {
     pub fn prints(&self, s: &str) {
         unsafe {
-            let ptr: *const u8 = s.as_ptr();
-            let len: usize = s.len();
-            core::arch::asm!(
-            "push eax",
-            "push ebx",
-            "push ecx",
-            "int 0x80",
-            "pop ecx",
-            "pop ebx",
-            "pop eax",
-            in("eax") 0,
-            in("ebx") ptr as u32,
-            in("ecx") len as u32,
-            );
+            syscall2(SYS_PRINT, s.as_ptr() as u32, s.len() as u32);
         }
     }
 }
 
+impl fmt::Write for ErrorPrinter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.prints(s);
+        Ok(())
+    }
+}
+
+impl ErrorPrinter {
+    pub fn prints(&self, s: &str) {
+        write(STDERR, s.as_bytes());
+    }
+}
+
 pub fn _print(args: fmt::Arguments) {
     use core::fmt::Write;
     unsafe {
@@ -64,4 +69,32 @@ macro_rules! println {
             $crate::print::PRINTER.prints("\n");
         }
     };
+}
+
+pub fn _eprint(args: fmt::Arguments) {
+    use core::fmt::Write;
+    unsafe {
+        ERROR_PRINTER.write_fmt(args).unwrap();
+    }
+}
+
+#[macro_export]
+macro_rules! eprint {
+    ($($arg:tt)*) => ($crate::print::_eprint(format_args!($($arg)*)));
+}
+
+#[macro_export]
+macro_rules! eprintln {
+    () => {
+        unsafe {
+            $crate::print::ERROR_PRINTER.prints("\n");
+        }
+    };
+
+    ($($arg:tt)*) => {
+        $crate::eprint!("{}", format_args!($($arg)*));
+        unsafe {
+            $crate::print::ERROR_PRINTER.prints("\n");
+        }
+    };
 }
\ No newline at end of file
@@ -0,0 +1,26 @@
+// Project Name:  MinmusOS
+// File Name:     process.rs
+// File Function: Process utils
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::syscall::{syscall0, syscall2, SYS_EXIT, SYS_GETGID, SYS_GETUID, SYS_SETUID};
+
+pub fn exit() -> ! {
+    unsafe {
+        syscall0(SYS_EXIT);
+    }
+    loop {}
+}
+
+pub fn getuid() -> u32 {
+    unsafe { syscall0(SYS_GETUID) as u32 }
+}
+
+pub fn getgid() -> u32 {
+    unsafe { syscall0(SYS_GETGID) as u32 }
+}
+
+pub fn setuid(uid: u32, gid: u32) -> i32 {
+    unsafe { syscall2(SYS_SETUID, uid, gid) }
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+// Project Name:  MinmusOS
+// File Name:     shm.rs
+// File Function: Shared memory utils
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::syscall::{syscall1, SYS_SHM_MAP, SYS_SHM_UNMAP};
+
+pub const SHARED_WINDOW: u32 = 0x02400000;
+pub const MAX_NAME_LENGTH: usize = 16;
+
+#[repr(C)]
+pub struct SharedMemory {
+    pub name: [u8; MAX_NAME_LENGTH],
+    pub size: u32,
+    pub address: u32,
+}
+
+pub fn map(name: &str, size: u32, address: u32) -> Option<&'static mut [u8]> {
+    let mut request: SharedMemory = SharedMemory {
+        name: [0; MAX_NAME_LENGTH],
+        size,
+        address,
+    };
+    let length: usize = name.len().min(MAX_NAME_LENGTH);
+    request.name[..length].copy_from_slice(&name.as_bytes()[..length]);
+    let result: i32 = unsafe { syscall1(SYS_SHM_MAP, &mut request as *mut SharedMemory as u32) };
+    if result < 0 {
+        return None;
+    }
+    unsafe { Some(core::slice::from_raw_parts_mut(request.address as *mut u8, request.size as usize)) }
+}
+
+pub fn unmap(region: &mut [u8]) -> i32 {
+    unsafe { syscall1(SYS_SHM_UNMAP, region.as_ptr() as u32) }
+}
\ No newline at end of file
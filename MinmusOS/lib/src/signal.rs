@@ -0,0 +1,30 @@
+// Project Name:  MinmusOS
+// File Name:     signal.rs
+// File Function: Signal utils
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::syscall::{syscall2, SYS_KILL, SYS_SIGNAL};
+
+pub const SIGINT: u32 = 2;
+pub const SIGKILL: u32 = 9;
+pub const SIGSEGV: u32 = 11;
+pub const SIGTERM: u32 = 15;
+pub const SIGCHLD: u32 = 17;
+pub const SIGCONT: u32 = 18;
+pub const SIGSTOP: u32 = 19;
+pub const SIGTSTP: u32 = 20;
+pub const SIG_DFL: u32 = 0;
+pub const SIG_IGN: u32 = 1;
+
+pub fn signal(signal: u32, handler: extern "C" fn(u32)) -> i32 {
+    set_handler(signal, handler as u32)
+}
+
+pub fn set_handler(signal: u32, handler: u32) -> i32 {
+    unsafe { syscall2(SYS_SIGNAL, signal, handler) }
+}
+
+pub fn kill(pid: u32, signal: u32) -> i32 {
+    unsafe { syscall2(SYS_KILL, pid, signal) }
+}
\ No newline at end of file
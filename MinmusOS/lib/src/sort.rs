@@ -29,11 +29,15 @@ pub fn selection_sort(arr: &mut [i32]) {
 }
 
 pub fn insertion_sort(arr: &mut [i32]) {
+    insertion_sort_by(arr, |a: &i32, b: &i32| a > b);
+}
+
+pub fn insertion_sort_by<T: Copy, F: FnMut(&T, &T) -> bool>(arr: &mut [T], mut greater: F) {
     let n: usize = arr.len();
     for i in 1..n {
-        let key: i32 = arr[i];
+        let key: T = arr[i];
         let mut j: isize = i as isize - 1;
-        while j >= 0 && arr[j as usize] > key {
+        while j >= 0 && greater(&arr[j as usize], &key) {
             arr[(j + 1) as usize] = arr[j as usize];
             j -= 1;
         }
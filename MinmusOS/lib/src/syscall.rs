@@ -0,0 +1,107 @@
+// Project Name:  MinmusOS
+// File Name:     syscall.rs
+// File Function: System call interface
+// Author:        Jishen Lin
+// License:       MIT License
+
+// Arguments are passed in ebx, ecx, edx, esi, edi and ebp; the result is returned in eax.
+// Negative results are errno values from errno.rs.
+pub const SYS_PRINT: u32 = 0; // (buffer, length) -> written
+pub const SYS_EXIT: u32 = 1; // () -> never returns
+pub const SYS_READ: u32 = 2; // (fd, buffer, length) -> read
+pub const SYS_WRITE: u32 = 3; // (fd, buffer, length) -> written
+pub const SYS_PIPE: u32 = 4; // (fds: *mut [u32; 2]) -> 0
+pub const SYS_CLOSE: u32 = 5; // (fd) -> 0
+pub const SYS_SEND: u32 = 6; // (pid, message) -> 0
+pub const SYS_TRY_SEND: u32 = 7; // (pid, message) -> 0
+pub const SYS_RECEIVE: u32 = 8; // (message) -> 0
+pub const SYS_TRY_RECEIVE: u32 = 9; // (message) -> 0
+pub const SYS_CALL: u32 = 10; // (pid, message) -> 0
+pub const SYS_REPLY: u32 = 11; // (pid, message) -> 0
+pub const SYS_SIGNAL: u32 = 12; // (signal, handler) -> previous handler
+pub const SYS_KILL: u32 = 13; // (pid, signal) -> 0
+pub const SYS_SHM_MAP: u32 = 14; // (request: *mut SharedMemory) -> 0
+pub const SYS_SHM_UNMAP: u32 = 15; // (address) -> 0
+pub const SYS_THREAD_SPAWN: u32 = 16; // (entry, argument) -> tid
+pub const SYS_THREAD_JOIN: u32 = 17; // (tid) -> 0
+pub const SYS_OPEN: u32 = 18; // (path, length, flags) -> fd
+pub const SYS_LSEEK: u32 = 19; // (fd, offset, whence) -> position
+pub const SYS_STAT: u32 = 20; // (path, length, stat: *mut Stat) -> 0
+pub const SYS_READDIR: u32 = 21; // (cursor, entry: *mut DirectoryEntry) -> next cursor, 0 at the end
+pub const SYS_UNLINK: u32 = 22; // (path, length) -> 0
+pub const SYS_INPUT_MODE: u32 = 23; // (mode) -> previous mode
+pub const SYS_TTY_GET: u32 = 24; // (attributes: *mut TtyAttributes) -> 0
+pub const SYS_TTY_SET: u32 = 25; // (attributes: *const TtyAttributes) -> 0
+pub const SYS_KEY_EVENT: u32 = 26; // (event: *mut KeyEvent, wait) -> 0
+pub const SYS_MOUSE_EVENT: u32 = 27; // (event: *mut MouseEvent, wait) -> 0
+pub const SYS_CHMOD: u32 = 28; // (path, length, mode) -> 0
+pub const SYS_CHOWN: u32 = 29; // (path, length, uid, gid) -> 0
+pub const SYS_GETUID: u32 = 30; // () -> uid
+pub const SYS_GETGID: u32 = 31; // () -> gid
+pub const SYS_SETUID: u32 = 32; // (uid, gid) -> 0, root only
+
+/// # Safety
+///
+/// `number` must be a system call that takes no arguments.
+pub unsafe fn syscall0(number: u32) -> i32 {
+    syscall6(number, 0, 0, 0, 0, 0, 0)
+}
+
+/// # Safety
+///
+/// `number` must be a valid system call, and every argument it treats as a pointer must be valid for the access it performs.
+pub unsafe fn syscall1(number: u32, a: u32) -> i32 {
+    syscall6(number, a, 0, 0, 0, 0, 0)
+}
+
+/// # Safety
+///
+/// `number` must be a valid system call, and every argument it treats as a pointer must be valid for the access it performs.
+pub unsafe fn syscall2(number: u32, a: u32, b: u32) -> i32 {
+    syscall6(number, a, b, 0, 0, 0, 0)
+}
+
+/// # Safety
+///
+/// `number` must be a valid system call, and every argument it treats as a pointer must be valid for the access it performs.
+pub unsafe fn syscall3(number: u32, a: u32, b: u32, c: u32) -> i32 {
+    syscall6(number, a, b, c, 0, 0, 0)
+}
+
+/// # Safety
+///
+/// `number` must be a valid system call, and every argument it treats as a pointer must be valid for the access it performs.
+pub unsafe fn syscall4(number: u32, a: u32, b: u32, c: u32, d: u32) -> i32 {
+    syscall6(number, a, b, c, d, 0, 0)
+}
+
+/// # Safety
+///
+/// `number` must be a valid system call, and every argument it treats as a pointer must be valid for the access it performs.
+pub unsafe fn syscall5(number: u32, a: u32, b: u32, c: u32, d: u32, e: u32) -> i32 {
+    syscall6(number, a, b, c, d, e, 0)
+}
+
+/// # Safety
+///
+/// `number` must be a valid system call, and every argument it treats as a pointer must be valid for the access it performs.
+pub unsafe fn syscall6(number: u32, a: u32, b: u32, c: u32, d: u32, e: u32, f: u32) -> i32 {
+    let result: i32;
+    let rest: [u32; 3] = [d, e, f];
+    core::arch::asm!(
+    "push ebp",
+    "push esi",
+    "mov esi, [edi]",
+    "mov ebp, [edi + 8]",
+    "mov edi, [edi + 4]",
+    "int 0x80",
+    "pop esi",
+    "pop ebp",
+    inlateout("eax") number => result,
+    in("ebx") a,
+    in("ecx") b,
+    in("edx") c,
+    inlateout("edi") rest.as_ptr() => _,
+    );
+    result
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+// Project Name:  MinmusOS
+// File Name:     thread.rs
+// File Function: Thread utils
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::syscall::{syscall1, syscall2, SYS_THREAD_JOIN, SYS_THREAD_SPAWN};
+use core::mem::ManuallyDrop;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+pub struct JoinHandle {
+    id: u32,
+}
+
+impl JoinHandle {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn join(self) -> i32 {
+        join(self.id)
+    }
+}
+
+struct Start<F> {
+    closure: ManuallyDrop<F>,
+    taken: AtomicBool,
+}
+
+pub fn spawn<F: FnOnce() + Send + 'static>(f: F) -> Option<JoinHandle> {
+    let start: Start<F> = Start {
+        closure: ManuallyDrop::new(f),
+        taken: AtomicBool::new(false),
+    };
+    let id: i32 = thread_spawn(entry::<F> as extern "C" fn(u32) as u32, &start as *const Start<F> as u32);
+    if id < 0 {
+        drop(ManuallyDrop::into_inner(start.closure));
+        return None;
+    }
+    while !start.taken.load(Ordering::SeqCst) {
+        core::hint::spin_loop();
+    }
+    Some(JoinHandle { id: id as u32 })
+}
+
+extern "C" fn entry<F: FnOnce()>(argument: u32) {
+    let start: &Start<F> = unsafe { &*(argument as *const Start<F>) };
+    let f: F = unsafe { core::ptr::read(&*start.closure) };
+    start.taken.store(true, Ordering::SeqCst);
+    f();
+}
+
+pub fn thread_spawn(entry: u32, argument: u32) -> i32 {
+    unsafe { syscall2(SYS_THREAD_SPAWN, entry, argument) }
+}
+
+pub fn join(id: u32) -> i32 {
+    unsafe { syscall1(SYS_THREAD_JOIN, id) }
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+// Project Name:  MinmusOS
+// File Name:     tty.rs
+// File Function: Terminal utils
+// Author:        Jishen Lin
+// License:       MIT License
+
+use crate::print;
+use crate::syscall::{syscall1, SYS_TTY_GET, SYS_TTY_SET};
+
+pub const ECHO: u32 = 0x0001;
+pub const ICANON: u32 = 0x0002;
+pub const ISIG: u32 = 0x0004;
+pub const BLACK: u8 = 0;
+pub const RED: u8 = 1;
+pub const GREEN: u8 = 2;
+pub const YELLOW: u8 = 3;
+pub const BLUE: u8 = 4;
+pub const MAGENTA: u8 = 5;
+pub const CYAN: u8 = 6;
+pub const WHITE: u8 = 7;
+
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(C)]
+pub struct TtyAttributes {
+    pub flags: u32,
+    pub columns: u32,
+    pub rows: u32,
+}
+
+pub fn get_attributes() -> Option<TtyAttributes> {
+    let mut attributes: TtyAttributes = TtyAttributes::default();
+    let result: i32 = unsafe { syscall1(SYS_TTY_GET, &mut attributes as *mut TtyAttributes as u32) };
+    if result < 0 {
+        return None;
+    }
+    Some(attributes)
+}
+
+pub fn set_attributes(attributes: &TtyAttributes) -> i32 {
+    unsafe { syscall1(SYS_TTY_SET, attributes as *const TtyAttributes as u32) }
+}
+
+pub fn is_echo() -> bool {
+    get_attributes().map_or(false, |a| a.flags & ECHO != 0)
+}
+
+pub fn is_canonical() -> bool {
+    get_attributes().map_or(false, |a| a.flags & ICANON != 0)
+}
+
+pub fn move_to(row: u32, column: u32) {
+    print!("\x1B[{};{}H", row + 1, column + 1);
+}
+
+pub fn clear_screen() {
+    print!("\x1B[2J\x1B[H");
+}
+
+pub fn clear_line() {
+    print!("\r\x1B[2K");
+}
+
+pub fn set_color(fg_color: u8, bg_color: u8) {
+    print!("\x1B[{};{}m", 30 + (fg_color & 0x7) as u32 + if fg_color & 0x8 != 0 { 60 } else { 0 }, 40 + (bg_color & 0x7) as u32);
+}
+
+pub fn reset() {
+    print!("\x1B[0m");
+}
\ No newline at end of file